@@ -2,25 +2,175 @@
 // src/app.rs
 // ============================================================================
 
-use crossterm::event::KeyCode;
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::widgets::ListState;
-use std::{collections::BTreeMap, fs, io, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    time::{Duration, Instant, SystemTime},
+};
 
-use crate::commands;
-use crate::monitor::{Monitor, MonitorConfig};
+use crate::commands::{self, HyprCommand};
+use crate::config::{Config, MonitorOrder};
+use crate::events::Event;
+use crate::history::CommandHistory;
+use crate::hooks;
+use crate::hyprconf;
+use crate::i18n::{self, tr, tr_args};
+use crate::input::{InputOutcome, InputPurpose, TextInput};
+use crate::layout_log::{self, LayoutChange};
+use crate::lid::{self, LidRule};
+use crate::main_monitor;
+use crate::message::{MessageQueue, Severity};
+use crate::modeline::Modeline;
+use crate::monitor::{
+    self, AppliedMode, ColorMode, MirrorFit, Monitor, MonitorConfig, Rate, Workspace,
+    WorkspaceWindows,
+};
+use crate::palette::{Action, Palette};
+use crate::picker::ModePicker;
+use crate::presets::{MonitorPreset, Preset, PresetStep};
+use crate::profile::{self, Profile, ProfileMonitor};
+use crate::seen::SeenMonitors;
+use crate::setup::{self, Arrangement};
+use crate::state::State;
+use crate::svg;
+use crate::template::Template;
+use crate::theme::ThemeName;
+use crate::version::{Feature, HyprVersion};
+use crate::wallpaper::{self, Wallpapers};
+use crate::xwayland;
 
-const SCALE_STEP: i32 = 25;
-const MIN_SCALE: i32 = 50;
-pub const OPTION_COUNT: usize = 11;
+const IDENTIFY_DURATION_MS: u32 = 4000;
+const TARGET_LOGICAL_DPI: f64 = 110.0;
+const RESERVED_STEP: u32 = 5;
+/// How long live mode waits after the last change before applying it.
+const LIVE_DEBOUNCE: Duration = Duration::from_millis(300);
+/// How long a live change stays applied without being kept.
+const REVERT_TIMEOUT: Duration = Duration::from_secs(15);
+/// How often the config file is checked for edits.
+const CONFIG_POLL: Duration = Duration::from_secs(1);
+/// How long a just-connected monitor stays highlighted in the list.
+const NEW_MONITOR_HIGHLIGHT: Duration = Duration::from_secs(10);
+/// Offered for virtual outputs, which advertise no modes of their own.
+const VIRTUAL_MODES: [&str; 5] = [
+    "1280x720",
+    "1920x1080",
+    "2560x1440",
+    "2560x1600",
+    "3840x2160",
+];
+pub const OPTION_COUNT: usize = 27;
 
-const APPLY_OPTION_IDX: usize = 3;
-const SET_MAIN_IDX: usize = 4;
-const EXTEND_LEFT_IDX: usize = 5;
-const EXTEND_RIGHT_IDX: usize = 6;
-const MIRROR_IDX: usize = 7;
-const BLACK_SCREEN_IDX: usize = 8;
-const SAVE_OPTION_IDX: usize = 9;
-const DISABLE_OPTION_IDX: usize = 10;
+/// Non-selectable header rows in the options pane, with their label keys.
+pub const OPTION_SECTIONS: [(usize, &str); 4] = [
+    (0, "sections.mode"),
+    (11, "sections.layout"),
+    (17, "sections.power"),
+    (23, "sections.persistence"),
+];
+
+const RESOLUTION_IDX: usize = 1;
+const REFRESH_RATE_IDX: usize = 2;
+const SCALE_IDX: usize = 3;
+const COLOR_MODE_IDX: usize = 4;
+const SUGGEST_SCALE_IDX: usize = 5;
+/// Four consecutive rows: top, bottom, left, right.
+const RESERVED_TOP_IDX: usize = 6;
+const RESERVED_RIGHT_IDX: usize = 9;
+const APPLY_OPTION_IDX: usize = 10;
+const POSITION_IDX: usize = 12;
+const SET_MAIN_IDX: usize = 13;
+const EXTEND_LEFT_IDX: usize = 14;
+const EXTEND_RIGHT_IDX: usize = 15;
+const MIRROR_IDX: usize = 16;
+const GAMING_MODE_IDX: usize = 18;
+const DESKTOP_MODE_IDX: usize = 19;
+const BLACK_SCREEN_IDX: usize = 20;
+const LID_RULE_IDX: usize = 21;
+const DISABLE_OPTION_IDX: usize = 22;
+const PERSIST_IDX: usize = 24;
+const SAVE_DISABLED_IDX: usize = 25;
+const SAVE_OPTION_IDX: usize = 26;
+
+pub fn is_option_header(idx: usize) -> bool {
+    OPTION_SECTIONS.iter().any(|&(header, _)| header == idx)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tab {
+    Monitors,
+    Workspaces,
+    Profiles,
+    Log,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 4] = [Tab::Monitors, Tab::Workspaces, Tab::Profiles, Tab::Log];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Monitors => tr("tabs.monitors"),
+            Tab::Workspaces => tr("tabs.workspaces"),
+            Tab::Profiles => tr("tabs.profiles"),
+            Tab::Log => tr("tabs.log"),
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&tab| tab == self).unwrap_or(0)
+    }
+}
+
+/// A move within a list, shared by every list view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Motion {
+    Next,
+    Previous,
+    First,
+    Last,
+}
+
+impl Motion {
+    /// j/k and the arrow keys step; g/G and Home/End jump to either end.
+    pub fn from_key(code: KeyCode) -> Option<Self> {
+        match code {
+            KeyCode::Char('j') | KeyCode::Down => Some(Motion::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(Motion::Previous),
+            KeyCode::Char('g') | KeyCode::Home => Some(Motion::First),
+            KeyCode::Char('G') | KeyCode::End => Some(Motion::Last),
+            _ => None,
+        }
+    }
+
+    /// Like `from_key`, for lists with a text filter where letters are typed
+    /// rather than used to move.
+    pub fn from_filter_key(code: KeyCode) -> Option<Self> {
+        match code {
+            KeyCode::Down | KeyCode::Up | KeyCode::Home | KeyCode::End => Self::from_key(code),
+            _ => None,
+        }
+    }
+
+    pub fn is_forward(&self) -> bool {
+        matches!(self, Motion::Next | Motion::First)
+    }
+
+    /// The selection in a list of `len` entries after this move. Stepping
+    /// wraps around at either end.
+    pub fn apply(self, current: Option<usize>, len: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        Some(match (self, current) {
+            (Motion::First, _) | (_, None) => 0,
+            (Motion::Last, _) => len - 1,
+            (Motion::Next, Some(i)) => (i + 1) % len,
+            (Motion::Previous, Some(i)) => (i + len - 1) % len,
+        })
+    }
+}
 
 #[derive(PartialEq)]
 pub enum FocusedPane {
@@ -28,31 +178,692 @@ pub enum FocusedPane {
     Options,
 }
 
+/// Modal overlays that take over keyboard input while open.
+pub enum Popup {
+    ModePicker(ModePicker),
+    Palette(Palette),
+    TextInput(TextInput),
+    Presets(ListState),
+    DisableConfirm(DisableConfirm),
+    Details(Details),
+    Cleanup(Cleanup),
+    Template(TemplateChooser),
+    Setup(SetupWizard),
+    Reconcile(Reconcile),
+    ShrinkConfirm(ShrinkConfirm),
+    /// The change queue, with the selected command.
+    Review(ListState),
+    ReloadConfirm(ReloadConfirm),
+}
+
+/// Label keys of the choices offered before disabling a monitor that still
+/// has windows.
+pub const DISABLE_CHOICES: [&str; 3] = [
+    "popups.disable_move",
+    "popups.disable_anyway",
+    "popups.cancel",
+];
+
+/// A change made in live mode: applied once edits settle, then reverted to
+/// `previous` unless the user keeps it before `revert_at`.
+pub struct LiveChange {
+    /// By name, so a hotplug in the meantime can't move it to another output.
+    pub monitor: String,
+    pub previous: MonitorConfig,
+    pub apply_at: Option<Instant>,
+    pub revert_at: Option<Instant>,
+}
+
+/// Label keys of the choices offered after a config reload changed monitors
+/// that were set up from here.
+pub const RELOAD_CHOICES: [&str; 2] = ["popups.reload_reapply", "popups.reload_keep"];
+
+/// Monitors a config reload took back to what the config files say.
+pub struct ReloadConfirm {
+    pub monitors: Vec<String>,
+    pub state: ListState,
+}
+
+/// A command staged for the next batch, and the monitor it changes.
+pub struct QueuedChange {
+    pub monitor: String,
+    pub command: HyprCommand,
+    /// The mode the monitor runs once the command succeeds, for its
+    /// `monitor` rule.
+    pub applied: Option<AppliedMode>,
+}
+
+/// Label keys of the choices offered before applying a much smaller mode to
+/// a monitor that has windows.
+pub const SHRINK_CHOICES: [&str; 2] = ["popups.shrink_apply", "popups.cancel"];
+
+/// Applying a mode is confirmed first when it leaves the monitor less than
+/// this share of its current logical area.
+const SHRINK_WARNING_RATIO: f64 = 0.75;
+
+pub struct ShrinkConfirm {
+    pub monitor: usize,
+    pub workspaces: Vec<WorkspaceWindows>,
+    /// The new logical area as a share of the current one.
+    pub ratio: f64,
+    pub state: ListState,
+}
+
+pub struct DisableConfirm {
+    pub monitor: usize,
+    pub workspaces: Vec<WorkspaceWindows>,
+    /// Monitor the workspaces are moved to.
+    pub target: Option<usize>,
+    pub state: ListState,
+}
+
+/// Saved monitors that haven't been connected for a long time, offered for
+/// removal when saving.
+pub struct Cleanup {
+    pub names: Vec<String>,
+    /// Whether each of `names` is left out of the saved file.
+    pub prune: Vec<bool>,
+    pub state: ListState,
+}
+
+/// Picking a layout template, then the monitor for each of its slots.
+pub struct TemplateChooser {
+    pub template: Option<Template>,
+    /// Monitors placed so far, in slot order.
+    pub chosen: Vec<usize>,
+    pub state: ListState,
+}
+
+impl TemplateChooser {
+    /// Active monitors that aren't placed yet, in list order.
+    pub fn candidates(&self, monitors: &[Monitor]) -> Vec<usize> {
+        (0..monitors.len())
+            .filter(|&i| monitors[i].active && !self.chosen.contains(&i))
+            .collect()
+    }
+}
+
+/// Matching the monitors of a profile that aren't connected to outputs
+/// that are, before applying it.
+pub struct Reconcile {
+    pub profile: Profile,
+    /// Index of each missing profile entry and the output standing in for
+    /// it; `None` leaves it out.
+    pub entries: Vec<(usize, Option<String>)>,
+    /// Connected outputs the profile doesn't mention.
+    pub free: Vec<String>,
+    pub state: ListState,
+}
+
+/// The questions of the first-run setup, in the order they are asked.
+#[derive(Clone, Copy, PartialEq)]
+pub enum SetupStep {
+    Primary,
+    Arrangement,
+    SavePath,
+    Source,
+}
+
+/// Offered on the first launch: the answers so far and the current question.
+pub struct SetupWizard {
+    pub step: SetupStep,
+    /// Name of the monitor chosen as primary.
+    pub primary: String,
+    pub arrangement: Arrangement,
+    pub per_host: bool,
+    pub state: ListState,
+}
+
+impl SetupWizard {
+    /// Active monitors, any of which can be the primary.
+    pub fn candidates(monitors: &[Monitor]) -> Vec<usize> {
+        (0..monitors.len())
+            .filter(|&i| monitors[i].active)
+            .collect()
+    }
+
+    fn choice_count(&self, monitors: &[Monitor]) -> usize {
+        match self.step {
+            SetupStep::Primary => Self::candidates(monitors).len(),
+            SetupStep::Arrangement => Arrangement::ALL.len(),
+            SetupStep::SavePath | SetupStep::Source => 2,
+        }
+    }
+
+    fn ask(&mut self, step: SetupStep, choices: usize) {
+        self.step = step;
+        self.state = App::init_list_state(choices);
+    }
+}
+
+/// Read-only text scrolled by line, such as a monitor's raw hyprctl JSON.
+pub struct Details {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub scroll: usize,
+    /// Enter comments out the conflicting lines in hyprland.conf; only set
+    /// for the conflicts list when there are some to comment out.
+    pub supersede: bool,
+}
+
 pub struct App {
     pub monitors: Vec<Monitor>,
     pub configs: Vec<MonitorConfig>,
     pub monitor_list_state: ListState,
     pub option_list_state: ListState,
     pub focused_pane: FocusedPane,
-    pub info_message: Option<String>,
+    pub messages: MessageQueue,
+    pub popup: Option<Popup>,
+    pub config: Config,
+    /// ASCII rendering asked for on the command line, kept over reloads.
+    pub force_ascii: bool,
+    /// The config file's modification time as last loaded, and when it was
+    /// last checked.
+    config_modified: Option<SystemTime>,
+    config_checked: Instant,
+    pub history: CommandHistory,
+    pub lid_rule: LidRule,
+    pub mode_filter_enabled: bool,
+    /// Apply mode and scale changes as soon as they are made.
+    pub live_mode: bool,
+    pub live_change: Option<LiveChange>,
+    /// Changes staged across monitors, run together from the review popup.
+    pub queue: Vec<QueuedChange>,
+    /// A monitor that was just plugged in, and when its highlight ends.
+    pub new_monitor: Option<(String, Instant)>,
+    /// Bumped whenever a monitor rule is applied, so observers can tell the
+    /// layout changed.
+    pub layout_generation: u64,
+    pub tab: Tab,
+    pub workspaces: Vec<Workspace>,
+    pub workspace_list_state: ListState,
+    pub profiles: Vec<String>,
+    pub profile_list_state: ListState,
+    /// Selection in the log tab, which lists the newest entry first.
+    pub log_list_state: ListState,
+    /// The running Hyprland release; `None` if `hyprctl version` couldn't
+    /// be read, in which case nothing is gated.
+    pub hyprland_version: Option<HyprVersion>,
+    /// The saved monitor configuration as last read or written, to tell
+    /// which monitors have unsaved changes.
+    saved_conf: String,
+    /// The most recent message, kept for the status bar once it expired.
+    pub last_result: Option<String>,
+    /// Output made the X primary by "Set as Main Screen", saved so it is
+    /// set again at login.
+    pub xwayland_primary: Option<String>,
+    /// Output designated with "Set as Main Screen"; saved first and marked
+    /// in the list.
+    pub main_monitor: Option<String>,
+    pub monitor_order: MonitorOrder,
+    pub theme: ThemeName,
+    seen: SeenMonitors,
+    /// Outputs with disagreeing `monitor=` lines in the config files.
+    pub conflicts: Vec<hyprconf::Conflict>,
+    /// Stale monitors the user chose to keep, so they aren't asked about
+    /// again on every save.
+    kept_stale: Vec<String>,
+    /// Connected outputs left out by the `ignore` setting.
+    ignored: Vec<String>,
+    /// Wallpapers of the profile applied last, set again after each apply.
+    wallpapers: Wallpapers,
+    /// Monitor groups folded to their header in the Monitors pane.
+    pub collapsed_groups: BTreeSet<String>,
+    /// Settings of the last rule logged per monitor since the last refresh,
+    /// which is what it runs at until Hyprland is read again.
+    logged_rules: BTreeMap<String, String>,
+}
+
+/// The position of the group `name` belongs to among `groups`, and the
+/// group's name.
+fn group_of<'a>(groups: &'a BTreeMap<String, Vec<String>>, name: &str) -> Option<(usize, &'a str)> {
+    groups
+        .iter()
+        .enumerate()
+        .find(|(_, (_, members))| members.iter().any(|m| m == name))
+        .map(|(rank, (group, _))| (rank, group.as_str()))
 }
 
 impl App {
     pub fn new() -> io::Result<Self> {
         let monitors_data = commands::fetch_monitors()?;
-        let (monitors, configs) = Self::parse_monitors(monitors_data)?;
+        let (mut monitors, mut configs) = Self::parse_monitors(monitors_data)?;
+        let saved_conf = hyprconf::read_monitors_conf().unwrap_or_default();
+        Self::restore_saved_settings(&mut monitors, &mut configs, &saved_conf);
+        let mut messages = MessageQueue::default();
+        let config_modified = Config::modified();
+        let config = Config::load().unwrap_or_else(|e| {
+            messages.push(
+                Severity::Warning,
+                tr_args("messages.config_error", &[("error", &e)]),
+            );
+            Config::default()
+        });
+        i18n::init(config.ui.language.as_deref());
+        let ignored = Self::ignored_names(&monitors, &config.ignore);
+        let (monitors, configs) = Self::without_ignored(monitors, configs, &config.ignore);
         let monitor_count = monitors.len();
+        let mut seen = SeenMonitors::load();
+        // Only used to suggest cleanups; not worth failing over.
+        let _ = seen.update(
+            monitors.iter().map(|m| m.name.as_str()),
+            hyprconf::monitor_names(&saved_conf)
+                .iter()
+                .map(String::as_str),
+        );
+        let conflicts = Self::find_conflicts(&saved_conf);
+        if !conflicts.is_empty() {
+            messages.push(
+                Severity::Warning,
+                tr_args("messages.conflicts_found", &[("count", &conflicts.len())]),
+            );
+        }
+        let monitor_order = config.ui.monitor_order;
+        let theme = config.ui.theme;
+        let (monitors, configs) =
+            Self::sort_monitors(monitors, configs, monitor_order, &config.groups);
 
         Ok(Self {
             monitors,
             configs,
             monitor_list_state: Self::init_list_state(monitor_count),
-            option_list_state: Self::init_list_state(OPTION_COUNT),
+            option_list_state: ListState::default().with_selected(Some(RESOLUTION_IDX)),
             focused_pane: FocusedPane::Monitors,
-            info_message: None,
+            messages,
+            popup: None,
+            mode_filter_enabled: config.mode_filter.enabled,
+            history: CommandHistory::new(config.history.persist),
+            force_ascii: false,
+            config_modified,
+            config_checked: Instant::now(),
+            lid_rule: LidRule::from_config(&saved_conf),
+            xwayland_primary: xwayland::find_primary(&saved_conf),
+            main_monitor: main_monitor::load(),
+            config,
+            live_mode: false,
+            live_change: None,
+            queue: Vec::new(),
+            new_monitor: None,
+            layout_generation: 0,
+            tab: Tab::Monitors,
+            workspaces: Vec::new(),
+            workspace_list_state: ListState::default(),
+            profiles: Vec::new(),
+            profile_list_state: ListState::default(),
+            log_list_state: ListState::default(),
+            hyprland_version: commands::fetch_version()
+                .ok()
+                .as_ref()
+                .and_then(HyprVersion::from_json),
+            saved_conf,
+            last_result: None,
+            monitor_order,
+            theme,
+            seen,
+            conflicts,
+            kept_stale: Vec::new(),
+            ignored,
+            wallpapers: Wallpapers::new(),
+            collapsed_groups: BTreeSet::new(),
+            logged_rules: BTreeMap::new(),
         })
     }
 
+    /// Orders monitors (and their configs alongside) for the Monitors pane.
+    fn sort_monitors(
+        monitors: Vec<Monitor>,
+        configs: Vec<MonitorConfig>,
+        order: MonitorOrder,
+        groups: &BTreeMap<String, Vec<String>>,
+    ) -> (Vec<Monitor>, Vec<MonitorConfig>) {
+        let mut pairs: Vec<(Monitor, MonitorConfig)> = monitors.into_iter().zip(configs).collect();
+        match order {
+            MonitorOrder::Position => pairs.sort_by(|(a, _), (b, _)| {
+                (!a.active, a.x, a.y, &a.name).cmp(&(!b.active, b.x, b.y, &b.name))
+            }),
+            MonitorOrder::Name => pairs.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name)),
+        }
+        // Members of a group are listed together, ungrouped monitors last.
+        pairs.sort_by_key(|(m, _)| group_of(groups, &m.name).map_or(usize::MAX, |(rank, _)| rank));
+        pairs.into_iter().unzip()
+    }
+
+    /// Loads the config file again if it was edited since it was last read.
+    fn reload_config_if_changed(&mut self) -> bool {
+        if Config::modified() == self.config_modified {
+            return false;
+        }
+        let result = self.reload_config();
+        self.report(result);
+        true
+    }
+
+    /// Loads the config file again. A file that doesn't parse is reported
+    /// and the old settings stay.
+    pub fn reload_config(&mut self) -> Result<String, String> {
+        self.config_modified = Config::modified();
+        let mut config =
+            Config::load().map_err(|e| tr_args("messages.config_error", &[("error", &e)]))?;
+        config.ui.ascii |= self.force_ascii;
+
+        // Settings also changed from the UI only follow the file when the
+        // file's value changed.
+        if config.ui.theme != self.config.ui.theme {
+            self.theme = config.ui.theme;
+        }
+        if config.mode_filter.enabled != self.config.mode_filter.enabled {
+            self.mode_filter_enabled = config.mode_filter.enabled;
+        }
+        let resort = config.ui.monitor_order != self.config.ui.monitor_order
+            || config.groups != self.config.groups;
+        if config.ui.monitor_order != self.config.ui.monitor_order {
+            self.monitor_order = config.ui.monitor_order;
+        }
+        self.config = config;
+        if resort {
+            self.resort_monitors();
+        }
+        Ok(tr("messages.config_reloaded").to_string())
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        self.info(tr_args("messages.theme", &[("theme", &self.theme.label())]));
+    }
+
+    /// Switches the Monitors pane between position and name order, keeping
+    /// the selected monitor selected.
+    pub fn toggle_monitor_order(&mut self) {
+        self.monitor_order = match self.monitor_order {
+            MonitorOrder::Position => MonitorOrder::Name,
+            MonitorOrder::Name => MonitorOrder::Position,
+        };
+        self.resort_monitors();
+        self.info(tr_args(
+            "messages.sorted",
+            &[("order", &self.monitor_order.label())],
+        ));
+    }
+
+    /// Sorts the Monitors pane again, keeping the selected monitor selected.
+    fn resort_monitors(&mut self) {
+        let selected_name = self
+            .selected_monitor()
+            .and_then(|i| self.monitors.get(i))
+            .map(|m| m.name.clone());
+        let (monitors, configs) = Self::sort_monitors(
+            std::mem::take(&mut self.monitors),
+            std::mem::take(&mut self.configs),
+            self.monitor_order,
+            &self.config.groups,
+        );
+        self.monitors = monitors;
+        self.configs = configs;
+        if let Some(idx) =
+            selected_name.and_then(|name| self.monitors.iter().position(|m| m.name == name))
+        {
+            self.monitor_list_state.select(Some(idx));
+        }
+    }
+
+    /// Re-reads the monitor list from Hyprland, keeping pending edits for
+    /// monitors that were already known and the selection where possible.
+    pub fn refresh_monitors(&mut self) -> io::Result<()> {
+        let (mut monitors, mut fresh_configs) = Self::parse_monitors(commands::fetch_monitors()?)?;
+        let saved = hyprconf::read_monitors_conf().unwrap_or_default();
+        Self::restore_saved_settings(&mut monitors, &mut fresh_configs, &saved);
+        self.ignored = Self::ignored_names(&monitors, &self.config.ignore);
+        let (monitors, fresh_configs) =
+            Self::without_ignored(monitors, fresh_configs, &self.config.ignore);
+        let (monitors, fresh_configs) = Self::sort_monitors(
+            monitors,
+            fresh_configs,
+            self.monitor_order,
+            &self.config.groups,
+        );
+        let _ = self
+            .seen
+            .update(monitors.iter().map(|m| m.name.as_str()), []);
+        let selected_name = self
+            .selected_monitor()
+            .and_then(|i| self.monitors.get(i))
+            .map(|m| m.name.clone());
+
+        let configs = monitors
+            .iter()
+            .zip(fresh_configs)
+            .map(|(monitor, fresh)| {
+                // Pending edits survive; whether the monitor is on, asleep or
+                // disabled is always read back from Hyprland.
+                self.monitors
+                    .iter()
+                    .position(|old| old.name == monitor.name)
+                    .map(|i| self.configs[i].clone())
+                    .unwrap_or(fresh)
+            })
+            .collect();
+
+        let selection = selected_name
+            .and_then(|name| monitors.iter().position(|m| m.name == name))
+            .or(if monitors.is_empty() { None } else { Some(0) });
+
+        let old_names: Vec<String> = self.monitors.iter().map(|m| m.name.clone()).collect();
+        self.monitors = monitors;
+        self.configs = configs;
+        self.logged_rules.clear();
+        self.monitor_list_state.select(selection);
+        self.remap_popup(&old_names);
+        Ok(())
+    }
+
+    /// Points the monitor indices held by the open popup at the same outputs
+    /// after the list was read again, which can reorder and shrink it. A
+    /// popup whose monitor went away is closed.
+    fn remap_popup(&mut self, old_names: &[String]) {
+        let monitors = &self.monitors;
+        let remap = |idx: usize| {
+            let name = old_names.get(idx)?;
+            monitors.iter().position(|m| &m.name == name)
+        };
+        let connected = |name: &str| monitors.iter().any(|m| m.name == name);
+        let gone = |idx: usize| old_names.get(idx).cloned().unwrap_or_default();
+        // The output a popup is about, if it went away.
+        let gone = match &mut self.popup {
+            Some(Popup::DisableConfirm(confirm)) => {
+                confirm.target = confirm.target.and_then(remap);
+                remap_index(&mut confirm.monitor, remap, gone)
+            }
+            Some(Popup::ShrinkConfirm(confirm)) => remap_index(&mut confirm.monitor, remap, gone),
+            Some(Popup::TextInput(input)) => match &mut input.purpose {
+                InputPurpose::Position(idx)
+                | InputPurpose::CustomMode(idx)
+                | InputPurpose::VirtualResolution(idx) => remap_index(idx, remap, gone),
+            },
+            Some(Popup::Template(chooser)) => {
+                chooser.chosen = chooser.chosen.iter().filter_map(|&i| remap(i)).collect();
+                None
+            }
+            Some(Popup::ModePicker(picker)) => {
+                (!connected(&picker.monitor)).then(|| picker.monitor.clone())
+            }
+            Some(Popup::Setup(wizard)) => {
+                let candidates = SetupWizard::candidates(monitors).len();
+                if !connected(&wizard.primary) || candidates == 0 {
+                    Some(wizard.primary.clone())
+                } else {
+                    if wizard.step == SetupStep::Primary {
+                        let selected = wizard.state.selected().unwrap_or(0);
+                        wizard.state.select(Some(selected.min(candidates - 1)));
+                    }
+                    None
+                }
+            }
+            _ => None,
+        };
+        if let Some(name) = gone {
+            self.close_popup_for(&name);
+        }
+    }
+
+    /// Closes the popup about `monitor`, which went away.
+    fn close_popup_for(&mut self, monitor: &str) {
+        self.popup = None;
+        self.warn(tr_args(
+            "messages.popup_monitor_gone",
+            &[("monitor", &monitor)],
+        ));
+    }
+
+    /// Keeps the monitor list current as outputs come and go or take focus,
+    /// without waiting for a key press.
+    pub fn handle_event(&mut self, event: &Event) {
+        if event.name == "configreloaded" {
+            self.handle_config_reload();
+            return;
+        }
+        let Some(name) = event.monitor() else {
+            return;
+        };
+        if event.name == "focusedmon" {
+            for monitor in &mut self.monitors {
+                monitor.focused = monitor.name == name;
+            }
+            return;
+        }
+
+        let key = if event.name == "monitoradded" {
+            "messages.monitor_connected"
+        } else {
+            "messages.monitor_disconnected"
+        };
+        let message = tr_args(key, &[("monitor", &name)]);
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+            return;
+        }
+        self.info(message);
+
+        // Select a just-connected monitor so it is ready to configure.
+        if event.name == "monitoradded" {
+            if let Some(idx) = self.monitors.iter().position(|m| m.name == name) {
+                self.monitor_list_state.select(Some(idx));
+                self.new_monitor = Some((name.to_string(), Instant::now() + NEW_MONITOR_HIGHLIGHT));
+            }
+        } else if self
+            .new_monitor
+            .as_ref()
+            .is_some_and(|(new, _)| new == name)
+        {
+            self.new_monitor = None;
+        }
+    }
+
+    /// After `hyprctl reload` or a config edit, Hyprland applies the
+    /// `monitor=` lines again, undoing modes applied from here that weren't
+    /// saved. Warns about the monitors that changed and offers to apply
+    /// their settings again.
+    fn handle_config_reload(&mut self) {
+        let before: Vec<(String, AppliedMode)> = self
+            .monitors
+            .iter()
+            .filter(|m| m.active)
+            .map(|m| (m.name.clone(), m.applied.clone()))
+            .collect();
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+            return;
+        }
+        let overwritten: Vec<String> = before
+            .into_iter()
+            .filter(|(name, applied)| {
+                self.monitors
+                    .iter()
+                    .any(|m| m.name == *name && m.applied != *applied)
+            })
+            .map(|(name, _)| name)
+            .collect();
+        if overwritten.is_empty() {
+            self.info(tr("messages.hyprland_reloaded"));
+            return;
+        }
+        self.warn(tr_args(
+            "messages.reload_overwrote",
+            &[("monitors", &overwritten.join(", "))],
+        ));
+        if self.popup.is_none() {
+            self.popup = Some(Popup::ReloadConfirm(ReloadConfirm {
+                monitors: overwritten,
+                state: Self::init_list_state(RELOAD_CHOICES.len()),
+            }));
+        }
+    }
+
+    fn handle_reload_confirm_key(&mut self, code: KeyCode) {
+        let Some(Popup::ReloadConfirm(confirm)) = self.popup.as_mut() else {
+            return;
+        };
+
+        if let Some(motion) = Motion::from_key(code) {
+            let selection = motion.apply(confirm.state.selected(), RELOAD_CHOICES.len());
+            confirm.state.select(selection);
+            return;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.popup = None,
+            KeyCode::Enter => {
+                let Some(Popup::ReloadConfirm(confirm)) = self.popup.take() else {
+                    return;
+                };
+                if confirm.state.selected() != Some(0) {
+                    return;
+                }
+                for name in confirm.monitors {
+                    if let Some(idx) = self.monitors.iter().position(|m| m.name == name) {
+                        let result = self.apply_monitor(idx);
+                        self.report(result);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Fills in settings Hyprland doesn't report back, such as reserved
+    /// areas, from the saved monitor configuration.
+    fn restore_saved_settings(
+        monitors: &mut [Monitor],
+        configs: &mut [MonitorConfig],
+        saved: &str,
+    ) {
+        for (monitor, config) in monitors.iter_mut().zip(configs.iter_mut()) {
+            if let Some(reserved) = hyprconf::find_reserved(saved, &monitor.name) {
+                config.reserved = reserved;
+            }
+            config.position = hyprconf::find_position(saved, &monitor.name);
+            config.save_disabled = hyprconf::is_disabled(saved, &monitor.name);
+            // A custom mode isn't in the advertised list, so the live mode
+            // was matched to the nearest one; take the saved timings back
+            // if that's what the monitor is running.
+            if let Some(modeline) = hyprconf::find_modeline(saved, &monitor.name).filter(|m| {
+                m.resolution()
+                    == format!(
+                        "{}x{}",
+                        monitor.raw["width"].as_u64().unwrap_or(0),
+                        monitor.raw["height"].as_u64().unwrap_or(0)
+                    )
+            }) {
+                config.resolution = modeline.resolution();
+                config.refresh_rate = modeline.refresh_rate();
+                config.modeline = Some(modeline);
+                monitor.applied = AppliedMode {
+                    scale: monitor.applied.scale,
+                    ..AppliedMode::of(config)
+                };
+            }
+        }
+    }
+
     fn init_list_state(count: usize) -> ListState {
         let mut state = ListState::default();
         if count > 0 {
@@ -61,6 +872,35 @@ impl App {
         state
     }
 
+    /// Names of the outputs listed in the `ignore` setting.
+    fn ignored_names(monitors: &[Monitor], ignore: &[String]) -> Vec<String> {
+        monitors
+            .iter()
+            .filter(|monitor| ignore.iter().any(|pattern| monitor.matches(pattern)))
+            .map(|monitor| monitor.name.clone())
+            .collect()
+    }
+
+    /// Whether saved lines for `name` belong to an ignored output, which are
+    /// neither carried over nor offered for cleanup.
+    fn is_ignored(&self, name: &str) -> bool {
+        self.ignored.iter().any(|ignored| ignored == name)
+            || self.config.ignore.iter().any(|pattern| pattern == name)
+    }
+
+    /// Drops the outputs listed in the `ignore` setting.
+    fn without_ignored(
+        monitors: Vec<Monitor>,
+        configs: Vec<MonitorConfig>,
+        ignore: &[String],
+    ) -> (Vec<Monitor>, Vec<MonitorConfig>) {
+        monitors
+            .into_iter()
+            .zip(configs)
+            .filter(|(monitor, _)| !ignore.iter().any(|pattern| monitor.matches(pattern)))
+            .unzip()
+    }
+
     fn parse_monitors(
         monitors_data: Vec<serde_json::Value>,
     ) -> io::Result<(Vec<Monitor>, Vec<MonitorConfig>)> {
@@ -88,14 +928,38 @@ impl App {
         let resolution = resolutions.get(res_idx).cloned().unwrap_or_default();
         let refresh_rate = modes
             .get(&resolution)
-            .and_then(|rates| rates.get(refresh_idx).copied())
-            .unwrap_or(60.0);
+            .and_then(|rates| rates.get(refresh_idx))
+            .map_or(60.0, |rate| rate.value);
 
+        let applied = AppliedMode {
+            resolution: resolution.clone(),
+            refresh_rate,
+            scale,
+            modeline: None,
+        };
         Some(Ok((
             Monitor {
+                id: data["id"].as_i64().unwrap_or(-1),
                 name,
+                description: data["description"].as_str().unwrap_or_default().to_string(),
                 active,
+                dpms_on: data["dpmsStatus"].as_bool().unwrap_or(true),
+                focused: data["focused"].as_bool().unwrap_or(false),
                 modes,
+                x: data["x"].as_i64().unwrap_or(0) as i32,
+                y: data["y"].as_i64().unwrap_or(0) as i32,
+                physical_width: data["physicalWidth"].as_u64().unwrap_or(0) as u32,
+                physical_height: data["physicalHeight"].as_u64().unwrap_or(0) as u32,
+                mirror_of: data["mirrorOf"]
+                    .as_str()
+                    .filter(|source| !source.is_empty() && *source != "none")
+                    .map(String::from),
+                native_resolution: data["availableModes"][0]
+                    .as_str()
+                    .and_then(|mode| mode.split_once('@'))
+                    .map(|(resolution, _)| resolution.to_string()),
+                raw: data.clone(),
+                applied,
             },
             MonitorConfig {
                 resolution,
@@ -103,18 +967,36 @@ impl App {
                 scale,
                 resolution_index: res_idx,
                 refresh_rate_index: refresh_idx,
-                dpms_on: true,
+                vrr: data["vrr"].as_bool().unwrap_or(false),
+                previous_refresh_rate: None,
+                previous_vrr: None,
+                previous_tearing: None,
+                reserved: [0; 4],
+                position: None,
+                save_disabled: false,
+                persist: true,
+                transform: data["transform"].as_u64().unwrap_or(0) as u8,
+                // 10-bit formats are reported as e.g. XRGB2101010.
+                bitdepth: data["currentFormat"]
+                    .as_str()
+                    .filter(|format| format.contains("2101010"))
+                    .map(|_| 10),
+                color_mode: data["colorManagementPreset"]
+                    .as_str()
+                    .map(ColorMode::from_keyword)
+                    .unwrap_or_default(),
+                modeline: None,
             },
         )))
     }
 
-    fn parse_modes(data: &serde_json::Value) -> BTreeMap<String, Vec<f64>> {
+    fn parse_modes(data: &serde_json::Value) -> BTreeMap<String, Vec<Rate>> {
         let mut modes = BTreeMap::new();
 
         if let Some(available_modes) = data["availableModes"].as_array() {
             for mode_str in available_modes.iter().filter_map(|v| v.as_str()) {
                 if let Some((res, rate_str)) = mode_str.split_once('@') {
-                    if let Ok(rate) = rate_str.trim_end_matches("Hz").parse::<f64>() {
+                    if let Some(rate) = Rate::parse(rate_str) {
                         modes
                             .entry(res.to_string())
                             .or_insert_with(Vec::new)
@@ -124,9 +1006,32 @@ impl App {
             }
         }
 
+        if modes.is_empty() {
+            // Headless outputs report no modes; offer common ones plus
+            // whatever they are currently running at.
+            let rate = data["refreshRate"].as_f64().unwrap_or(60.0);
+            for res in VIRTUAL_MODES {
+                modes.insert(res.to_string(), vec![Rate::from(60.0)]);
+            }
+            if let (Some(w), Some(h)) = (data["width"].as_i64(), data["height"].as_i64()) {
+                if w > 0 && h > 0 {
+                    modes
+                        .entry(format!("{}x{}", w, h))
+                        .or_insert_with(Vec::new)
+                        .push(Rate::from(rate));
+                }
+            }
+        }
+
+        // Only drop rates listed twice; 143.998 and 144.00 are different
+        // modes even if they round alike.
         for rates in modes.values_mut() {
-            rates.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
-            rates.dedup_by(|a, b| (*a - *b).abs() < 0.01);
+            rates.sort_by(|a, b| {
+                b.value
+                    .total_cmp(&a.value)
+                    .then_with(|| a.spec.cmp(&b.spec))
+            });
+            rates.dedup_by(|a, b| a.spec == b.spec);
         }
 
         modes
@@ -139,7 +1044,7 @@ impl App {
 
     fn find_current_mode(
         data: &serde_json::Value,
-        modes: &BTreeMap<String, Vec<f64>>,
+        modes: &BTreeMap<String, Vec<Rate>>,
         active: bool,
     ) -> (usize, usize) {
         if !active || modes.is_empty() {
@@ -161,9 +1066,8 @@ impl App {
         let refresh_idx = modes
             .get(&resolution)
             .and_then(|rates| {
-                rates
-                    .iter()
-                    .position(|&rate| (rate - current_rate).abs() < 0.1)
+                let rate = monitor::closest_rate(rates, current_rate, 0.1)?;
+                rates.iter().position(|r| r == rate)
             })
             .unwrap_or(0);
 
@@ -180,266 +1084,2807 @@ impl App {
     }
 
     fn cycle_selection(current: Option<usize>, max: usize, forward: bool) -> Option<usize> {
-        if max == 0 {
-            return None;
-        }
-        Some(match current {
-            Some(i) if forward => (i + 1) % max,
-            Some(i) => (i + max - 1) % max,
-            None => 0,
-        })
+        let motion = if forward {
+            Motion::Next
+        } else {
+            Motion::Previous
+        };
+        motion.apply(current, max)
     }
 
-    fn navigate_monitors(&mut self, forward: bool) {
-        let selection = Self::cycle_selection(
-            self.monitor_list_state.selected(),
-            self.monitors.len(),
-            forward,
-        );
+    fn navigate_monitors(&mut self, motion: Motion) {
+        let count = self.monitors.len();
+        let mut selection = motion.apply(self.monitor_list_state.selected(), count);
+        while selection.is_some_and(|i| self.is_hidden(i)) {
+            selection = Self::cycle_selection(selection, count, motion.is_forward());
+        }
         self.monitor_list_state.select(selection);
-        self.option_list_state.select(Some(0));
+        self.option_list_state.select(Some(RESOLUTION_IDX));
     }
 
-    fn navigate_options(&mut self, forward: bool) {
-        let selection =
-            Self::cycle_selection(self.option_list_state.selected(), OPTION_COUNT, forward);
-        self.option_list_state.select(selection);
+    /// The group monitor `idx` belongs to, if any.
+    pub fn group_of(&self, idx: usize) -> Option<&str> {
+        group_of(&self.config.groups, &self.monitors[idx].name).map(|(_, group)| group)
     }
 
-    fn modify_selected_option(&mut self, increase: bool) {
-        let Some(mon_idx) = self.monitor_list_state.selected() else {
-            return;
-        };
-        let Some(opt_idx) = self.option_list_state.selected() else {
-            return;
-        };
+    /// The group whose header is drawn above monitor `idx`, its first
+    /// listed member.
+    pub fn group_header(&self, idx: usize) -> Option<&str> {
+        let group = self.group_of(idx)?;
+        (idx == 0 || self.group_of(idx - 1) != Some(group)).then_some(group)
+    }
 
-        match opt_idx {
-            0 => self.cycle_resolution(mon_idx, increase),
-            1 => self.cycle_refresh_rate(mon_idx, increase),
-            2 => self.adjust_scale(mon_idx, increase),
-            _ => {}
-        }
+    /// Whether monitor `idx` is folded away under its group's header.
+    pub fn is_hidden(&self, idx: usize) -> bool {
+        self.group_of(idx)
+            .is_some_and(|group| self.collapsed_groups.contains(group))
+            && self.group_header(idx).is_none()
     }
 
-    fn cycle_resolution(&mut self, mon_idx: usize, increase: bool) {
-        let resolutions: Vec<_> = self.monitors[mon_idx].modes.keys().cloned().collect();
-        if resolutions.is_empty() {
-            return;
-        }
+    /// How many connected monitors are in `group`.
+    pub fn group_size(&self, group: &str) -> usize {
+        self.group_members(group).len()
+    }
 
-        let config = &mut self.configs[mon_idx];
-        config.resolution_index = if increase {
-            (config.resolution_index + 1) % resolutions.len()
-        } else {
-            (config.resolution_index + resolutions.len() - 1) % resolutions.len()
-        };
+    /// Indices of the connected members of `group`.
+    fn group_members(&self, group: &str) -> Vec<usize> {
+        (0..self.monitors.len())
+            .filter(|&i| self.group_of(i) == Some(group))
+            .collect()
+    }
 
-        config.resolution = resolutions[config.resolution_index].clone();
-        config.refresh_rate_index = 0;
+    /// Folds or unfolds the selected monitor's group.
+    fn toggle_selected_group(&mut self) {
+        if let Some(group) = self
+            .selected_monitor()
+            .and_then(|idx| self.group_of(idx))
+            .map(str::to_string)
+        {
+            self.toggle_group(&group);
+        }
+    }
 
-        if let Some(rates) = self.monitors[mon_idx].modes.get(&config.resolution) {
-            config.refresh_rate = rates.first().copied().unwrap_or(60.0);
+    fn toggle_group(&mut self, group: &str) {
+        if !self.collapsed_groups.remove(group) {
+            self.collapsed_groups.insert(group.to_string());
+        }
+        if self
+            .selected_monitor()
+            .is_some_and(|idx| self.is_hidden(idx))
+        {
+            let header = self.group_members(group).first().copied();
+            self.monitor_list_state.select(header);
         }
     }
 
-    fn cycle_refresh_rate(&mut self, mon_idx: usize, increase: bool) {
-        let resolution = self.configs[mon_idx].resolution.clone();
-        let Some(rates) = self.monitors[mon_idx].modes.get(&resolution).cloned() else {
+    /// Gives every member of `group` the selected monitor's scale and
+    /// applies them.
+    fn scale_group(&mut self, group: &str) {
+        let Some(selected) = self.selected_monitor() else {
             return;
         };
-        if rates.is_empty() {
-            return;
+        let scale = self.configs[selected].scale;
+        let mut failed = Vec::new();
+        for idx in self.group_members(group) {
+            if !self.monitors[idx].active {
+                continue;
+            }
+            self.configs[idx].scale = scale;
+            if self.apply_monitor(idx).is_err() {
+                failed.push(self.monitors[idx].name.clone());
+            }
         }
+        self.report_group(group, failed);
+    }
 
-        let config = &mut self.configs[mon_idx];
-        config.refresh_rate_index = if increase {
-            (config.refresh_rate_index + 1) % rates.len()
-        } else {
-            (config.refresh_rate_index + rates.len() - 1) % rates.len()
-        };
-
-        config.refresh_rate = rates[config.refresh_rate_index];
+    /// Blanks every member of `group`, or wakes them all if any is blank.
+    fn toggle_group_dpms(&mut self, group: &str) {
+        let members = self.group_members(group);
+        let on = members.iter().any(|&idx| !self.monitors[idx].dpms_on);
+        let mut failed = Vec::new();
+        for idx in members {
+            if self.set_dpms(idx, on).is_err() {
+                failed.push(self.monitors[idx].name.clone());
+            }
+        }
+        self.report_group(group, failed);
     }
 
-    fn adjust_scale(&mut self, mon_idx: usize, increase: bool) {
-        let config = &mut self.configs[mon_idx];
-        config.scale =
-            (config.scale + if increase { SCALE_STEP } else { -SCALE_STEP }).max(MIN_SCALE);
+    fn disable_group(&mut self, group: &str) {
+        for idx in self.group_members(group) {
+            if self.monitors[idx].active {
+                self.disable_monitor_now(idx);
+            }
+        }
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+        }
     }
 
-    fn get_other_monitor_info(&self, current_idx: usize) -> Option<(usize, String)> {
-        self.monitors
-            .iter()
-            .enumerate()
-            .find(|(i, m)| *i != current_idx && m.active)
-            .map(|(i, m)| (i, m.name.clone()))
+    fn report_group(&mut self, group: &str, failed: Vec<String>) {
+        if failed.is_empty() {
+            self.info(tr_args("messages.group_done", &[("group", &group)]));
+        } else {
+            self.error(tr_args(
+                "messages.group_failed",
+                &[("group", &group), ("monitors", &failed.join(", "))],
+            ));
+        }
     }
 
-    fn set_as_main(&self) {
-        let Some(idx) = self.monitor_list_state.selected() else {
+    /// Jumps straight to monitor `idx` on the monitors tab, if it exists.
+    fn select_monitor(&mut self, idx: usize) {
+        if idx >= self.monitors.len() {
             return;
-        };
-        let monitor = &self.monitors[idx];
-        let config = &self.configs[idx];
+        }
+        if self.is_hidden(idx) {
+            if let Some(group) = self.group_of(idx).map(str::to_string) {
+                self.collapsed_groups.remove(&group);
+            }
+        }
+        self.switch_tab(Tab::Monitors);
+        self.monitor_list_state.select(Some(idx));
+        self.option_list_state.select(Some(RESOLUTION_IDX));
+    }
 
-        let command = format!(
-            "hyprctl keyword monitor \"{},preferred,{}@{:.2},auto,{:.2}\"",
-            monitor.name,
-            config.resolution,
-            config.refresh_rate,
-            config.scale_as_float()
-        );
-        commands::execute_hyprctl(&command);
+    fn navigate_options(&mut self, motion: Motion) {
+        let mut selection = motion.apply(self.option_list_state.selected(), OPTION_COUNT);
+        while selection.is_some_and(is_option_header) {
+            selection = Self::cycle_selection(selection, OPTION_COUNT, motion.is_forward());
+        }
+        self.option_list_state.select(selection);
     }
 
-    fn extend_relative(&self, direction: &str) {
-        let Some(idx) = self.monitor_list_state.selected() else {
+    fn modify_selected_option(&mut self, increase: bool, fine: bool) {
+        let Some(mon_idx) = self.monitor_list_state.selected() else {
             return;
         };
+        let Some(opt_idx) = self.option_list_state.selected() else {
+            return;
+        };
+        let previous = self.configs[mon_idx].clone();
+
+        match opt_idx {
+            RESOLUTION_IDX => self.cycle_resolution(mon_idx, increase),
+            REFRESH_RATE_IDX => self.cycle_refresh_rate(mon_idx, increase),
+            SCALE_IDX => self.adjust_scale(mon_idx, increase, fine),
+            COLOR_MODE_IDX => self.cycle_color_mode(mon_idx, increase),
+            RESERVED_TOP_IDX..=RESERVED_RIGHT_IDX => {
+                self.adjust_reserved(mon_idx, opt_idx - RESERVED_TOP_IDX, increase, fine)
+            }
+            LID_RULE_IDX => self.cycle_lid_rule(increase),
+            PERSIST_IDX => self.toggle_persist(mon_idx),
+            SAVE_DISABLED_IDX => {
+                let config = &mut self.configs[mon_idx];
+                config.save_disabled = !config.save_disabled;
+            }
+            _ => {}
+        }
+
+        if matches!(
+            opt_idx,
+            RESOLUTION_IDX | REFRESH_RATE_IDX | SCALE_IDX | COLOR_MODE_IDX
+        ) {
+            self.schedule_live_apply(mon_idx, previous);
+        }
+    }
+
+    fn toggle_live_mode(&mut self) {
+        self.live_mode = !self.live_mode;
+        self.info(tr(if self.live_mode {
+            "messages.live_mode_on"
+        } else {
+            "messages.live_mode_off"
+        }));
+    }
+
+    /// In live mode, applies monitor `idx` once edits pause. `previous` is
+    /// its configuration before this edit, kept as the revert target for the
+    /// first edit of a series.
+    fn schedule_live_apply(&mut self, idx: usize, previous: MonitorConfig) {
+        if !self.live_mode {
+            return;
+        }
+        // Switching to another monitor keeps the pending change of the last one.
+        let name = self.monitors[idx].name.clone();
+        if self.live_change.as_ref().is_some_and(|c| c.monitor != name) {
+            self.keep_live_change();
+        }
+        let change = self.live_change.get_or_insert(LiveChange {
+            monitor: name,
+            previous,
+            apply_at: None,
+            revert_at: None,
+        });
+        change.apply_at = Some(Instant::now() + LIVE_DEBOUNCE);
+    }
+
+    fn keep_live_change(&mut self) {
+        if let Some(change) = self.live_change.take() {
+            self.info(tr_args(
+                "messages.kept_settings",
+                &[("monitor", &change.monitor)],
+            ));
+        }
+    }
+
+    /// Where the monitor of the live change is now. A change to one that
+    /// went away is dropped with a warning.
+    fn live_change_monitor(&mut self) -> Option<usize> {
+        let name = &self.live_change.as_ref()?.monitor;
+        let idx = self.monitors.iter().position(|m| &m.name == name);
+        if idx.is_none() {
+            let name = name.clone();
+            self.live_change = None;
+            self.warn(tr_args("messages.live_change_gone", &[("monitor", &name)]));
+        }
+        idx
+    }
+
+    fn revert_live_change(&mut self) {
+        let Some(idx) = self.live_change_monitor() else {
+            return;
+        };
+        let Some(change) = self.live_change.take() else {
+            return;
+        };
+        self.configs[idx] = change.previous;
+        let result = self.apply_monitor(idx).map(|_| {
+            tr_args(
+                "messages.reverted",
+                &[("monitor", &self.monitors[idx].name)],
+            )
+        });
+        self.report(result);
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.messages.push(Severity::Info, text);
+    }
+
+    pub fn warn(&mut self, text: impl Into<String>) {
+        self.messages.push(Severity::Warning, text);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.messages.push(Severity::Error, text);
+    }
+
+    /// Shows the outcome of an action: an error if it failed.
+    fn report(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(message) => self.info(message),
+            Err(message) => self.error(message),
+        }
+    }
+
+    /// Time-based updates, called regularly by the event loop. Returns
+    /// whether anything on screen may have changed.
+    pub fn tick(&mut self) -> bool {
+        let now = Instant::now();
+        let mut changed = false;
+        if let Some(message) = self.messages.expire(now) {
+            self.last_result = Some(message.text);
+            changed = true;
+        }
+        if self
+            .new_monitor
+            .as_ref()
+            .is_some_and(|(_, until)| *until <= now)
+        {
+            self.new_monitor = None;
+            changed = true;
+        }
+        if now.duration_since(self.config_checked) >= CONFIG_POLL {
+            self.config_checked = now;
+            changed |= self.reload_config_if_changed();
+        }
+
+        let Some(change) = self.live_change.as_mut() else {
+            return changed;
+        };
+
+        // The keep/revert countdown is on screen until one happens.
+        if change.apply_at.is_some_and(|at| at <= now) {
+            change.apply_at = None;
+            change.revert_at = Some(now + REVERT_TIMEOUT);
+            if let Some(idx) = self.live_change_monitor() {
+                if let Err(message) = self.apply_monitor(idx) {
+                    self.error(message);
+                }
+            }
+        } else if change.revert_at.is_some_and(|at| at <= now) {
+            self.revert_live_change();
+        } else {
+            return changed || change.revert_at.is_some();
+        }
+        true
+    }
+
+    /// Modes offered for cycling and in the picker, with the mode filter
+    /// applied when it is enabled.
+    fn visible_modes(&self, mon_idx: usize) -> BTreeMap<String, Vec<Rate>> {
+        let modes = &self.monitors[mon_idx].modes;
+        if !self.mode_filter_enabled {
+            return modes.clone();
+        }
+        let config = &self.configs[mon_idx];
+        self.config
+            .mode_filter
+            .apply(modes, (&config.resolution, config.refresh_rate))
+    }
+
+    /// Points the mode indices of `mon_idx` at its configured resolution and
+    /// refresh rate within the full, unfiltered mode list.
+    fn sync_mode_indices(&mut self, mon_idx: usize) {
+        let modes = &self.monitors[mon_idx].modes;
+        let config = &mut self.configs[mon_idx];
+
+        config.resolution_index = modes
+            .keys()
+            .position(|r| *r == config.resolution)
+            .unwrap_or(0);
+        config.refresh_rate_index = modes
+            .get(&config.resolution)
+            .and_then(|rates| {
+                let rate = monitor::closest_rate(rates, config.refresh_rate, 0.01)?;
+                rates.iter().position(|r| r == rate)
+            })
+            .unwrap_or(0);
+    }
+
+    fn cycle_resolution(&mut self, mon_idx: usize, increase: bool) {
+        let modes = self.visible_modes(mon_idx);
+        let resolutions: Vec<_> = modes.keys().cloned().collect();
+        if resolutions.is_empty() {
+            return;
+        }
+
+        let config = &mut self.configs[mon_idx];
+        let current = resolutions
+            .iter()
+            .position(|r| *r == config.resolution)
+            .unwrap_or(0);
+        let next = if increase {
+            (current + 1) % resolutions.len()
+        } else {
+            (current + resolutions.len() - 1) % resolutions.len()
+        };
+
+        config.resolution = resolutions[next].clone();
+        if let Some(rates) = modes.get(&config.resolution) {
+            let values: Vec<f64> = rates.iter().map(|rate| rate.value).collect();
+            config.refresh_rate = self
+                .config
+                .modes
+                .rate_policy
+                .pick(&values, config.refresh_rate)
+                .unwrap_or(60.0);
+        }
+        self.sync_mode_indices(mon_idx);
+    }
+
+    fn cycle_refresh_rate(&mut self, mon_idx: usize, increase: bool) {
+        let modes = self.visible_modes(mon_idx);
+        let Some(rates) = modes.get(&self.configs[mon_idx].resolution) else {
+            return;
+        };
+        if rates.is_empty() {
+            return;
+        }
+
+        let config = &mut self.configs[mon_idx];
+        let current = monitor::closest_rate(rates, config.refresh_rate, 0.01)
+            .and_then(|rate| rates.iter().position(|r| r == rate))
+            .unwrap_or(0);
+        let next = if increase {
+            (current + 1) % rates.len()
+        } else {
+            (current + rates.len() - 1) % rates.len()
+        };
+
+        config.refresh_rate = rates[next].value;
+        self.sync_mode_indices(mon_idx);
+    }
+
+    fn toggle_mode_filter(&mut self) {
+        self.mode_filter_enabled = !self.mode_filter_enabled;
+        self.info(tr(if self.mode_filter_enabled {
+            "messages.mode_filter_on"
+        } else {
+            "messages.mode_filter_off"
+        }));
+    }
+
+    fn open_mode_picker(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        let config = &self.configs[idx];
+        self.popup = Some(Popup::ModePicker(ModePicker::new(
+            &self.monitors[idx].name,
+            &self.visible_modes(idx),
+            &config.resolution,
+            config.refresh_rate,
+        )));
+    }
+
+    fn select_mode(&mut self, idx: usize, resolution: String, refresh_rate: f64) {
+        let previous = self.configs[idx].clone();
+        let config = &mut self.configs[idx];
+        config.resolution = resolution;
+        config.refresh_rate = refresh_rate;
+        self.sync_mode_indices(idx);
+        self.schedule_live_apply(idx, previous);
+    }
+
+    fn handle_mode_picker_key(&mut self, code: KeyCode) {
+        let Some(Popup::ModePicker(picker)) = self.popup.as_mut() else {
+            return;
+        };
+        if let Some(motion) = Motion::from_filter_key(code) {
+            picker.navigate(motion);
+            return;
+        }
+
+        match code {
+            KeyCode::Esc => self.popup = None,
+            KeyCode::Enter => {
+                let selected = picker.selected();
+                let idx = self.monitors.iter().position(|m| m.name == picker.monitor);
+                self.popup = None;
+                if let (Some(idx), Some((resolution, refresh_rate))) = (idx, selected) {
+                    self.select_mode(idx, resolution, refresh_rate);
+                }
+            }
+            KeyCode::Backspace => picker.pop_char(),
+            KeyCode::Char(c) => picker.push_char(c),
+            _ => {}
+        }
+    }
+
+    fn open_position_input(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        let text = match self.configs[idx].position {
+            Some((x, y)) => format!("{},{}", x, y),
+            None => String::new(),
+        };
+        let input = TextInput::new(
+            InputPurpose::Position(idx),
+            tr_args(
+                "popups.position_title",
+                &[("monitor", &self.monitors[idx].name)],
+            ),
+            tr("popups.position_label"),
+            &text,
+            |text| {
+                if text.is_empty() || text == "auto" || monitor::parse_position(text).is_some() {
+                    Ok(())
+                } else {
+                    Err(tr_args(
+                        "popups.position_invalid",
+                        &[("text", &format!("{:?}", text))],
+                    ))
+                }
+            },
+        )
+        .with_help(tr("popups.position_help"));
+        self.popup = Some(Popup::TextInput(input));
+    }
+
+    /// Asks for a mode the monitor doesn't advertise and computes CVT
+    /// timings for it.
+    fn open_custom_mode_input(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        let config = &self.configs[idx];
+        let text = if config.resolution.is_empty() {
+            String::new()
+        } else {
+            format!("{}@{}", config.resolution, config.refresh_rate.round())
+        };
+        let input = TextInput::new(
+            InputPurpose::CustomMode(idx),
+            tr_args(
+                "popups.custom_mode_title",
+                &[("monitor", &self.monitors[idx].name)],
+            ),
+            tr("popups.custom_mode_label"),
+            &text,
+            |text| {
+                if Modeline::parse(text).is_some() {
+                    Ok(())
+                } else {
+                    Err(tr_args(
+                        "popups.custom_mode_invalid",
+                        &[("text", &format!("{:?}", text))],
+                    ))
+                }
+            },
+        )
+        .with_help(tr("popups.custom_mode_help"));
+        self.popup = Some(Popup::TextInput(input));
+    }
+
+    fn open_virtual_resolution_input(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        let config = &self.configs[idx];
+        let text = monitor::parse_resolution(&config.resolution)
+            .map(|(w, h)| {
+                let scale = config.scale_as_float();
+                format!(
+                    "{}x{}",
+                    (w as f64 / scale).round(),
+                    (h as f64 / scale).round()
+                )
+            })
+            .unwrap_or_default();
+        let input = TextInput::new(
+            InputPurpose::VirtualResolution(idx),
+            tr_args(
+                "popups.virtual_resolution_title",
+                &[("monitor", &self.monitors[idx].name)],
+            ),
+            tr("popups.virtual_resolution_label"),
+            &text,
+            |text| match monitor::parse_resolution(text) {
+                Some((w, h)) if w > 0 && h > 0 => Ok(()),
+                _ => Err(tr_args(
+                    "popups.virtual_resolution_invalid",
+                    &[("text", &format!("{:?}", text))],
+                )),
+            },
+        )
+        .with_help(tr("popups.virtual_resolution_help"));
+        self.popup = Some(Popup::TextInput(input));
+    }
+
+    /// Scales monitor `idx` so its logical width is `width`, within the
+    /// virtual resolution bounds, warning when that costs performance or
+    /// sharpness.
+    fn set_virtual_width(&mut self, idx: usize, width: u32) {
+        let Some((physical_width, _)) = monitor::parse_resolution(&self.configs[idx].resolution)
+        else {
+            return;
+        };
+        let settings = &self.config.scale;
+        let scale = ((physical_width as f64 / width as f64) * 100.0).round() as i32;
+        let scale = scale.clamp(
+            settings.virtual_min_percent(),
+            settings.virtual_max_percent(),
+        );
+        let zoomed = scale > settings.max_percent();
+        self.configs[idx].scale = scale;
+
+        let name = self.monitors[idx].name.clone();
+        let size = self.configs[idx]
+            .logical_size()
+            .map(|(w, h)| format!("{}x{}", w, h))
+            .unwrap_or_default();
+        let scale_text = format!("{:.2}", scale as f64 / 100.0);
+        // Below 1 the compositor renders more pixels than the panel has.
+        let load = format!("{:.1}", (100.0 / scale as f64).powi(2));
+        let key = if scale < 100 {
+            "messages.virtual_supersample"
+        } else if zoomed {
+            "messages.virtual_zoom"
+        } else {
+            "messages.virtual_resolution"
+        };
+        let message = tr_args(
+            key,
+            &[
+                ("monitor", &name),
+                ("size", &size),
+                ("scale", &scale_text),
+                ("load", &load),
+            ],
+        );
+        if scale < 100 || zoomed {
+            self.warn(message);
+        } else {
+            self.info(message);
+        }
+    }
+
+    fn handle_text_input_key(&mut self, key: KeyEvent) {
+        let Some(Popup::TextInput(input)) = self.popup.as_mut() else {
+            return;
+        };
+
+        match input.handle_key(key) {
+            InputOutcome::Editing => {}
+            InputOutcome::Cancelled => self.popup = None,
+            InputOutcome::Submitted(text) => {
+                let purpose = input.purpose.clone();
+                self.popup = None;
+                self.submit_input(purpose, &text);
+            }
+        }
+    }
+
+    /// Acts on text accepted by a text input popup.
+    fn submit_input(&mut self, purpose: InputPurpose, text: &str) {
+        match purpose {
+            InputPurpose::Position(idx) => {
+                self.configs[idx].position = monitor::parse_position(text);
+            }
+            InputPurpose::VirtualResolution(idx) => {
+                if let Some((width, _)) = monitor::parse_resolution(text) {
+                    self.set_virtual_width(idx, width);
+                }
+            }
+            InputPurpose::CustomMode(idx) => {
+                let Some(modeline) = Modeline::parse(text) else {
+                    return;
+                };
+                let config = &mut self.configs[idx];
+                config.resolution = modeline.resolution();
+                config.refresh_rate = modeline.refresh_rate();
+                let spec = modeline.to_string();
+                config.modeline = Some(modeline);
+                self.sync_mode_indices(idx);
+                let name = self.monitors[idx].name.clone();
+                self.info(tr_args(
+                    "messages.custom_mode",
+                    &[("monitor", &name), ("modeline", &spec)],
+                ));
+            }
+        }
+    }
+
+    fn open_palette(&mut self) {
+        let profiles = Profile::list().unwrap_or_default();
+        let groups: Vec<String> = self.config.groups.keys().cloned().collect();
+        self.popup = Some(Popup::Palette(Palette::new(Action::all(
+            &profiles, &groups,
+        ))));
+    }
+
+    fn handle_palette_key(&mut self, code: KeyCode) {
+        let Some(Popup::Palette(palette)) = self.popup.as_mut() else {
+            return;
+        };
+
+        if let Some(motion) = Motion::from_filter_key(code) {
+            palette.navigate(motion);
+            return;
+        }
+
+        match code {
+            KeyCode::Esc => self.popup = None,
+            KeyCode::Enter => {
+                let selected = palette.selected();
+                self.popup = None;
+                if let Some(action) = selected {
+                    self.run_action(action);
+                }
+            }
+            KeyCode::Backspace => palette.pop_char(),
+            KeyCode::Char(c) => palette.push_char(c),
+            _ => {}
+        }
+    }
+
+    /// The action behind an options pane row, for rows that run one on Enter.
+    fn option_action(idx: usize) -> Option<Action> {
+        Some(match idx {
+            RESOLUTION_IDX | REFRESH_RATE_IDX => Action::PickMode,
+            SUGGEST_SCALE_IDX => Action::SuggestScale,
+            POSITION_IDX => Action::SetPosition,
+            APPLY_OPTION_IDX => Action::ApplyChanges,
+            SET_MAIN_IDX => Action::SetMain,
+            EXTEND_LEFT_IDX => Action::ExtendLeft,
+            EXTEND_RIGHT_IDX => Action::ExtendRight,
+            MIRROR_IDX => Action::ToggleMirror,
+            GAMING_MODE_IDX => Action::MonitorPreset(MonitorPreset::Gaming),
+            DESKTOP_MODE_IDX => Action::MonitorPreset(MonitorPreset::Desktop),
+            BLACK_SCREEN_IDX => Action::ToggleBlackScreen,
+            PERSIST_IDX => Action::TogglePersist,
+            SAVE_OPTION_IDX => Action::SaveToFile,
+            DISABLE_OPTION_IDX => Action::DisableMonitor,
+            _ => return None,
+        })
+    }
+
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::PickMode => self.open_mode_picker(),
+            Action::CustomMode => self.open_custom_mode_input(),
+            Action::VirtualResolution => self.open_virtual_resolution_input(),
+            Action::SuggestScale => self.suggest_scale(),
+            Action::SetPosition => self.open_position_input(),
+            Action::ApplyChanges => self.apply_changes(),
+            Action::QueueChanges => self.queue_changes(),
+            Action::ReviewChanges => self.open_review(),
+            Action::SetMain => self.set_as_main(),
+            Action::ExtendLeft => self.extend_relative("left"),
+            Action::ExtendRight => self.extend_relative("right"),
+            Action::ToggleMirror => self.toggle_mirror(),
+            Action::MonitorPreset(preset) => self.apply_monitor_preset(preset),
+            Action::ToggleBlackScreen => self.toggle_dpms(),
+            Action::WakeAll => {
+                let result = self.wake_all();
+                self.report(result);
+            }
+            Action::DisableMonitor => self.disable_monitor(),
+            Action::Solo => {
+                if let Some(idx) = self.monitor_list_state.selected() {
+                    let result = self.solo(idx);
+                    self.report(result);
+                    self.refresh_after_change();
+                }
+            }
+            Action::EnableAll => {
+                let result = self.enable_all();
+                self.report(result);
+                self.refresh_after_change();
+            }
+            Action::TogglePersist => {
+                if let Some(idx) = self.monitor_list_state.selected() {
+                    self.toggle_persist(idx);
+                }
+            }
+            Action::SaveToFile => self.save_config_to_file(),
+            Action::ResetToSaved => self.reset_to_saved_and_reload(),
+            Action::Identify => self.identify_monitors(),
+            Action::ShowDetails => self.open_details(),
+            Action::ToggleMonitorOrder => self.toggle_monitor_order(),
+            Action::ShowConflicts => self.open_conflicts(),
+            Action::CycleTheme => self.cycle_theme(),
+            Action::ExportSvg => {
+                let result = self.export_svg();
+                self.report(result);
+            }
+            Action::FocusMonitor => self.focus_selected_monitor(),
+            Action::ToggleModeFilter => self.toggle_mode_filter(),
+            Action::ToggleLiveMode => self.toggle_live_mode(),
+            Action::AddVirtual => self.create_virtual_output(),
+            Action::RemoveVirtual => self.remove_virtual_output(),
+            Action::Template(template) => self.open_templates(Some(template)),
+            Action::ToggleGroup(group) => self.toggle_group(&group),
+            Action::ScaleGroup(group) => self.scale_group(&group),
+            Action::ToggleGroupDpms(group) => self.toggle_group_dpms(&group),
+            Action::DisableGroup(group) => self.disable_group(&group),
+            Action::Preset(preset) => {
+                let result = self.apply_preset(preset);
+                self.report(result);
+            }
+            Action::ApplyProfile(name) => self.apply_profile_named(&name),
+            Action::SwitchTab(tab) => self.switch_tab(tab),
+        }
+    }
+
+    fn switch_tab(&mut self, tab: Tab) {
+        self.tab = tab;
+        match tab {
+            Tab::Monitors => {}
+            Tab::Workspaces => self.refresh_workspaces(),
+            Tab::Profiles => self.refresh_profiles(),
+            Tab::Log => {
+                self.log_list_state = Self::init_list_state(self.history.entries().len());
+            }
+        }
+    }
+
+    fn cycle_tab(&mut self, forward: bool) {
+        let next = Self::cycle_selection(Some(self.tab.index()), Tab::ALL.len(), forward);
+        self.switch_tab(Tab::ALL[next.unwrap_or(0)]);
+    }
+
+    fn refresh_workspaces(&mut self) {
+        match commands::fetch_workspaces() {
+            Ok(data) => {
+                self.workspaces = data.iter().filter_map(Workspace::from_json).collect();
+                self.workspaces.sort_by_key(|w| w.id);
+            }
+            Err(e) => {
+                self.workspaces.clear();
+                self.error(tr_args("messages.workspaces_failed", &[("error", &e)]));
+            }
+        }
+        let selected = self.workspace_list_state.selected().unwrap_or(0);
+        self.workspace_list_state
+            .select(if self.workspaces.is_empty() {
+                None
+            } else {
+                Some(selected.min(self.workspaces.len() - 1))
+            });
+    }
+
+    fn refresh_profiles(&mut self) {
+        self.profiles = Profile::list().unwrap_or_else(|e| {
+            self.error(tr_args("messages.profiles_failed", &[("error", &e)]));
+            Vec::new()
+        });
+        self.profile_list_state = Self::init_list_state(self.profiles.len());
+    }
+
+    /// Keys for the list-based tabs. Returns whether the key was handled.
+    fn handle_tab_key(&mut self, code: KeyCode) -> bool {
+        let (state, count) = match self.tab {
+            Tab::Monitors => return false,
+            Tab::Workspaces => (&mut self.workspace_list_state, self.workspaces.len()),
+            Tab::Profiles => (&mut self.profile_list_state, self.profiles.len()),
+            Tab::Log => (&mut self.log_list_state, self.history.entries().len()),
+        };
+
+        if let Some(motion) = Motion::from_key(code) {
+            state.select(motion.apply(state.selected(), count));
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('h') | KeyCode::Left if self.tab == Tab::Workspaces => {
+                self.move_selected_workspace(false)
+            }
+            KeyCode::Char('l') | KeyCode::Right if self.tab == Tab::Workspaces => {
+                self.move_selected_workspace(true)
+            }
+            KeyCode::Enter if self.tab == Tab::Profiles => self.apply_selected_profile(),
+            KeyCode::Enter if self.tab == Tab::Log => self.rerun_selected_log_entry(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Moves the selected workspace to the next or previous active monitor.
+    fn move_selected_workspace(&mut self, forward: bool) {
+        let Some(workspace) = self
+            .workspace_list_state
+            .selected()
+            .and_then(|i| self.workspaces.get(i))
+        else {
+            return;
+        };
+        let active: Vec<&str> = self
+            .monitors
+            .iter()
+            .filter(|m| m.active)
+            .map(|m| m.name.as_str())
+            .collect();
+        let current = active.iter().position(|&name| name == workspace.monitor);
+        let Some(target) =
+            Self::cycle_selection(current, active.len(), forward).map(|i| active[i].to_string())
+        else {
+            return;
+        };
+        if Some(target.as_str()) == current.map(|i| active[i]) {
+            return;
+        }
+
+        let command = HyprCommand::move_workspace(workspace.id, &target);
+        let name = workspace.name.clone();
+        if self.run_command(&command) {
+            self.info(tr_args(
+                "messages.workspace_moved",
+                &[("workspace", &name), ("monitor", &target)],
+            ));
+        } else {
+            self.error(tr_args(
+                "messages.workspace_move_failed",
+                &[("workspace", &name), ("monitor", &target)],
+            ));
+        }
+        self.refresh_workspaces();
+    }
+
+    fn apply_selected_profile(&mut self) {
+        if let Some(name) = self
+            .profile_list_state
+            .selected()
+            .and_then(|i| self.profiles.get(i))
+            .cloned()
+        {
+            self.apply_profile_named(&name);
+        }
+    }
+
+    fn cycle_profile_and_refresh(&mut self, forward: bool) {
+        let result = self.cycle_profile(forward);
+        self.report(result);
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+        }
+    }
+
+    fn apply_profile_named(&mut self, name: &str) {
+        let profile = match Profile::load(name) {
+            Ok(profile) => profile,
+            Err(e) => return self.error(e.to_string()),
+        };
+        let connected: Vec<String> = self.monitors.iter().map(|m| m.name.clone()).collect();
+        let missing = profile.missing(&connected);
+        if missing.is_empty() {
+            self.apply_profile_and_refresh(&profile);
+            return;
+        }
+
+        let suggested = profile.suggest(&missing, &self.monitors);
+        let free = connected
+            .into_iter()
+            .filter(|name| !profile.monitors.iter().any(|m| &m.name == name))
+            .collect();
+        self.popup = Some(Popup::Reconcile(Reconcile {
+            entries: missing.into_iter().zip(suggested).collect(),
+            free,
+            state: ListState::default().with_selected(Some(0)),
+            profile,
+        }));
+    }
+
+    fn apply_profile_and_refresh(&mut self, profile: &Profile) {
+        let result = self.apply_profile(profile);
+        self.report(result);
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+        }
+    }
+
+    fn handle_reconcile_key(&mut self, code: KeyCode) {
+        let Some(Popup::Reconcile(reconcile)) = self.popup.as_mut() else {
+            return;
+        };
+        if let Some(motion) = Motion::from_key(code) {
+            let selection = motion.apply(reconcile.state.selected(), reconcile.entries.len());
+            reconcile.state.select(selection);
+            return;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.popup = None,
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Char('l') | KeyCode::Right => {
+                let Some(row) = reconcile.state.selected() else {
+                    return;
+                };
+                let forward = matches!(code, KeyCode::Char('l') | KeyCode::Right);
+                // Skipping, or any output no other entry is using.
+                let taken: Vec<&String> = reconcile
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != row)
+                    .filter_map(|(_, (_, output))| output.as_ref())
+                    .collect();
+                let options: Vec<Option<String>> = std::iter::once(None)
+                    .chain(
+                        reconcile
+                            .free
+                            .iter()
+                            .filter(|name| !taken.contains(name))
+                            .map(|name| Some(name.clone())),
+                    )
+                    .collect();
+                let current = options
+                    .iter()
+                    .position(|option| *option == reconcile.entries[row].1);
+                let next = Self::cycle_selection(current, options.len(), forward).unwrap_or(0);
+                reconcile.entries[row].1 = options[next].clone();
+            }
+            KeyCode::Enter => {
+                let Some(Popup::Reconcile(reconcile)) = self.popup.take() else {
+                    return;
+                };
+                let profile = reconcile.profile.remapped(&reconcile.entries);
+                self.apply_profile_and_refresh(&profile);
+            }
+            _ => {}
+        }
+    }
+
+    fn rerun_selected_log_entry(&mut self) {
+        let command = self
+            .log_list_state
+            .selected()
+            .and_then(|i| self.history.entries().iter().rev().nth(i))
+            .map(|entry| entry.command.clone());
+        if let Some(command) = command {
+            if self.run_command(&command) {
+                self.info(tr_args("messages.rerun", &[("command", &command)]));
+            } else {
+                self.error(tr_args("messages.rerun_failed", &[("command", &command)]));
+            }
+            // Keep the selection on the same entry now that a new one is on top.
+            self.log_list_state
+                .select(self.log_list_state.selected().map(|i| i + 1));
+        }
+    }
+
+    fn open_presets(&mut self) {
+        self.popup = Some(Popup::Presets(Self::init_list_state(Preset::ALL.len())));
+    }
+
+    fn open_templates(&mut self, template: Option<Template>) {
+        let active = self.monitors.iter().filter(|m| m.active).count();
+        if let Some(template) = template.filter(|t| active < t.slots()) {
+            self.error(tr_args(
+                "messages.template_needs",
+                &[
+                    ("template", &template.label()),
+                    ("count", &template.slots()),
+                ],
+            ));
+            return;
+        }
+        let len = match template {
+            Some(_) => active,
+            None => Template::ALL.len(),
+        };
+        self.popup = Some(Popup::Template(TemplateChooser {
+            template,
+            chosen: Vec::new(),
+            state: Self::init_list_state(len),
+        }));
+    }
+
+    fn handle_template_key(&mut self, code: KeyCode) {
+        let Some(Popup::Template(chooser)) = self.popup.as_mut() else {
+            return;
+        };
+        let candidates = chooser.candidates(&self.monitors);
+        let len = match chooser.template {
+            Some(_) => candidates.len(),
+            None => Template::ALL.len(),
+        };
+        if let Some(motion) = Motion::from_key(code) {
+            chooser
+                .state
+                .select(motion.apply(chooser.state.selected(), len));
+            return;
+        }
+        if !matches!(code, KeyCode::Enter) {
+            if matches!(code, KeyCode::Esc | KeyCode::Char('q')) {
+                self.popup = None;
+            }
+            return;
+        }
+        let Some(selected) = chooser.state.selected() else {
+            return;
+        };
+
+        let Some(template) = chooser.template else {
+            let template = Template::ALL[selected];
+            if candidates.len() < template.slots() {
+                self.popup = None;
+                self.error(tr_args(
+                    "messages.template_needs",
+                    &[
+                        ("template", &template.label()),
+                        ("count", &template.slots()),
+                    ],
+                ));
+                return;
+            }
+            chooser.template = Some(template);
+            chooser.state = Self::init_list_state(candidates.len());
+            return;
+        };
+        chooser.chosen.push(candidates[selected]);
+        if chooser.chosen.len() < template.slots() {
+            chooser.state = Self::init_list_state(candidates.len() - 1);
+            return;
+        }
+
+        let chosen = std::mem::take(&mut chooser.chosen);
+        self.popup = None;
+        let result = self.apply_template(template, &chosen);
+        self.report(result);
+    }
+
+    /// Positions the `chosen` monitors, one per slot of `template`, and
+    /// applies them together.
+    pub fn apply_template(
+        &mut self,
+        template: Template,
+        chosen: &[usize],
+    ) -> Result<String, String> {
+        let sizes = chosen
+            .iter()
+            .map(|&idx| {
+                self.configs[idx]
+                    .logical_size()
+                    .ok_or_else(|| format!("{} has no usable resolution", self.monitors[idx].name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for (&idx, position) in chosen.iter().zip(template.positions(&sizes)) {
+            self.configs[idx].position = Some(position);
+        }
+
+        let commands: Vec<HyprCommand> = chosen
+            .iter()
+            .map(|&idx| HyprCommand::monitor(self.monitor_rule(idx)))
+            .collect();
+        let result = self.run_batch(&commands, &format!("layout {}", template.label()));
+        if result.is_ok() {
+            for &idx in chosen {
+                self.monitors[idx].applied = AppliedMode::of(&self.configs[idx]);
+            }
+        }
+        result
+    }
+
+    /// Opens the setup wizard on a first run with monitors to set up.
+    pub fn offer_setup(&mut self) {
+        let candidates = SetupWizard::candidates(&self.monitors);
+        if candidates.is_empty() || !setup::is_first_run(&self.saved_conf) {
+            return;
+        }
+        let primary = self
+            .monitors
+            .iter()
+            .find(|m| m.focused && m.active)
+            .unwrap_or(&self.monitors[candidates[0]])
+            .name
+            .clone();
+        self.popup = Some(Popup::Setup(SetupWizard {
+            step: SetupStep::Primary,
+            primary,
+            arrangement: Arrangement::Keep,
+            per_host: false,
+            state: Self::init_list_state(candidates.len()),
+        }));
+    }
+
+    fn handle_setup_key(&mut self, code: KeyCode) {
+        let Some(Popup::Setup(wizard)) = self.popup.as_mut() else {
+            return;
+        };
+        if let Some(motion) = Motion::from_key(code) {
+            let len = wizard.choice_count(&self.monitors);
+            wizard
+                .state
+                .select(motion.apply(wizard.state.selected(), len));
+            return;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.popup = None;
+                self.info(tr("messages.setup_skipped"));
+                return;
+            }
+            KeyCode::Enter => {}
+            _ => return,
+        }
+        let Some(selected) = wizard.state.selected() else {
+            return;
+        };
+
+        match wizard.step {
+            SetupStep::Primary => {
+                let Some(&idx) = SetupWizard::candidates(&self.monitors).get(selected) else {
+                    return;
+                };
+                wizard.primary = self.monitors[idx].name.clone();
+                wizard.ask(SetupStep::Arrangement, Arrangement::ALL.len());
+            }
+            SetupStep::Arrangement => {
+                wizard.arrangement = Arrangement::ALL[selected];
+                wizard.ask(SetupStep::SavePath, 2);
+            }
+            SetupStep::SavePath => {
+                wizard.per_host = selected == 1;
+                let sourced = hyprconf::read_hyprland_conf()
+                    .is_ok_and(|contents| hyprconf::sources_monitors_conf(&contents));
+                if sourced {
+                    self.finish_setup(false);
+                } else {
+                    wizard.ask(SetupStep::Source, 2);
+                }
+            }
+            SetupStep::Source => self.finish_setup(selected == 0),
+        }
+    }
+
+    /// Applies and saves what was chosen in the setup wizard.
+    fn finish_setup(&mut self, add_source: bool) {
+        let Some(Popup::Setup(wizard)) = self.popup.take() else {
+            return;
+        };
+        let Some(primary) = self.monitors.iter().position(|m| m.name == wizard.primary) else {
+            self.warn(tr_args(
+                "messages.popup_monitor_gone",
+                &[("monitor", &wizard.primary)],
+            ));
+            return;
+        };
+        let name = wizard.primary.clone();
+        self.main_monitor = Some(name.clone());
+        let _ = main_monitor::store(&name);
+
+        let mut order = vec![primary];
+        order.extend(
+            SetupWizard::candidates(&self.monitors)
+                .into_iter()
+                .filter(|&i| i != primary),
+        );
+        let sizes: Option<Vec<(u32, u32)>> = order
+            .iter()
+            .map(|&i| self.configs[i].logical_size())
+            .collect();
+        let positions = sizes.and_then(|sizes| wizard.arrangement.positions(&sizes));
+        if let Some(positions) = positions {
+            for (&idx, position) in order.iter().zip(positions) {
+                self.configs[idx].position = Some(position);
+            }
+            let commands: Vec<HyprCommand> = order
+                .iter()
+                .map(|&idx| HyprCommand::monitor(self.monitor_rule(idx)))
+                .collect();
+            let result = self.run_batch(&commands, wizard.arrangement.label());
+            self.report(result);
+        }
+
+        if add_source {
+            match hyprconf::add_source_line() {
+                Ok(path) => self.info(tr_args(
+                    "messages.source_added",
+                    &[("path", &path.display())],
+                )),
+                Err(e) => self.error(tr_args("messages.conf_update_failed", &[("error", &e)])),
+            }
+        }
+        self.config.profiles.per_host = wizard.per_host;
+        if let Err(e) = setup::write_config(wizard.per_host) {
+            self.error(tr_args("messages.write_failed", &[("error", &e)]));
+        }
+        self.write_config_file(&[]);
+    }
+
+    fn handle_presets_key(&mut self, code: KeyCode) {
+        let Some(Popup::Presets(state)) = self.popup.as_mut() else {
+            return;
+        };
+        if let Some(motion) = Motion::from_key(code) {
+            state.select(motion.apply(state.selected(), Preset::ALL.len()));
+            return;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('p') => self.popup = None,
+            KeyCode::Enter => {
+                let preset = state.selected().map(|i| Preset::ALL[i]);
+                self.popup = None;
+                if let Some(preset) = preset {
+                    let result = self.apply_preset(preset);
+                    self.report(result);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn apply_preset(&mut self, preset: Preset) -> Result<String, String> {
+        let commands = preset.commands(&self.monitors, &self.configs)?;
+        self.run_batch(&commands, &format!("preset {}", preset.label()))
+    }
+
+    pub fn apply_profile(&mut self, profile: &Profile) -> Result<String, String> {
+        if profile.monitors.is_empty() {
+            return Err(format!("Profile {} has no monitors", profile.name));
+        }
+        self.wallpapers = profile.wallpapers.clone();
+        let result = self.run_batch(&profile.commands(), &format!("profile {}", profile.name));
+        if result.is_ok() {
+            hooks::run(&profile.on_apply);
+            // Only used to continue cycling from here; not worth failing over.
+            let _ = profile::remember_applied(&profile.name);
+        }
+        result
+    }
+
+    /// Re-applies the `monitor=` lines of the saved configuration, undoing
+    /// whatever was changed live since Hyprland started.
+    pub fn reset_to_saved(&mut self) -> Result<String, String> {
+        let hyprland_conf = hyprconf::read_hyprland_conf().map_err(|e| e.to_string())?;
+        let monitors_conf = hyprconf::read_monitors_conf().map_err(|e| e.to_string())?;
+        let commands: Vec<HyprCommand> = hyprconf::saved_rules(&hyprland_conf, &monitors_conf)
+            .into_iter()
+            .map(HyprCommand::monitor)
+            .collect();
+        if commands.is_empty() {
+            return Err(tr("messages.nothing_saved").to_string());
+        }
+        self.run_batch(&commands, "saved configuration")
+    }
+
+    fn reset_to_saved_and_reload(&mut self) {
+        let result = self.reset_to_saved();
+        self.report(result);
+        self.live_change = None;
+
+        // Drop unapplied edits so the options show what is running now.
+        let selected_name = self
+            .selected_monitor()
+            .map(|idx| self.monitors[idx].name.clone());
+        self.monitors.clear();
+        self.configs.clear();
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+            return;
+        }
+        if let Some(idx) =
+            selected_name.and_then(|name| self.monitors.iter().position(|m| m.name == name))
+        {
+            self.monitor_list_state.select(Some(idx));
+        }
+    }
+
+    /// Applies the next (or previous) saved profile that fits the connected
+    /// monitors.
+    pub fn cycle_profile(&mut self, forward: bool) -> Result<String, String> {
+        let connected: Vec<String> = self.monitors.iter().map(|m| m.name.clone()).collect();
+        let profile = Profile::cycle(&connected, forward)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| tr("messages.no_profile_fits").to_string())?;
+        self.apply_profile(&profile)
+    }
+
+    /// Puts back a layout captured with `state dump`, checking it against
+    /// this Hyprland first.
+    pub fn restore_state(&mut self, state: &State) -> Result<String, String> {
+        let commands = state.commands(&self.monitors, &self.configs);
+        if commands.is_empty() {
+            return Err(String::from("None of the saved monitors are connected"));
+        }
+        if state.monitors.iter().any(|m| m.color_mode.is_some()) {
+            self.require(Feature::ColorManagement)?;
+        }
+        self.run_batch(&commands, "saved state")
+    }
+
+    /// The live layout as a profile named `name`.
+    pub fn current_profile(&self, name: &str) -> Profile {
+        let monitors = self
+            .monitors
+            .iter()
+            .zip(&self.configs)
+            .map(|(monitor, config)| ProfileMonitor {
+                name: monitor.name.clone(),
+                enabled: monitor.active,
+                mode: if monitor.active && !config.resolution.is_empty() {
+                    monitor.mode_spec(config)
+                } else {
+                    String::from("preferred")
+                },
+                position: config.position_spec(),
+                scale: config.scale_as_float(),
+            })
+            .collect();
+
+        Profile {
+            name: name.to_string(),
+            on_apply: Vec::new(),
+            wallpapers: Wallpapers::new(),
+            power: None,
+            blanking: None,
+            monitors,
+        }
+    }
+
+    fn run_batch(&mut self, commands: &[HyprCommand], what: &str) -> Result<String, String> {
+        let results = commands::execute_batch(commands);
+        for (command, &success) in commands.iter().zip(&results) {
+            self.record(command, success);
+        }
+        let failed = results.iter().filter(|&&success| !success).count();
+
+        if failed == 0 {
+            hooks::run(&self.config.hooks.on_apply);
+            let names: Vec<&str> = self.monitors.iter().map(|m| m.name.as_str()).collect();
+            hooks::run(&wallpaper::commands(
+                &self.config.wallpaper,
+                &self.wallpapers,
+                &names,
+            ));
+            Ok(tr_args("messages.applied", &[("what", &what)]))
+        } else {
+            Err(tr_args(
+                "messages.apply_commands_failed",
+                &[
+                    ("what", &what),
+                    ("failed", &failed),
+                    ("total", &commands.len()),
+                ],
+            ))
+        }
+    }
+
+    /// Runs a command against Hyprland and records it in the history.
+    fn run_command(&mut self, command: &HyprCommand) -> bool {
+        let success = commands::execute_hyprctl(command);
+        self.record(command, success);
+        success
+    }
+
+    fn record(&mut self, command: &HyprCommand, success: bool) {
+        self.history.record(command, success);
+        if success
+            && command
+                .args()
+                .starts_with(&["keyword".to_string(), "monitor".to_string()])
+        {
+            self.layout_generation += 1;
+            if let Some(rule) = command.args().get(2) {
+                self.log_layout_change(rule);
+            }
+        }
+    }
+
+    /// Adds an applied `monitor` rule to the layout log, next to what the
+    /// monitor was running before.
+    fn log_layout_change(&mut self, rule: &str) {
+        let Some((name, after)) = layout_log::split_rule(rule) else {
+            return;
+        };
+        let before = self
+            .logged_rules
+            .insert(name.to_string(), after.to_string())
+            .or_else(|| {
+                self.monitors
+                    .iter()
+                    .find(|m| m.name == name)
+                    .map(layout_log::live_rule)
+            })
+            .unwrap_or_else(|| String::from("none"));
+        if before == after {
+            return;
+        }
+        // Like the command history, the log must not get in the way of
+        // the change itself.
+        let _ = layout_log::append(&LayoutChange {
+            timestamp: Local::now(),
+            monitor: name.to_string(),
+            before,
+            after: after.to_string(),
+        });
+    }
+
+    fn adjust_reserved(&mut self, mon_idx: usize, side: usize, increase: bool, fine: bool) {
+        let step = if fine { 1 } else { RESERVED_STEP };
+        let value = &mut self.configs[mon_idx].reserved[side];
+        *value = if increase {
+            value.saturating_add(step)
+        } else {
+            value.saturating_sub(step)
+        };
+    }
+
+    fn cycle_color_mode(&mut self, idx: usize, increase: bool) {
+        if let Err(e) = self.require(Feature::ColorManagement) {
+            self.warn(e);
+            return;
+        }
+        let count = ColorMode::ALL.len();
+        let config = &mut self.configs[idx];
+        let current = ColorMode::ALL
+            .iter()
+            .position(|mode| *mode == config.color_mode)
+            .unwrap_or(0);
+        let next = if increase {
+            (current + 1) % count
+        } else {
+            (current + count - 1) % count
+        };
+        config.color_mode = ColorMode::ALL[next];
+    }
+
+    /// Fails with a "requires Hyprland ≥ X" message if the running Hyprland
+    /// is too old for `feature`.
+    pub fn require(&self, feature: Feature) -> Result<(), String> {
+        match self.hyprland_version {
+            Some(version) if !version.supports(feature) => Err(format!(
+                "{} requires Hyprland ≥ {} (running {})",
+                feature.label(),
+                feature.min_version(),
+                version
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that Hyprland understands every option in the rule for `idx`.
+    fn require_rule_features(&self, idx: usize) -> Result<(), String> {
+        if self.configs[idx].color_mode != ColorMode::Default {
+            self.require(Feature::ColorManagement)?;
+        }
+        Ok(())
+    }
+
+    fn cycle_lid_rule(&mut self, increase: bool) {
+        let count = LidRule::ALL.len();
+        let current = LidRule::ALL
+            .iter()
+            .position(|rule| *rule == self.lid_rule)
+            .unwrap_or(0);
+        let next = if increase {
+            (current + 1) % count
+        } else {
+            (current + count - 1) % count
+        };
+        self.lid_rule = LidRule::ALL[next];
+        self.info(tr("messages.lid_rule_pending"));
+    }
+
+    /// Handles the lid-close `bindl` written for `LidRule`.
+    pub fn lid_closed(&mut self, only_with_external: bool) -> Result<String, String> {
+        let internal = self
+            .monitors
+            .iter()
+            .find(|m| m.is_internal())
+            .map(|m| m.name.clone())
+            .ok_or("No internal panel detected")?;
+        let has_external = self.monitors.iter().any(|m| m.active && !m.is_internal());
+
+        if only_with_external && !has_external {
+            return Ok(format!("No external monitor, leaving {} on", internal));
+        }
+
+        let command = HyprCommand::monitor(format!("{},disable", internal));
+        if self.run_command(&command) {
+            Ok(format!("Disabled {}", internal))
+        } else {
+            Err(format!("Failed to disable {}", internal))
+        }
+    }
+
+    /// Handles the lid-open `bindl` written for `LidRule`.
+    pub fn lid_opened(&mut self) -> Result<String, String> {
+        let internal = self
+            .monitors
+            .iter()
+            .find(|m| m.is_internal())
+            .map(|m| m.name.clone())
+            .ok_or("No internal panel detected")?;
+
+        if self.run_command(&lid::enable_command(&internal)) {
+            Ok(format!("Enabled {}", internal))
+        } else {
+            Err(format!("Failed to enable {}", internal))
+        }
+    }
+
+    fn adjust_scale(&mut self, mon_idx: usize, increase: bool, fine: bool) {
+        let settings = &self.config.scale;
+        let step = settings.step_percent(fine);
+        let config = &mut self.configs[mon_idx];
+        config.scale = (config.scale + if increase { step } else { -step })
+            .clamp(settings.min_percent(), settings.max_percent());
+    }
+
+    /// Smallest scale (in coarse step increments, never below 1.0) that brings
+    /// the logical density down to roughly `TARGET_LOGICAL_DPI`.
+    fn suggested_scale(&self, dpi: f64) -> i32 {
+        let settings = &self.config.scale;
+        let step = settings.step_percent(false);
+        let raw = (dpi / TARGET_LOGICAL_DPI * 100.0).ceil() as i32;
+        let stepped = (raw + step - 1) / step * step;
+        stepped.max(100).min(settings.max_percent())
+    }
+
+    fn suggest_scale(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+
+        let Some(dpi) = self.monitors[idx].dpi(&self.configs[idx].resolution) else {
+            self.warn(tr_args(
+                "messages.no_physical_size",
+                &[("monitor", &self.monitors[idx].name)],
+            ));
+            return;
+        };
+
+        let scale = self.suggested_scale(dpi);
+        self.configs[idx].scale = scale;
+        let scale = self.configs[idx].scale_as_float();
+        self.info(tr_args(
+            "messages.suggested_scale",
+            &[
+                ("dpi", &format!("{:.0}", dpi)),
+                ("scale", &format!("{:.2}", scale)),
+                ("logical", &format!("{:.0}", dpi / scale)),
+            ],
+        ));
+    }
+
+    fn get_other_monitor_info(&self, current_idx: usize) -> Option<(usize, String)> {
+        self.monitors
+            .iter()
+            .enumerate()
+            .find(|(i, m)| *i != current_idx && m.active)
+            .map(|(i, m)| (i, m.name.clone()))
+    }
+
+    fn set_as_main(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        let monitor = &self.monitors[idx];
+        let config = &self.configs[idx];
+
+        let command = HyprCommand::monitor(format!(
+            "{},preferred,{},auto,{:.2}",
+            monitor.name,
+            monitor.mode_spec(config),
+            config.scale_as_float()
+        ));
+        let name = monitor.name.clone();
+        if !self.run_command(&command) {
+            self.error(tr_args("messages.set_main_failed", &[("monitor", &name)]));
+            return;
+        }
+        self.main_monitor = Some(name.clone());
+        // The marker is a convenience; not worth failing the action over.
+        let _ = main_monitor::store(&name);
+        if self.config.main.bind_workspace {
+            let rule = main_monitor::workspace_rule(&name);
+            self.run_command(&HyprCommand::new(["keyword", "workspace", &rule]));
+            self.run_command(&HyprCommand::move_workspace(1, &name));
+        }
+
+        if !xwayland::available() {
+            self.info(tr_args("messages.set_main", &[("monitor", &name)]));
+        } else if xwayland::set_primary(&name) {
+            self.xwayland_primary = Some(name.clone());
+            self.info(tr_args("messages.set_main_primary", &[("monitor", &name)]));
+        } else {
+            self.warn(tr_args(
+                "messages.set_main_no_primary",
+                &[("monitor", &name)],
+            ));
+        }
+    }
+
+    fn extend_relative(&mut self, direction: &str) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+
+        if let Some((_, other_monitor_name)) = self.get_other_monitor_info(idx) {
+            let command = HyprCommand::monitor(format!(
+                "{},{},auto,{:.2},{}of,{}",
+                self.monitors[idx].name,
+                self.mode_token(idx),
+                self.configs[idx].scale_as_float(),
+                direction,
+                other_monitor_name
+            ));
+            self.run_command(&command);
+        }
+    }
+
+    fn toggle_mirror(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+
+        if self.monitors[idx].mirror_of.is_some() {
+            self.unmirror_monitor(idx);
+        } else {
+            self.mirror_monitor(idx);
+        }
+
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+        }
+    }
+
+    /// Turns a mirroring monitor back into an independent, extended output.
+    fn unmirror_monitor(&mut self, idx: usize) {
+        let name = self.monitors[idx].name.clone();
+        let source = self.monitors[idx].mirror_of.clone().unwrap_or_default();
+        let result = self
+            .apply_monitor(idx)
+            .map(|_| format!("{} no longer mirrors {}", name, source));
+        self.report(result);
+    }
+
+    /// Makes monitor `idx` mirror another active one, switching it to the
+    /// mode that shows the source's picture with the least cropping or
+    /// letterboxing.
+    fn mirror_monitor(&mut self, idx: usize) {
+        let Some((source_idx, source_name)) = self.get_other_monitor_info(idx) else {
+            return;
+        };
+        let source = &self.configs[source_idx];
+        let Some((resolution, rate, fit)) = monitor::mirror_mode(
+            &self.monitors[idx].modes,
+            &source.resolution,
+            source.refresh_rate,
+        ) else {
+            self.error(tr_args(
+                "messages.mirror_no_mode",
+                &[("source", &source_name)],
+            ));
+            return;
+        };
+
+        let name = self.monitors[idx].name.clone();
+        let config = &mut self.configs[idx];
+        config.resolution = resolution.clone();
+        config.refresh_rate = rate.value;
+        self.sync_mode_indices(idx);
+        if let Err(message) = self.require_rule_features(idx) {
+            self.error(message);
+            return;
+        }
+
+        let command =
+            HyprCommand::monitor(format!("{},mirror,{}", self.monitor_rule(idx), source_name));
+        if !self.run_command(&command) {
+            self.error(tr_args(
+                "messages.mirror_failed",
+                &[("source", &source_name), ("monitor", &name)],
+            ));
+            return;
+        }
+        let mode = format!("{}@{:.2}", resolution, rate.value);
+        let args: [(&str, &dyn std::fmt::Display); 3] = [
+            ("monitor", &name),
+            ("source", &source_name),
+            ("mode", &mode),
+        ];
+        match fit {
+            MirrorFit::Exact => self.info(tr_args("messages.mirrored", &args)),
+            MirrorFit::Scaled => self.info(tr_args("messages.mirrored_scaled", &args)),
+            MirrorFit::Letterboxed => self.warn(tr_args("messages.mirrored_letterboxed", &args)),
+        }
+    }
+
+    fn toggle_dpms(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+
+        if let Err(message) = self.set_dpms(idx, !self.monitors[idx].dpms_on) {
+            self.error(message);
+        }
+    }
+
+    pub fn set_dpms(&mut self, idx: usize, on: bool) -> Result<String, String> {
+        let state = if on { "on" } else { "off" };
+        let monitor_name = self.monitors[idx].name.clone();
+        let command = HyprCommand::dispatch("dpms", format!("{} {}", state, monitor_name));
+
+        let success = self.run_command(&command);
+        if success {
+            self.monitors[idx].dpms_on = on;
+        }
+        let key = match (on, success) {
+            (true, true) => "messages.dpms_turned_on",
+            (false, true) => "messages.dpms_turned_off",
+            (true, false) => "messages.dpms_on_failed",
+            (false, false) => "messages.dpms_off_failed",
+        };
+        let message = tr_args(key, &[("monitor", &monitor_name)]);
+        if success {
+            Ok(message)
+        } else {
+            Err(message)
+        }
+    }
+
+    /// Turns the display of every monitor on, for panels that went to
+    /// sleep and don't wake up on input.
+    pub fn wake_all(&mut self) -> Result<String, String> {
+        if !self.run_command(&HyprCommand::dispatch("dpms", "on")) {
+            return Err(tr("messages.wake_failed").to_string());
+        }
+        // Disabled outputs have no panel to wake.
+        for monitor in self.monitors.iter_mut().filter(|m| m.active) {
+            monitor.dpms_on = true;
+        }
+        Ok(tr("messages.woke_all").to_string())
+    }
+
+    /// Disables every active monitor except `idx`, in one batch.
+    pub fn solo(&mut self, idx: usize) -> Result<String, String> {
+        let name = self.monitors[idx].name.clone();
+        if !self.monitors[idx].active {
+            return Err(tr_args("messages.solo_inactive", &[("monitor", &name)]));
+        }
+        let others: Vec<usize> = (0..self.monitors.len())
+            .filter(|&i| i != idx && self.monitors[i].active)
+            .collect();
+        if others.is_empty() {
+            return Ok(tr_args("messages.solo_already", &[("monitor", &name)]));
+        }
+        let commands: Vec<HyprCommand> = others
+            .iter()
+            .map(|&i| HyprCommand::monitor(format!("{},disable", self.monitors[i].name)))
+            .collect();
+        self.run_batch(&commands, &format!("solo {}", name))?;
+        for &i in &others {
+            self.configs[i].save_disabled = true;
+        }
+        Ok(tr_args(
+            "messages.solo",
+            &[("monitor", &name), ("count", &others.len())],
+        ))
+    }
+
+    /// Turns every disabled monitor back on with its settings, in one batch.
+    pub fn enable_all(&mut self) -> Result<String, String> {
+        let disabled: Vec<usize> = (0..self.monitors.len())
+            .filter(|&i| !self.monitors[i].active)
+            .collect();
+        if disabled.is_empty() {
+            return Ok(tr("messages.all_enabled").to_string());
+        }
+        let commands: Vec<HyprCommand> = disabled
+            .iter()
+            .map(|&i| {
+                if self.configs[i].resolution.is_empty() {
+                    HyprCommand::monitor(format!("{},preferred,auto,1", self.monitors[i].name))
+                } else {
+                    HyprCommand::monitor(self.monitor_rule(i))
+                }
+            })
+            .collect();
+        self.run_batch(&commands, "all monitors")?;
+        for &i in &disabled {
+            self.configs[i].save_disabled = false;
+        }
+        Ok(tr_args(
+            "messages.enabled_all",
+            &[("count", &disabled.len())],
+        ))
+    }
+
+    /// Reads the monitors back after a change that turned some on or off.
+    fn refresh_after_change(&mut self) {
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+        }
+    }
+
+    /// Turns every monitor off, as [`Self::wake_all`] turns them on.
+    pub fn blank_all(&mut self) -> Result<String, String> {
+        if !self.run_command(&HyprCommand::dispatch("dpms", "off")) {
+            return Err(tr("messages.blank_failed").to_string());
+        }
+        for monitor in self.monitors.iter_mut().filter(|m| m.active) {
+            monitor.dpms_on = false;
+        }
+        Ok(tr("messages.blanked_all").to_string())
+    }
+
+    fn apply_changes(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        if let Some(ratio) = self
+            .shrink_ratio(idx)
+            .filter(|&ratio| ratio < SHRINK_WARNING_RATIO)
+        {
+            let workspaces = self.workspaces_on(idx);
+            if !workspaces.is_empty() {
+                self.popup = Some(Popup::ShrinkConfirm(ShrinkConfirm {
+                    monitor: idx,
+                    workspaces,
+                    ratio,
+                    state: Self::init_list_state(SHRINK_CHOICES.len()),
+                }));
+                return;
+            }
+        }
+        let result = self.apply_monitor(idx);
+        self.report(result);
+    }
+
+    /// The logical area the pending mode and scale give monitor `idx`, as a
+    /// share of what it is running at now. `None` for monitors that are off
+    /// or modes that can't be measured.
+    fn shrink_ratio(&self, idx: usize) -> Option<f64> {
+        let monitor = &self.monitors[idx];
+        if !monitor.active {
+            return None;
+        }
+        let (w, h) = monitor::parse_resolution(&monitor.applied.resolution)?;
+        let scale = monitor.applied.scale as f64 / 100.0;
+        let before = w as f64 * h as f64 / (scale * scale);
+        let (w, h) = self.configs[idx].logical_size()?;
+        (before > 0.0).then(|| w as f64 * h as f64 / before)
+    }
+
+    fn handle_shrink_confirm_key(&mut self, code: KeyCode) {
+        let Some(Popup::ShrinkConfirm(confirm)) = self.popup.as_mut() else {
+            return;
+        };
+
+        if let Some(motion) = Motion::from_key(code) {
+            let selection = motion.apply(confirm.state.selected(), SHRINK_CHOICES.len());
+            confirm.state.select(selection);
+            return;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.popup = None,
+            KeyCode::Enter => {
+                let Some(Popup::ShrinkConfirm(confirm)) = self.popup.take() else {
+                    return;
+                };
+                if confirm.state.selected() == Some(0) {
+                    let result = self.apply_monitor(confirm.monitor);
+                    self.report(result);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The mode field for monitor `idx`: a custom modeline if one is set,
+    /// with `[modes] strict` the token Hyprland advertised, otherwise one
+    /// rebuilt from the parsed parts.
+    fn mode_token(&self, idx: usize) -> String {
+        self.mode_token_of(idx, &self.configs[idx])
+    }
+
+    /// [`Self::mode_token`] for `config` in place of monitor `idx`'s pending
+    /// settings.
+    fn mode_token_of(&self, idx: usize, config: &MonitorConfig) -> String {
+        if let Some(modeline) = config.modeline_spec() {
+            return modeline;
+        }
+        self.config
+            .modes
+            .strict
+            .then(|| self.monitors[idx].advertised_mode(&config.resolution, config.refresh_rate))
+            .flatten()
+            .map_or_else(|| self.monitors[idx].mode_spec(config), str::to_string)
+    }
+
+    /// The `monitor` rule for monitor `idx`, with its mode from
+    /// [`Self::mode_token`].
+    fn monitor_rule(&self, idx: usize) -> String {
+        self.configs[idx].rule_with_mode(&self.monitors[idx].name, &self.mode_token(idx))
+    }
+
+    /// Applies the configuration of monitor `idx`, running the apply hooks on
+    /// success.
+    pub fn apply_monitor(&mut self, idx: usize) -> Result<String, String> {
+        self.require_rule_features(idx)?;
+        let name = self.monitors[idx].name.clone();
+        // Applying a mode turns the monitor back on.
+        self.configs[idx].save_disabled = false;
+        let commands = [
+            HyprCommand::monitor(self.monitor_rule(idx)),
+            HyprCommand::monitor(self.configs[idx].reserved_rule(&name)),
+        ];
+        let result = self.run_batch(&commands, &name);
+        if result.is_ok() {
+            self.monitors[idx].applied = AppliedMode::of(&self.configs[idx]);
+        }
+        result
+    }
+
+    /// Applies the options row `row` of the selected monitor on its own: the
+    /// scale and color rows keep the mode Hyprland is running, so it doesn't
+    /// modeset, and the reserved rows only send `addreserved`. Monitors
+    /// that are off need a full rule, so everything is applied instead.
+    fn apply_option_row(&mut self, row: usize) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        if !matches!(
+            row,
+            SCALE_IDX | COLOR_MODE_IDX | RESERVED_TOP_IDX..=RESERVED_RIGHT_IDX
+        ) {
+            return;
+        }
+        if !self.monitors[idx].active {
+            self.apply_changes();
+            return;
+        }
+        let name = self.monitors[idx].name.clone();
+        let command = match row {
+            SCALE_IDX | COLOR_MODE_IDX => {
+                let mut live = Self::live_config(&self.monitors[idx], &self.configs[idx]);
+                if row == SCALE_IDX {
+                    live.scale = self.configs[idx].scale;
+                } else {
+                    live.color_mode = self.configs[idx].color_mode;
+                    if live.color_mode != ColorMode::Default {
+                        if let Err(e) = self.require(Feature::ColorManagement) {
+                            self.error(e);
+                            return;
+                        }
+                    }
+                }
+                HyprCommand::monitor(live.rule_with_mode(&name, &self.mode_token_of(idx, &live)))
+            }
+            RESERVED_TOP_IDX..=RESERVED_RIGHT_IDX => {
+                HyprCommand::monitor(self.configs[idx].reserved_rule(&name))
+            }
+            _ => return,
+        };
+        let result = self.run_batch(&[command], &name);
+        if result.is_ok() && matches!(row, SCALE_IDX | COLOR_MODE_IDX) {
+            self.monitors[idx].applied.scale = self.configs[idx].scale;
+        }
+        self.report(result);
+    }
+
+    /// What `monitor` is running: the mode and scale applied last and the
+    /// rest as Hyprland last reported it, leaving out the pending edits in
+    /// `config`.
+    fn live_config(monitor: &Monitor, config: &MonitorConfig) -> MonitorConfig {
+        let mut live = Self::parse_single_monitor(&monitor.raw)
+            .and_then(Result::ok)
+            .map_or_else(|| config.clone(), |(_, config)| config);
+        live.resolution = monitor.applied.resolution.clone();
+        live.refresh_rate = monitor.applied.refresh_rate;
+        live.scale = monitor.applied.scale;
+        live.position = Some((monitor.x, monitor.y));
+        live.modeline = monitor.applied.modeline.clone();
+        live
+    }
+
+    /// Stages the rules of the selected monitor, replacing any it already
+    /// had in the queue.
+    fn queue_changes(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        if let Err(e) = self.require_rule_features(idx) {
+            self.error(e);
+            return;
+        }
+        let name = self.monitors[idx].name.clone();
+        self.queue.retain(|change| change.monitor != name);
+        self.queue.push(QueuedChange {
+            monitor: name.clone(),
+            command: HyprCommand::monitor(self.monitor_rule(idx)),
+            applied: Some(AppliedMode::of(&self.configs[idx])),
+        });
+        self.queue.push(QueuedChange {
+            monitor: name.clone(),
+            command: HyprCommand::monitor(self.configs[idx].reserved_rule(&name)),
+            applied: None,
+        });
+        self.info(tr_args(
+            "messages.queued",
+            &[("monitor", &name), ("count", &self.queue.len())],
+        ));
+    }
+
+    fn open_review(&mut self) {
+        if self.queue.is_empty() {
+            self.info(tr("messages.queue_empty"));
+            return;
+        }
+        self.popup = Some(Popup::Review(Self::init_list_state(self.queue.len())));
+    }
+
+    fn handle_review_key(&mut self, code: KeyCode) {
+        let Some(Popup::Review(state)) = self.popup.as_mut() else {
+            return;
+        };
+
+        if let Some(motion) = Motion::from_key(code) {
+            state.select(motion.apply(state.selected(), self.queue.len()));
+            return;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.popup = None,
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(i) = state.selected().filter(|&i| i < self.queue.len()) {
+                    self.queue.remove(i);
+                    if self.queue.is_empty() {
+                        self.popup = None;
+                    } else {
+                        state.select(Some(i.min(self.queue.len() - 1)));
+                    }
+                }
+            }
+            KeyCode::Enter => {
+                self.popup = None;
+                let result = self.run_queue();
+                self.report(result);
+            }
+            _ => {}
+        }
+    }
+
+    /// Runs every queued command as one batch and empties the queue.
+    fn run_queue(&mut self) -> Result<String, String> {
+        let queue = std::mem::take(&mut self.queue);
+        let commands: Vec<HyprCommand> =
+            queue.iter().map(|change| change.command.clone()).collect();
+        let result = self.run_batch(
+            &commands,
+            &tr_args("messages.queue_what", &[("count", &commands.len())]),
+        );
+        if result.is_ok() {
+            for change in queue {
+                let Some(applied) = change.applied else {
+                    continue;
+                };
+                if let Some(idx) = self.monitors.iter().position(|m| m.name == change.monitor) {
+                    // Applying a mode turns the monitor back on.
+                    self.configs[idx].save_disabled = false;
+                    self.monitors[idx].applied = applied;
+                }
+            }
+        }
+        result
+    }
+
+    /// Changes the mode of monitor `idx` without applying it.
+    pub fn set_mode(&mut self, idx: usize, resolution: String, refresh_rate: f64) {
+        let config = &mut self.configs[idx];
+        config.resolution = resolution;
+        config.refresh_rate = refresh_rate;
+        self.sync_mode_indices(idx);
+    }
+
+    pub fn set_refresh_rate(&mut self, idx: usize, refresh_rate: f64) -> Result<String, String> {
+        self.configs[idx].refresh_rate = refresh_rate;
+        self.sync_mode_indices(idx);
+        self.apply_monitor(idx)
+            .map(|_| format!("Set {} to {:.2} Hz", self.monitors[idx].name, refresh_rate))
+    }
+
+    fn apply_monitor_preset(&mut self, preset: MonitorPreset) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+
+        let mut commands = Vec::new();
+        for step in preset.steps() {
+            let config = &mut self.configs[idx];
+            match *step {
+                PresetStep::HighestRefreshRate => {
+                    let highest = self.monitors[idx]
+                        .modes
+                        .get(&config.resolution)
+                        .and_then(|rates| rates.first())
+                        .map(|rate| rate.value);
+                    // Pressed again, the rate from before the first press
+                    // is still the one to go back to.
+                    if let Some(highest) = highest {
+                        config
+                            .previous_refresh_rate
+                            .get_or_insert(config.refresh_rate);
+                        config.refresh_rate = highest;
+                    }
+                }
+                PresetStep::PreviousRefreshRate => {
+                    if let Some(previous) = config.previous_refresh_rate.take() {
+                        config.refresh_rate = previous;
+                    }
+                }
+                // Turning a setting on remembers it; turning it off puts
+                // back what it was before, if that was remembered.
+                PresetStep::Vrr(true) => {
+                    config.previous_vrr.get_or_insert(config.vrr);
+                    config.vrr = true;
+                }
+                PresetStep::Vrr(false) => config.vrr = config.previous_vrr.take().unwrap_or(false),
+                PresetStep::Tearing(enabled) => {
+                    let allow = if enabled {
+                        if config.previous_tearing.is_none() {
+                            config.previous_tearing = commands::fetch_allow_tearing().ok();
+                        }
+                        true
+                    } else {
+                        config.previous_tearing.take().unwrap_or(false)
+                    };
+                    commands.push(HyprCommand::new([
+                        "keyword",
+                        "general:allow_tearing",
+                        if allow { "true" } else { "false" },
+                    ]));
+                }
+            }
+        }
+        self.sync_mode_indices(idx);
+        if let Err(e) = self.require_rule_features(idx) {
+            self.warn(e);
+            return;
+        }
+
+        let monitor_name = self.monitors[idx].name.clone();
+        commands.insert(0, HyprCommand::monitor(self.monitor_rule(idx)));
+
+        let result = self.run_batch(
+            &commands,
+            &format!("{} on {}", preset.label(), monitor_name),
+        );
+        if result.is_ok() {
+            self.monitors[idx].applied = AppliedMode::of(&self.configs[idx]);
+        }
+        self.report(result);
+    }
+
+    /// Writes the layout diagram to the export path.
+    pub fn export_svg(&self) -> Result<String, String> {
+        let path = svg::export_path().map_err(|e| e.to_string())?;
+        fs::write(&path, svg::layout(&self.monitors, &self.configs))
+            .map(|_| format!("Exported layout to {}", path.display()))
+            .map_err(|e| format!("Error writing {}: {}", path.display(), e))
+    }
+
+    fn toggle_persist(&mut self, idx: usize) {
+        let config = &mut self.configs[idx];
+        config.persist = !config.persist;
+        let message = tr_args(
+            if config.persist {
+                "messages.persist_on"
+            } else {
+                "messages.persist_off"
+            },
+            &[("monitor", &self.monitors[idx].name)],
+        );
+        self.info(message);
+    }
+
+    /// Saved monitors that aren't connected, haven't been for a long time,
+    /// and aren't part of any profile.
+    pub fn stale_monitors(&self) -> Vec<String> {
+        let in_profiles: Vec<String> = Profile::list()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|name| Profile::load(name).ok())
+            .flat_map(|profile| profile.monitors.into_iter().map(|m| m.name))
+            .collect();
+        hyprconf::monitor_names(&self.saved_conf)
+            .into_iter()
+            .filter(|name| {
+                !name.is_empty()
+                    && !self.monitors.iter().any(|m| &m.name == name)
+                    && !self.is_ignored(name)
+                    && !in_profiles.contains(name)
+                    && !self.kept_stale.contains(name)
+                    && self.seen.is_stale(name)
+            })
+            .collect()
+    }
+
+    /// Saves, first asking about saved monitors that look abandoned.
+    fn save_config_to_file(&mut self) {
+        let stale = self.stale_monitors();
+        if stale.is_empty() {
+            self.write_config_file(&[]);
+            return;
+        }
+        self.popup = Some(Popup::Cleanup(Cleanup {
+            prune: vec![true; stale.len()],
+            names: stale,
+            state: Self::init_list_state(1),
+        }));
+    }
+
+    fn handle_cleanup_key(&mut self, code: KeyCode) {
+        let Some(Popup::Cleanup(cleanup)) = self.popup.as_mut() else {
+            return;
+        };
+
+        if let Some(motion) = Motion::from_key(code) {
+            let selection = motion.apply(cleanup.state.selected(), cleanup.names.len());
+            cleanup.state.select(selection);
+            return;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.popup = None,
+            KeyCode::Char(' ') => {
+                if let Some(i) = cleanup.state.selected() {
+                    cleanup.prune[i] = !cleanup.prune[i];
+                }
+            }
+            KeyCode::Enter => {
+                let Some(Popup::Cleanup(cleanup)) = self.popup.take() else {
+                    return;
+                };
+                let (pruned, kept): (Vec<_>, Vec<_>) = cleanup
+                    .names
+                    .into_iter()
+                    .zip(cleanup.prune)
+                    .partition(|(_, prune)| *prune);
+                self.kept_stale
+                    .extend(kept.into_iter().map(|(name, _)| name));
+                let pruned: Vec<String> = pruned.into_iter().map(|(name, _)| name).collect();
+                self.write_config_file(&pruned);
+            }
+            _ => {}
+        }
+    }
+
+    /// Writes the monitor configuration. Lines for monitors that aren't
+    /// connected are carried over from the saved file, except for `pruned`.
+    pub fn write_config_file(&mut self, pruned: &[String]) {
+        let expanded_path = match hyprconf::monitors_conf_path() {
+            Ok(p) => p,
+            Err(e) => {
+                self.error(tr_args("messages.path_failed", &[("error", &e)]));
+                return;
+            }
+        };
+
+        if let Some(parent) = expanded_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                self.error(tr_args("messages.create_dir_failed", &[("error", &e)]));
+                return;
+            }
+        }
+
+        let mut file_content = String::from("# Monitor settings generated by hypr-tui\n# Add 'source = ~/.config/hypr/monitors.conf' to your hyprland.conf\n\n");
+
+        let names: Vec<&str> = self.monitors.iter().map(|m| m.name.as_str()).collect();
+        for idx in main_monitor::main_first(&names, self.main_monitor.as_deref()) {
+            for value in hyprconf::monitor_conf_lines(&self.monitors[idx], &self.configs[idx]) {
+                file_content.push_str(&format!("monitor={}\n", value));
+            }
+        }
+
+        let disconnected: Vec<&str> = hyprconf::monitor_names(&self.saved_conf)
+            .into_iter()
+            .filter(|name| !self.monitors.iter().any(|m| &m.name == name))
+            .filter(|name| !self.is_ignored(name))
+            .filter(|name| !pruned.contains(name))
+            .flat_map(|name| hyprconf::effective_lines(&self.saved_conf, &name))
+            .collect();
+        if !disconnected.is_empty() {
+            file_content.push_str("\n# Monitors not connected when this was saved\n");
+            for value in disconnected {
+                file_content.push_str(&format!("monitor={}\n", value));
+            }
+        }
 
-        if let Some((_, other_monitor_name)) = self.get_other_monitor_info(idx) {
-            let monitor = &self.monitors[idx];
-            let config = &self.configs[idx];
-
-            let command = format!(
-                "hyprctl keyword monitor \"{},{}@{:.2},auto,{:.2},{}of,{}\"",
-                monitor.name,
-                config.resolution,
-                config.refresh_rate,
-                config.scale_as_float(),
-                direction,
-                other_monitor_name
-            );
-            commands::execute_hyprctl(&command);
+        if let Some(line) = self
+            .xwayland_primary
+            .as_deref()
+            .and_then(xwayland::config_line)
+        {
+            file_content.push_str("\n# XWayland primary output, set with Set as Main Screen\n");
+            file_content.push_str(&line);
+        }
+        if let Some(name) = self
+            .main_monitor
+            .as_deref()
+            .filter(|_| self.config.main.bind_workspace)
+        {
+            file_content.push_str("\n# Workspace 1 opens on the main screen\n");
+            file_content.push_str(&format!(
+                "workspace={}\n",
+                main_monitor::workspace_rule(name)
+            ));
+        }
+        file_content.push_str(&self.lid_rule.config_lines());
+
+        if self.config.profiles.per_host {
+            self.save_per_host(&expanded_path, &file_content);
+        } else {
+            match fs::write(&expanded_path, &file_content) {
+                Ok(_) => {
+                    self.saved_conf = file_content.clone();
+                    self.info(tr_args(
+                        "messages.saved",
+                        &[("path", &expanded_path.display())],
+                    ))
+                }
+                Err(e) => self.error(tr_args("messages.write_failed", &[("error", &e)])),
+            }
+        }
+
+        // hyprland.conf is only changed once asked to, from the conflicts
+        // list.
+        self.conflicts = Self::find_conflicts(&self.saved_conf);
+        let overriding = self.supersedable_conflicts().len();
+        if self.saved_conf == file_content && overriding > 0 {
+            self.warn(tr_args(
+                "messages.conflicts_after_save",
+                &[("count", &overriding)],
+            ));
         }
     }
 
-    fn mirror_monitor(&mut self) {
-        let Some(idx) = self.monitor_list_state.selected() else {
+    /// Re-reads `hyprland.conf` and looks for outputs with disagreeing
+    /// `monitor=` lines.
+    fn find_conflicts(saved_conf: &str) -> Vec<hyprconf::Conflict> {
+        hyprconf::hyprland_conf_path()
+            .and_then(fs::read_to_string)
+            .map(|hyprland_conf| hyprconf::find_conflicts(&hyprland_conf, saved_conf))
+            .unwrap_or_default()
+    }
+
+    /// Outputs whose `monitor=` lines in hyprland.conf disagree with lines
+    /// in the saved file.
+    fn supersedable_conflicts(&self) -> Vec<String> {
+        self.conflicts
+            .iter()
+            .filter(|conflict| conflict.in_hyprland_conf())
+            .filter(|conflict| {
+                !hyprconf::monitor_lines(&self.saved_conf, &conflict.name).is_empty()
+            })
+            .map(|conflict| conflict.name.clone())
+            .collect()
+    }
+
+    /// Comments out the `monitor=` lines in hyprland.conf that disagree with
+    /// the saved file, so the saved line is the only one left. Only done
+    /// when hyprland.conf sources the saved file; a backup that no later
+    /// change overwrites is written next to it first.
+    fn supersede_conflicting_lines(&mut self) {
+        self.conflicts = Self::find_conflicts(&self.saved_conf);
+        let names = self.supersedable_conflicts();
+        if names.is_empty() {
             return;
-        };
+        }
 
-        if let Some((other_idx, other_monitor_name)) = self.get_other_monitor_info(idx) {
-            let source_scale;
-            let source_resolution;
-            let source_refresh_rate;
-            let source_scale_float;
+        let result = hyprconf::hyprland_conf_path().and_then(|path| {
+            let contents = fs::read_to_string(&path)?;
+            if !hyprconf::sources_monitors_conf(&contents) {
+                return Ok(None);
+            }
+            let (updated, count) = hyprconf::comment_out_monitors(&contents, &names);
+            let backup = hyprconf::backup(&path, &contents)?;
+            fs::write(&path, updated)?;
+            Ok(Some((count, backup)))
+        });
+        match result {
+            Ok(None) => self.warn(tr("messages.conflicts_not_sourced")),
+            Ok(Some((count, backup))) => self.info(tr_args(
+                "messages.conflicts_commented",
+                &[("count", &count), ("backup", &backup.display())],
+            )),
+            Err(e) => self.error(tr_args("messages.conf_update_failed", &[("error", &e)])),
+        }
+        self.conflicts = Self::find_conflicts(&self.saved_conf);
+    }
 
-            {
-                let source_config = &self.configs[idx];
-                source_scale = source_config.scale;
-                source_resolution = source_config.resolution.clone();
-                source_refresh_rate = source_config.refresh_rate;
-                source_scale_float = source_config.scale_as_float();
+    /// Lists each output with conflicting `monitor=` lines and which one
+    /// Hyprland ends up using.
+    fn open_conflicts(&mut self) {
+        if self.conflicts.is_empty() {
+            self.info(tr("messages.no_conflicts"));
+            return;
+        }
+        let winner = tr("popups.conflicts_winner");
+        let blank = " ".repeat(winner.chars().count());
+        let mut lines = Vec::new();
+        for conflict in &self.conflicts {
+            lines.push(format!("{}:", conflict.name));
+            for rule in &conflict.lines {
+                let marker = if rule == conflict.winner() {
+                    winner
+                } else {
+                    &blank
+                };
+                lines.push(format!(
+                    "  {} {}:{}  {}",
+                    marker, rule.file, rule.line, rule.value
+                ));
             }
+            lines.push(String::new());
+        }
+        let supersede = !self.supersedable_conflicts().is_empty();
+        lines.push(
+            tr(if supersede {
+                "popups.conflicts_supersede"
+            } else {
+                "popups.conflicts_save_first"
+            })
+            .to_string(),
+        );
+        self.popup = Some(Popup::Details(Details {
+            title: tr("popups.conflicts_title").to_string(),
+            lines,
+            scroll: 0,
+            supersede,
+        }));
+    }
 
-            let source_monitor_name = &self.monitors[idx].name;
+    /// How many monitors would be saved differently from what is on disk.
+    pub fn unsaved_count(&self) -> usize {
+        self.monitors
+            .iter()
+            .zip(&self.configs)
+            .filter(|(monitor, config)| {
+                hyprconf::monitor_lines(&self.saved_conf, &monitor.name)
+                    != hyprconf::monitor_conf_lines(monitor, config)
+            })
+            .count()
+    }
 
-            self.configs[other_idx].scale = source_scale;
+    /// Writes the layout to `monitors-<hostname>.conf` and points
+    /// `monitors.conf` at it.
+    fn save_per_host(&mut self, monitors_conf: &std::path::Path, file_content: &str) {
+        let result = profile::hostname()
+            .and_then(|host| hyprconf::host_monitors_conf_path(&host))
+            .and_then(|host_path| {
+                fs::write(&host_path, file_content)?;
+                fs::write(monitors_conf, hyprconf::host_source_stub(&host_path))?;
+                Ok(host_path)
+            });
+        if result.is_ok() {
+            self.saved_conf = file_content.to_string();
+        }
 
-            let command = format!(
-                "hyprctl keyword monitor \"{},{}@{:.2},auto,{:.2},mirror,{}\"",
-                source_monitor_name,
-                source_resolution,
-                source_refresh_rate,
-                source_scale_float,
-                other_monitor_name
-            );
-            commands::execute_hyprctl(&command);
+        match result {
+            Ok(host_path) => self.info(tr_args(
+                "messages.saved_per_host",
+                &[
+                    ("path", &host_path.display()),
+                    ("source", &monitors_conf.display()),
+                ],
+            )),
+            Err(e) => self.error(tr_args("messages.write_failed", &[("error", &e)])),
         }
     }
 
-    fn toggle_dpms(&mut self) {
+    fn disable_monitor(&mut self) {
         let Some(idx) = self.monitor_list_state.selected() else {
             return;
         };
 
-        let is_on = self.configs[idx].dpms_on;
-        let monitor_name = &self.monitors[idx].name;
-
-        let command = if is_on {
-            format!("hyprctl dispatch dpms off {}", monitor_name)
-        } else {
-            format!("hyprctl dispatch dpms on {}", monitor_name)
-        };
-
-        if commands::execute_hyprctl(&command) {
-            self.configs[idx].dpms_on = !is_on;
+        let workspaces = self.workspaces_on(idx);
+        if workspaces.is_empty() {
+            self.disable_monitor_now(idx);
+            return;
         }
+
+        self.popup = Some(Popup::DisableConfirm(DisableConfirm {
+            monitor: idx,
+            workspaces,
+            target: self.get_other_monitor_info(idx).map(|(i, _)| i),
+            state: Self::init_list_state(DISABLE_CHOICES.len()),
+        }));
     }
 
-    fn apply_changes(&self) {
+    /// Disables the selected monitor, or turns it back on with its current
+    /// settings if it is already off.
+    fn toggle_disabled(&mut self) {
         let Some(idx) = self.monitor_list_state.selected() else {
             return;
         };
+        if self.monitors[idx].active {
+            self.disable_monitor();
+        } else {
+            self.apply_changes();
+        }
+    }
 
-        let monitor = &self.monitors[idx];
-        let config = &self.configs[idx];
+    fn disable_monitor_now(&mut self, idx: usize) {
+        let command = HyprCommand::monitor(format!("{},disable", self.monitors[idx].name));
+        if self.run_command(&command) {
+            self.configs[idx].save_disabled = true;
+        }
+    }
 
-        let command = format!(
-            "hyprctl keyword monitor \"{},{}@{:.2},auto,{:.2}\"",
-            monitor.name,
-            config.resolution,
-            config.refresh_rate,
-            config.scale_as_float()
-        );
+    /// Workspaces with windows on monitor `idx`, from `hyprctl clients`.
+    fn workspaces_on(&self, idx: usize) -> Vec<WorkspaceWindows> {
+        let monitor_id = self.monitors[idx].id;
+        let clients = commands::fetch_clients().unwrap_or_default();
 
-        commands::execute_hyprctl(&command);
+        let mut workspaces: Vec<WorkspaceWindows> = Vec::new();
+        for client in clients
+            .iter()
+            .filter(|c| c["monitor"].as_i64() == Some(monitor_id))
+        {
+            let id = client["workspace"]["id"].as_i64().unwrap_or(0);
+            match workspaces.iter_mut().find(|w| w.id == id) {
+                Some(workspace) => workspace.windows += 1,
+                None => workspaces.push(WorkspaceWindows {
+                    id,
+                    name: client["workspace"]["name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    windows: 1,
+                }),
+            }
+        }
+        workspaces.sort_by_key(|w| w.id);
+        workspaces
     }
 
-    fn save_config_to_file(&mut self) {
-        let path_str = "~/.config/hypr/monitors.conf";
-        let expanded_path = match shellexpand::full(path_str) {
-            Ok(p) => PathBuf::from(p.into_owned()),
-            Err(e) => {
-                self.info_message = Some(format!("Error expanding path: {}", e));
-                return;
-            }
+    fn open_details(&mut self) {
+        let Some(monitor) = self.selected_monitor().map(|idx| &self.monitors[idx]) else {
+            return;
         };
+        let text = serde_json::to_string_pretty(&monitor.raw).unwrap_or_default();
+        self.popup = Some(Popup::Details(Details {
+            title: format!("{} JSON", monitor.name),
+            lines: text.lines().map(String::from).collect(),
+            scroll: 0,
+            supersede: false,
+        }));
+    }
 
-        if let Some(parent) = expanded_path.parent() {
-            if let Err(e) = fs::create_dir_all(parent) {
-                self.info_message = Some(format!("Error creating dir: {}", e));
-                return;
+    fn handle_details_key(&mut self, code: KeyCode) {
+        let Some(Popup::Details(details)) = self.popup.as_mut() else {
+            return;
+        };
+        let last = details.lines.len().saturating_sub(1);
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('D') => self.popup = None,
+            KeyCode::Enter if details.supersede => {
+                self.popup = None;
+                self.supersede_conflicting_lines();
             }
+            KeyCode::Char('j') | KeyCode::Down => details.scroll = (details.scroll + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => details.scroll = details.scroll.saturating_sub(1),
+            KeyCode::Char('g') | KeyCode::Home => details.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => details.scroll = last,
+            KeyCode::PageDown => details.scroll = (details.scroll + 10).min(last),
+            KeyCode::PageUp => details.scroll = details.scroll.saturating_sub(10),
+            _ => {}
         }
+    }
 
-        let mut file_content = String::from("# Monitor settings generated by hypr-tui\n# Add 'source = ~/.config/hypr/monitors.conf' to your hyprland.conf\n\n");
+    fn handle_disable_confirm_key(&mut self, code: KeyCode) {
+        let Some(Popup::DisableConfirm(confirm)) = self.popup.as_mut() else {
+            return;
+        };
 
-        for (i, monitor) in self.monitors.iter().enumerate() {
-            if monitor.active {
-                let config = &self.configs[i];
-                let line = format!(
-                    "monitor={},{}@{:.2},auto,{:.2}\n",
-                    monitor.name,
-                    config.resolution,
-                    config.refresh_rate,
-                    config.scale_as_float()
-                );
-                file_content.push_str(&line);
+        if let Some(motion) = Motion::from_key(code) {
+            let selection = motion.apply(confirm.state.selected(), DISABLE_CHOICES.len());
+            confirm.state.select(selection);
+            return;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.popup = None,
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Char('l') | KeyCode::Right => {
+                let forward = matches!(code, KeyCode::Char('l') | KeyCode::Right);
+                let candidates: Vec<usize> = (0..self.monitors.len())
+                    .filter(|&i| i != confirm.monitor && self.monitors[i].active)
+                    .collect();
+                let current = confirm
+                    .target
+                    .and_then(|t| candidates.iter().position(|&i| i == t));
+                confirm.target = Self::cycle_selection(current, candidates.len(), forward)
+                    .map(|i| candidates[i]);
             }
+            KeyCode::Enter => {
+                let Some(Popup::DisableConfirm(confirm)) = self.popup.take() else {
+                    return;
+                };
+                match confirm.state.selected() {
+                    Some(0) => self.move_workspaces_and_disable(confirm),
+                    Some(1) => self.disable_monitor_now(confirm.monitor),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn move_workspaces_and_disable(&mut self, confirm: DisableConfirm) {
+        let Some(target) = confirm.target else {
+            self.warn(tr("messages.no_move_target"));
+            return;
+        };
+        let target_name = self.monitors[target].name.clone();
+
+        let commands: Vec<HyprCommand> = confirm
+            .workspaces
+            .iter()
+            .map(|workspace| HyprCommand::move_workspace(workspace.id, &target_name))
+            .chain(std::iter::once(HyprCommand::monitor(format!(
+                "{},disable",
+                self.monitors[confirm.monitor].name
+            ))))
+            .collect();
+
+        let what = format!(
+            "moving {} workspace(s) to {} and disabling {}",
+            confirm.workspaces.len(),
+            target_name,
+            self.monitors[confirm.monitor].name
+        );
+        let result = self.run_batch(&commands, &what);
+        if result.is_ok() {
+            self.configs[confirm.monitor].save_disabled = true;
+        }
+        self.report(result);
+    }
+
+    fn create_virtual_output(&mut self) {
+        let before: Vec<String> = self.monitors.iter().map(|m| m.name.clone()).collect();
+
+        if !self.run_command(&HyprCommand::new(["output", "create", "headless"])) {
+            self.error(tr("messages.headless_failed"));
+            return;
+        }
+        if let Err(e) = self.refresh_monitors() {
+            self.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+            return;
         }
 
-        match fs::write(&expanded_path, file_content) {
-            Ok(_) => {
-                self.info_message = Some(format!("Success! Saved to {}", expanded_path.display()))
+        // Select the new output so its mode can be set right away.
+        match self.monitors.iter().position(|m| !before.contains(&m.name)) {
+            Some(idx) => {
+                self.monitor_list_state.select(Some(idx));
+                self.option_list_state.select(Some(RESOLUTION_IDX));
+                self.info(tr_args(
+                    "messages.created",
+                    &[("monitor", &self.monitors[idx].name)],
+                ));
             }
-            Err(e) => self.info_message = Some(format!("Error writing file: {}", e)),
+            None => self.info(tr("messages.created_headless")),
         }
     }
 
-    fn disable_monitor(&self) {
+    fn remove_virtual_output(&mut self) {
         let Some(idx) = self.monitor_list_state.selected() else {
             return;
         };
+        let name = self.monitors[idx].name.clone();
 
-        let command = format!(
-            "hyprctl keyword monitor \"{},disable\"",
-            self.monitors[idx].name
-        );
+        if !self.monitors[idx].is_virtual() {
+            self.warn(tr_args("messages.not_virtual", &[("monitor", &name)]));
+            return;
+        }
+
+        if !self.run_command(&HyprCommand::new(["output", "remove", &name])) {
+            self.error(tr_args("messages.remove_failed", &[("monitor", &name)]));
+            return;
+        }
+        match self.refresh_monitors() {
+            Ok(()) => self.info(tr_args("messages.removed", &[("monitor", &name)])),
+            Err(e) => self.error(tr_args("messages.refresh_failed", &[("error", &e)])),
+        }
+    }
+
+    /// Moves the compositor's focus (and cursor) to the selected monitor.
+    fn focus_selected_monitor(&mut self) {
+        let Some(idx) = self.monitor_list_state.selected() else {
+            return;
+        };
+        let name = self.monitors[idx].name.clone();
+
+        if !self.run_command(&HyprCommand::dispatch("focusmonitor", &name)) {
+            self.error(tr_args("messages.focus_failed", &[("monitor", &name)]));
+            return;
+        }
+        match self.refresh_monitors() {
+            Ok(()) => self.info(tr_args("messages.focused", &[("monitor", &name)])),
+            Err(e) => self.error(tr_args("messages.refresh_failed", &[("error", &e)])),
+        }
+    }
+
+    fn identify_monitors(&mut self) {
+        // Notifications are drawn on the focused monitor, so focus each one in
+        // turn and hand focus back to the original monitor afterwards.
+        let previously_focused = commands::fetch_monitors().ok().and_then(|data| {
+            data.iter()
+                .find(|m| m["focused"].as_bool().unwrap_or(false))
+                .and_then(|m| m["name"].as_str().map(String::from))
+        });
+
+        let mut identified = 0;
+        for (i, monitor) in self.monitors.iter().enumerate() {
+            if !monitor.active {
+                continue;
+            }
+            commands::execute_hyprctl(&HyprCommand::dispatch("focusmonitor", &monitor.name));
+            let notified = commands::execute_hyprctl(&HyprCommand::new([
+                "notify".to_string(),
+                "1".to_string(),
+                IDENTIFY_DURATION_MS.to_string(),
+                "0".to_string(),
+                format!("{}: {}", i + 1, monitor.name),
+            ]));
+            if notified {
+                identified += 1;
+            }
+        }
+
+        if let Some(name) = previously_focused {
+            commands::execute_hyprctl(&HyprCommand::dispatch("focusmonitor", name));
+        }
 
-        commands::execute_hyprctl(&command);
+        self.info(tr_args("messages.identifying", &[("count", &identified)]));
     }
 
     fn toggle_pane(&mut self) {
@@ -450,39 +3895,176 @@ impl App {
         };
     }
 
-    pub fn handle_key(&mut self, code: KeyCode) -> bool {
-        if self.info_message.is_some() {
-            self.info_message = None;
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let code = key.code;
+        let fine = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        match self.popup {
+            Some(Popup::ModePicker(_)) => {
+                self.handle_mode_picker_key(code);
+                return false;
+            }
+            Some(Popup::Palette(_)) => {
+                self.handle_palette_key(code);
+                return false;
+            }
+            Some(Popup::TextInput(_)) => {
+                self.handle_text_input_key(key);
+                return false;
+            }
+            Some(Popup::Presets(_)) => {
+                self.handle_presets_key(code);
+                return false;
+            }
+            Some(Popup::DisableConfirm(_)) => {
+                self.handle_disable_confirm_key(code);
+                return false;
+            }
+            Some(Popup::Details(_)) => {
+                self.handle_details_key(code);
+                return false;
+            }
+            Some(Popup::Cleanup(_)) => {
+                self.handle_cleanup_key(code);
+                return false;
+            }
+            Some(Popup::Template(_)) => {
+                self.handle_template_key(code);
+                return false;
+            }
+            Some(Popup::Setup(_)) => {
+                self.handle_setup_key(code);
+                return false;
+            }
+            Some(Popup::Reconcile(_)) => {
+                self.handle_reconcile_key(code);
+                return false;
+            }
+            Some(Popup::ShrinkConfirm(_)) => {
+                self.handle_shrink_confirm_key(code);
+                return false;
+            }
+            Some(Popup::Review(_)) => {
+                self.handle_review_key(code);
+                return false;
+            }
+            Some(Popup::ReloadConfirm(_)) => {
+                self.handle_reload_confirm_key(code);
+                return false;
+            }
+            None => {}
+        }
+
+        // An applied live change waits for a verdict before anything else.
+        if self
+            .live_change
+            .as_ref()
+            .is_some_and(|change| change.revert_at.is_some())
+        {
+            match code {
+                KeyCode::Enter => {
+                    self.keep_live_change();
+                    return false;
+                }
+                KeyCode::Esc => {
+                    self.revert_live_change();
+                    return false;
+                }
+                _ => {}
+            }
+        }
+
+        if code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.open_palette();
+            return false;
         }
 
         match code {
             KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::F(n @ 1..=4) => {
+                self.switch_tab(Tab::ALL[n as usize - 1]);
+                return false;
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                self.select_monitor(c as usize - '1' as usize);
+                return false;
+            }
+            KeyCode::Char(c) if c == self.config.keys.profile_previous => {
+                self.cycle_profile_and_refresh(false);
+                return false;
+            }
+            KeyCode::Char(c) if c == self.config.keys.profile_next => {
+                self.cycle_profile_and_refresh(true);
+                return false;
+            }
+            KeyCode::Char('<') => {
+                self.cycle_tab(false);
+                return false;
+            }
+            KeyCode::Char('>') => {
+                self.cycle_tab(true);
+                return false;
+            }
+            KeyCode::Char('c') => {
+                self.switch_tab(Tab::Log);
+                return false;
+            }
+            _ => {}
+        }
+
+        if self.tab != Tab::Monitors {
+            self.handle_tab_key(code);
+            return false;
+        }
+
+        if let Some(motion) = Motion::from_key(code) {
+            match self.focused_pane {
+                FocusedPane::Monitors => self.navigate_monitors(motion),
+                FocusedPane::Options => self.navigate_options(motion),
+            }
+            return false;
+        }
+
+        match code {
             KeyCode::Tab => self.toggle_pane(),
-            KeyCode::Char('j') | KeyCode::Down => match self.focused_pane {
-                FocusedPane::Monitors => self.navigate_monitors(true),
-                FocusedPane::Options => self.navigate_options(true),
-            },
-            KeyCode::Char('k') | KeyCode::Up => match self.focused_pane {
-                FocusedPane::Monitors => self.navigate_monitors(false),
-                FocusedPane::Options => self.navigate_options(false),
-            },
+            KeyCode::Char('i') => self.identify_monitors(),
+            KeyCode::Char('f') => self.toggle_mode_filter(),
+            KeyCode::Char('o') => self.toggle_live_mode(),
+            KeyCode::Char('p') => self.open_presets(),
+            KeyCode::Char('y') => self.open_templates(None),
+            KeyCode::Char('z') => self.toggle_selected_group(),
+            KeyCode::Char('F') => self.focus_selected_monitor(),
+            KeyCode::Char('n') => self.create_virtual_output(),
+            KeyCode::Char('x') => self.remove_virtual_output(),
+            KeyCode::Char('D') => self.open_details(),
+            KeyCode::Char('S') => self.toggle_monitor_order(),
+            KeyCode::Char('T') => self.cycle_theme(),
+            KeyCode::Char('a') => self.apply_changes(),
+            KeyCode::Char('A') => self.queue_changes(),
+            KeyCode::Char('R') => self.open_review(),
+            KeyCode::Char('s') => self.save_config_to_file(),
+            KeyCode::Char('m') => self.set_as_main(),
+            KeyCode::Char('d') => self.toggle_disabled(),
+            KeyCode::Char('b') => self.toggle_dpms(),
             KeyCode::Char('l') | KeyCode::Right if self.focused_pane == FocusedPane::Options => {
-                self.modify_selected_option(true)
+                self.modify_selected_option(true, fine)
+            }
+            KeyCode::Char('L') if self.focused_pane == FocusedPane::Options => {
+                self.modify_selected_option(true, true)
             }
             KeyCode::Char('h') | KeyCode::Left if self.focused_pane == FocusedPane::Options => {
-                self.modify_selected_option(false)
+                self.modify_selected_option(false, fine)
+            }
+            KeyCode::Char('H') if self.focused_pane == FocusedPane::Options => {
+                self.modify_selected_option(false, true)
             }
             KeyCode::Enter if self.focused_pane == FocusedPane::Options => {
-                match self.option_list_state.selected() {
-                    Some(APPLY_OPTION_IDX) => self.apply_changes(),
-                    Some(SET_MAIN_IDX) => self.set_as_main(),
-                    Some(EXTEND_LEFT_IDX) => self.extend_relative("left"),
-                    Some(EXTEND_RIGHT_IDX) => self.extend_relative("right"),
-                    Some(MIRROR_IDX) => self.mirror_monitor(),
-                    Some(BLACK_SCREEN_IDX) => self.toggle_dpms(),
-                    Some(SAVE_OPTION_IDX) => self.save_config_to_file(),
-                    Some(DISABLE_OPTION_IDX) => self.disable_monitor(),
-                    _ => {}
+                let Some(row) = self.option_list_state.selected() else {
+                    return false;
+                };
+                match Self::option_action(row) {
+                    Some(action) => self.run_action(action),
+                    None => self.apply_option_row(row),
                 }
             }
             _ => {}
@@ -494,7 +4076,357 @@ impl App {
         self.monitor_list_state.selected()
     }
 
+    /// Monitor `idx` as shown in lists: `Left Dell (DP-1)` if it has an
+    /// alias, the connector name otherwise.
+    pub fn display_name(&self, idx: usize) -> String {
+        let monitor = &self.monitors[idx];
+        match monitor.alias(&self.config.aliases) {
+            Some(alias) => format!("{} ({})", alias, monitor.name),
+            None => monitor.name.clone(),
+        }
+    }
+
+    /// The monitor called `name`, by connector name or alias.
+    pub fn find_monitor(&self, name: &str) -> Option<usize> {
+        self.monitors
+            .iter()
+            .position(|m| m.name == name)
+            .or_else(|| {
+                self.monitors.iter().position(|m| {
+                    m.alias(&self.config.aliases)
+                        .is_some_and(|alias| alias.eq_ignore_ascii_case(name))
+                })
+            })
+    }
+
     pub fn is_focused(&self, pane: FocusedPane) -> bool {
         self.focused_pane == pane
     }
 }
+
+/// Points `idx` at where its monitor is now, or returns the name of the
+/// monitor if it went away.
+fn remap_index(
+    idx: &mut usize,
+    remap: impl Fn(usize) -> Option<usize>,
+    gone: impl Fn(usize) -> String,
+) -> Option<String> {
+    match remap(*idx) {
+        Some(new) => {
+            *idx = new;
+            None
+        }
+        None => Some(gone(*idx)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StateFormat;
+
+    const MONITORS_V0_28: &str = include_str!("../tests/fixtures/monitors-v0.28.json");
+    const MONITORS_V0_41: &str = include_str!("../tests/fixtures/monitors-v0.41.json");
+    const MONITORS_V0_47: &str = include_str!("../tests/fixtures/monitors-v0.47.json");
+
+    fn parse_fixture(json: &str) -> (Vec<Monitor>, Vec<MonitorConfig>) {
+        let data: Vec<serde_json::Value> = serde_json::from_str(json).expect("fixture is JSON");
+        App::parse_monitors(data).expect("fixture parses")
+    }
+
+    /// What "Save to File" writes for the fixture, read back as a fresh
+    /// parse of the same monitors would be.
+    fn round_trip(monitors: &[Monitor], configs: &[MonitorConfig]) -> Vec<MonitorConfig> {
+        let saved: String = monitors
+            .iter()
+            .zip(configs)
+            .flat_map(|(monitor, config)| hyprconf::monitor_conf_lines(monitor, config))
+            .map(|value| format!("monitor={}\n", value))
+            .collect();
+        let mut restored: Vec<MonitorConfig> = configs
+            .iter()
+            .cloned()
+            .map(|config| MonitorConfig {
+                reserved: [0; 4],
+                position: None,
+                save_disabled: false,
+                ..config
+            })
+            .collect();
+        App::restore_saved_settings(&mut monitors.to_vec(), &mut restored, &saved);
+        restored
+    }
+
+    #[test]
+    fn parses_fractional_scale_and_current_mode() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_28);
+
+        assert_eq!(monitors.len(), 2);
+        assert_eq!(monitors[0].name, "eDP-1");
+        assert!(monitors[0].focused);
+        assert!(monitors[0].is_internal());
+        assert_eq!(configs[0].scale, 125);
+        assert_eq!(configs[0].resolution, "2256x1504");
+        assert_eq!(configs[0].refresh_rate, 59.99);
+
+        assert_eq!((monitors[1].x, monitors[1].y), (1805, 0));
+        assert_eq!(
+            monitors[1].modes["2560x1440"]
+                .iter()
+                .map(|rate| rate.value)
+                .collect::<Vec<_>>(),
+            vec![165.08, 143.91, 59.95],
+            "rates are sorted highest first"
+        );
+        assert_eq!(configs[1].refresh_rate, 143.91);
+        assert_eq!(configs[1].refresh_rate_index, 1);
+        assert!(configs[1].vrr);
+        assert_eq!(configs[1].bitdepth, None);
+        assert_eq!(monitors[1].native_resolution.as_deref(), Some("2560x1440"));
+        assert_eq!(
+            monitors[1].applied,
+            AppliedMode::of(&configs[1]),
+            "nothing is pending right after parsing"
+        );
+    }
+
+    #[test]
+    fn parses_disabled_monitor_transform_and_bitdepth() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_41);
+
+        assert!(!monitors[0].active);
+        assert_eq!(configs[0].resolution, "1920x1200");
+        assert_eq!(configs[0].refresh_rate, 60.03);
+
+        assert!(monitors[1].active);
+        assert_eq!(monitors[1].mirror_of, None, "\"none\" means not mirroring");
+        assert_eq!(configs[1].scale, 167);
+        assert_eq!(configs[1].resolution, "3840x2160");
+        assert_eq!(configs[1].refresh_rate, 60.0);
+        assert_eq!(configs[1].transform, 1);
+        assert_eq!(configs[1].bitdepth, Some(10));
+    }
+
+    #[test]
+    fn rates_that_round_alike_are_kept_apart() {
+        let modes = App::parse_modes(&serde_json::json!({
+            "availableModes": [
+                "2560x1440@144.00Hz",
+                "2560x1440@60.000Hz",
+                "2560x1440@143.998Hz",
+                "2560x1440@60.000Hz",
+            ]
+        }));
+        let specs: Vec<&str> = modes["2560x1440"]
+            .iter()
+            .map(|rate| rate.spec.as_str())
+            .collect();
+        assert_eq!(specs, ["144.00", "143.998", "60.000"]);
+    }
+
+    #[test]
+    fn parses_headless_output_without_modes() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+
+        let headless = &monitors[1];
+        assert!(headless.is_virtual());
+        for res in VIRTUAL_MODES {
+            assert_eq!(headless.modes[res], vec![Rate::from(60.0)]);
+        }
+        assert_eq!(configs[1].resolution, "1920x1080");
+        assert_eq!(configs[1].color_mode, ColorMode::Srgb);
+    }
+
+    #[test]
+    fn parses_color_management_and_mirroring() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+
+        assert_eq!(configs[0].color_mode, ColorMode::Hdr);
+        assert_eq!(configs[0].scale, 150);
+        assert_eq!(configs[0].bitdepth, Some(10));
+        assert_eq!(monitors[0].physical_width, 800);
+        assert_eq!(monitors[2].mirror_of.as_deref(), Some("DP-2"));
+    }
+
+    #[test]
+    fn skips_entries_without_a_name() {
+        let data = serde_json::json!([{ "name": "" }, { "id": 3 }]);
+        let (monitors, configs) = App::parse_monitors(serde_json::from_value(data).unwrap())
+            .expect("nameless entries are skipped, not errors");
+        assert!(monitors.is_empty());
+        assert!(configs.is_empty());
+    }
+
+    #[test]
+    fn saved_layout_round_trips() {
+        for fixture in [MONITORS_V0_28, MONITORS_V0_41, MONITORS_V0_47] {
+            let (monitors, mut configs) = parse_fixture(fixture);
+            configs[0].reserved = [0, 30, 0, 0];
+            configs[1].position = Some((-1920, 0));
+
+            let restored = round_trip(&monitors, &configs);
+            for ((monitor, config), restored) in monitors.iter().zip(&configs).zip(&restored) {
+                if !monitor.active {
+                    assert_eq!(restored.position, None, "{} is not saved", monitor.name);
+                    continue;
+                }
+                let position = config.position.unwrap_or((monitor.x, monitor.y));
+                assert_eq!(restored.position, Some(position), "{}", monitor.name);
+                assert_eq!(restored.reserved, config.reserved, "{}", monitor.name);
+                assert!(!restored.save_disabled, "{}", monitor.name);
+            }
+        }
+    }
+
+    #[test]
+    fn saved_disable_round_trips() {
+        let (monitors, mut configs) = parse_fixture(MONITORS_V0_41);
+        configs[0].save_disabled = true;
+
+        let restored = round_trip(&monitors, &configs);
+        assert!(restored[0].save_disabled);
+        assert!(!restored[1].save_disabled);
+    }
+
+    #[test]
+    fn scale_only_apply_keeps_the_running_modeline() {
+        let (mut monitors, mut configs) = parse_fixture(MONITORS_V0_47);
+        let idx = monitors.iter().position(|m| m.name == "DP-3").unwrap();
+        let modeline = Modeline::parse("3840x2160@60").expect("mode parses");
+        let saved = format!("monitor=DP-3,modeline {},0x0,1.00\n", modeline);
+        App::restore_saved_settings(&mut monitors, &mut configs, &saved);
+        configs[idx].scale = 200;
+
+        let mut live = App::live_config(&monitors[idx], &configs[idx]);
+        live.scale = configs[idx].scale;
+        let rule = monitors[idx].rule(&live);
+        assert!(rule.starts_with(&format!("DP-3,modeline {},", modeline)));
+        assert!(rule.contains(",2.00,"));
+    }
+
+    #[test]
+    fn transient_monitor_is_left_out() {
+        let (monitors, mut configs) = parse_fixture(MONITORS_V0_41);
+        configs[1].persist = false;
+        configs[1].save_disabled = true;
+
+        assert!(hyprconf::monitor_conf_lines(&monitors[1], &configs[1]).is_empty());
+        let restored = round_trip(&monitors, &configs);
+        assert!(!restored[1].save_disabled);
+        assert_eq!(restored[1].position, None);
+    }
+
+    #[test]
+    fn exported_svg_covers_every_monitor() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+        let svg = svg::layout(&monitors, &configs);
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        for monitor in &monitors {
+            assert!(svg.contains(&monitor.name), "{}", monitor.name);
+        }
+    }
+
+    #[test]
+    fn dumped_state_restores_the_same_layout() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+        let state = State::capture(&monitors, &configs);
+        for format in [StateFormat::Json, StateFormat::Toml] {
+            let dumped = state.dump(format).unwrap();
+            assert_eq!(State::parse(&dumped, format).unwrap(), state);
+        }
+
+        let rules: Vec<String> = state
+            .commands(&monitors, &configs)
+            .iter()
+            .map(|command| command.args()[2].clone())
+            .filter(|rule| !rule.contains("addreserved"))
+            .collect();
+        let saved: Vec<String> = monitors
+            .iter()
+            .zip(&configs)
+            .filter_map(|(monitor, config)| {
+                let lines = hyprconf::monitor_conf_lines(monitor, config);
+                lines
+                    .into_iter()
+                    .next()
+                    .or_else(|| Some(format!("{},disable", monitor.name)))
+            })
+            .collect();
+        let mut sorted = saved.clone();
+        sorted.sort_by_key(|rule| (rule.ends_with(",disable"), rule.contains(",mirror,")));
+        assert_eq!(rules, sorted);
+    }
+
+    #[test]
+    fn monitors_sort_left_to_right_with_disabled_last() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+        let (sorted, sorted_configs) = App::sort_monitors(
+            monitors.clone(),
+            configs.clone(),
+            MonitorOrder::Position,
+            &BTreeMap::new(),
+        );
+        let active: Vec<&Monitor> = sorted.iter().filter(|m| m.active).collect();
+        assert!(active
+            .windows(2)
+            .all(|w| (w[0].x, w[0].y) <= (w[1].x, w[1].y)));
+        assert!(sorted.iter().skip(active.len()).all(|m| !m.active));
+        for (monitor, config) in sorted.iter().zip(&sorted_configs) {
+            let original = monitors
+                .iter()
+                .position(|m| m.name == monitor.name)
+                .unwrap();
+            assert_eq!(config.resolution, configs[original].resolution);
+        }
+
+        let (by_name, _) =
+            App::sort_monitors(monitors, configs, MonitorOrder::Name, &BTreeMap::new());
+        assert!(by_name.windows(2).all(|w| w[0].name <= w[1].name));
+    }
+
+    #[test]
+    fn grouped_monitors_are_listed_together() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+        let groups: BTreeMap<String, Vec<String>> = [(
+            String::from("side"),
+            vec![String::from("HEADLESS-2"), String::from("DP-3")],
+        )]
+        .into_iter()
+        .collect();
+        let (sorted, _) = App::sort_monitors(monitors, configs, MonitorOrder::Name, &groups);
+        let names: Vec<&str> = sorted.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["DP-3", "HEADLESS-2", "DP-2"]);
+        assert_eq!(group_of(&groups, "HEADLESS-2"), Some((0, "side")));
+        assert_eq!(group_of(&groups, "DP-2"), None);
+    }
+
+    #[test]
+    fn ignored_outputs_are_dropped() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+        let ignore = [String::from("HEADLESS-2"), String::from("desc:Dell Inc.")];
+        assert_eq!(
+            App::ignored_names(&monitors, &ignore),
+            ["HEADLESS-2", "DP-3"]
+        );
+        let (monitors, configs) = App::without_ignored(monitors, configs, &ignore);
+        let names: Vec<&str> = monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["DP-2"]);
+        assert_eq!(configs.len(), 1);
+    }
+
+    #[test]
+    fn saved_rule_reads_back_unchanged() {
+        let (monitors, configs) = parse_fixture(MONITORS_V0_47);
+        for (monitor, config) in monitors.iter().zip(&configs) {
+            let lines = hyprconf::monitor_conf_lines(monitor, config);
+            let saved: String = lines.iter().map(|v| format!("monitor={}\n", v)).collect();
+
+            assert_eq!(
+                hyprconf::find_monitor_rule(&saved, &monitor.name),
+                Some(lines[0].as_str())
+            );
+            // The status bar counts a monitor as unsaved when these differ.
+            assert_eq!(hyprconf::monitor_lines(&saved, &monitor.name), lines);
+        }
+    }
+}
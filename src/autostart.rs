@@ -0,0 +1,76 @@
+use std::{fs, io, path::PathBuf, process::Command};
+
+use crate::profile::Profile;
+use crate::xdg;
+
+const UNIT_NAME: &str = "hyprmonitor-profile.service";
+/// Under the XDG config directory.
+const SYSTEMD_USER_DIR: &str = "systemd/user";
+const EXEC_ONCE_PATH: &str = "hypr/hyprmonitor-autostart.conf";
+
+/// A Hyprland `exec-once` line that applies `profile` at login.
+pub fn exec_once_snippet(profile: &Profile) -> String {
+    format!(
+        "exec-once = {} apply-profile {}\n",
+        env!("CARGO_BIN_NAME"),
+        profile.name
+    )
+}
+
+/// A oneshot systemd user unit applying `profile` once the graphical session
+/// is up. Hyprland must export its environment to systemd (e.g. via uwsm) for
+/// hyprctl to reach the compositor from the unit.
+pub fn systemd_unit(profile: &Profile) -> io::Result<String> {
+    let exe = std::env::current_exe()?;
+    Ok(format!(
+        "[Unit]\n\
+         Description=Apply the {name} monitor profile\n\
+         PartOf=graphical-session.target\n\
+         After=graphical-session.target\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         ExecStart={exe} apply-profile {name}\n\
+         \n\
+         [Install]\n\
+         WantedBy=graphical-session.target\n",
+        name = profile.name,
+        exe = exe.display()
+    ))
+}
+
+/// Writes the exec-once snippet to its own file, which hyprland.conf has to
+/// `source`.
+pub fn install_exec_once(profile: &Profile) -> io::Result<PathBuf> {
+    let path = xdg::config_path(EXEC_ONCE_PATH)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, exec_once_snippet(profile))?;
+    Ok(path)
+}
+
+/// Writes and enables the systemd user unit.
+pub fn install_systemd(profile: &Profile) -> io::Result<PathBuf> {
+    let dir = xdg::config_path(SYSTEMD_USER_DIR)?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(UNIT_NAME);
+    fs::write(&path, systemd_unit(profile)?)?;
+
+    let enabled = Command::new("systemctl")
+        .args(["--user", "daemon-reload"])
+        .status()
+        .and_then(|_| {
+            Command::new("systemctl")
+                .args(["--user", "enable", UNIT_NAME])
+                .status()
+        })?;
+    if !enabled.success() {
+        return Err(io::Error::other(format!(
+            "Wrote {} but `systemctl --user enable {}` failed",
+            path.display(),
+            UNIT_NAME
+        )));
+    }
+    Ok(path)
+}
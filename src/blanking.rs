@@ -0,0 +1,75 @@
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+/// A nightly window in which the daemon turns every monitor off, unless
+/// someone is using the machine. Profiles may carry their own copy to
+/// override the app-wide settings while they are active.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BlankingSettings {
+    pub enabled: bool,
+    /// Start of the window, `HH:MM`.
+    pub from: String,
+    /// End of the window, `HH:MM`; before `from` for windows spanning
+    /// midnight.
+    pub to: String,
+    /// Minutes without input (pointer movement) before blanking; 0 blanks
+    /// as soon as the window starts.
+    pub unless_input_within: u32,
+}
+
+impl Default for BlankingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            from: String::from("01:00"),
+            to: String::from("07:00"),
+            unless_input_within: 10,
+        }
+    }
+}
+
+impl BlankingSettings {
+    /// Whether `now` falls in the window. Times that don't parse never do.
+    pub fn in_window(&self, now: NaiveTime) -> bool {
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        let (Some(from), Some(to)) = (parse(&self.from), parse(&self.to)) else {
+            return false;
+        };
+        if from <= to {
+            from <= now && now < to
+        } else {
+            now >= from || now < to
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_may_span_midnight() {
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let night = BlankingSettings::default();
+        assert!(night.in_window(at(1, 0)));
+        assert!(night.in_window(at(6, 59)));
+        assert!(!night.in_window(at(7, 0)));
+        assert!(!night.in_window(at(0, 59)));
+
+        let late = BlankingSettings {
+            from: String::from("23:30"),
+            to: String::from("6:00"),
+            ..BlankingSettings::default()
+        };
+        assert!(late.in_window(at(23, 45)));
+        assert!(late.in_window(at(2, 0)));
+        assert!(!late.in_window(at(12, 0)));
+
+        let broken = BlankingSettings {
+            from: String::from("late"),
+            ..BlankingSettings::default()
+        };
+        assert!(!broken.in_window(at(2, 0)));
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::config::Config;
+
+/// What a bundle holds, relative to `~/.config/hyprmonitor`: the app
+/// config (aliases included) and every saved profile.
+const ENTRIES: [&str; 2] = ["config.toml", "profiles"];
+
+/// `~/.config/hyprmonitor`, where the bundled files live.
+fn base_dir() -> io::Result<PathBuf> {
+    let config = Config::path()?;
+    config
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No config directory"))
+}
+
+/// Runs `tar` with zstd compression, returning its standard output.
+fn tar(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("tar")
+        .arg("--zstd")
+        .args(args)
+        .output()
+        .map_err(|e| io::Error::new(e.kind(), format!("Failed to run tar: {}", e)))?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether a bundle member is something [`export`] writes, so importing a
+/// tampered or unrelated archive can't place files anywhere else.
+fn is_bundle_entry(entry: &str) -> bool {
+    let entry = entry.strip_prefix("./").unwrap_or(entry);
+    match entry.split_once('/') {
+        None => ENTRIES.contains(&entry),
+        Some(("profiles", "")) => true,
+        Some(("profiles", name)) => {
+            !name.contains('/') && !name.starts_with('.') && name.ends_with(".toml")
+        }
+        Some(_) => false,
+    }
+}
+
+/// Writes the config and profiles to `file`, a zstd-compressed tarball.
+/// Returns the files it holds.
+pub fn export(file: &Path) -> io::Result<Vec<String>> {
+    let dir = base_dir()?;
+    let present: Vec<&str> = ENTRIES
+        .into_iter()
+        .filter(|entry| dir.join(entry).exists())
+        .collect();
+    if present.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Nothing to export in {}", dir.display()),
+        ));
+    }
+    let file = file.to_string_lossy();
+    let dir = dir.to_string_lossy();
+    let mut args = vec!["-cf", file.as_ref(), "-C", dir.as_ref()];
+    args.extend(present);
+    tar(&args)?;
+    list(file.as_ref())
+}
+
+/// The files in a bundle, refusing archives with anything else in them.
+fn list(file: &str) -> io::Result<Vec<String>> {
+    let entries: Vec<String> = tar(&["-tf", file])?.lines().map(str::to_string).collect();
+    if let Some(entry) = entries.iter().find(|entry| !is_bundle_entry(entry)) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not a hypr-tui bundle: unexpected {:?}", file, entry),
+        ));
+    }
+    Ok(entries
+        .into_iter()
+        .filter(|entry| !entry.ends_with('/'))
+        .collect())
+}
+
+/// Unpacks a bundle into `~/.config/hyprmonitor`. Files that already exist
+/// are only replaced with `overwrite`; otherwise nothing is written and the
+/// clashes are reported. Returns the files written.
+pub fn import(file: &Path, overwrite: bool) -> io::Result<Vec<String>> {
+    let file = file.to_string_lossy();
+    let entries = list(&file)?;
+    let dir = base_dir()?;
+    let existing: Vec<&str> = entries
+        .iter()
+        .map(String::as_str)
+        .filter(|entry| dir.join(entry).exists())
+        .collect();
+    if !existing.is_empty() && !overwrite {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "Would replace {}; pass --overwrite to replace them",
+                existing.join(", ")
+            ),
+        ));
+    }
+    std::fs::create_dir_all(&dir)?;
+    let dir = dir.to_string_lossy();
+    tar(&["-xf", file.as_ref(), "-C", dir.as_ref()])?;
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_config_and_profiles_belong_in_a_bundle() {
+        for entry in [
+            "config.toml",
+            "profiles/",
+            "profiles/work.toml",
+            "./profiles/desk.toml",
+        ] {
+            assert!(is_bundle_entry(entry), "{}", entry);
+        }
+        for entry in [
+            "../.bashrc",
+            "profiles/../../.bashrc",
+            "profiles/nested/work.toml",
+            "profiles/.hidden.toml",
+            "/etc/passwd",
+            "state.json",
+        ] {
+            assert!(!is_bundle_entry(entry), "{}", entry);
+        }
+    }
+}
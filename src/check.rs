@@ -0,0 +1,325 @@
+use std::{fs, io, path::PathBuf, process::ExitCode};
+
+use crate::app::App;
+use crate::hyprconf::{self, RuleLine};
+use crate::message::Severity;
+use crate::modeline::Modeline;
+use crate::monitor::{parse_resolution, Monitor};
+
+/// Mode fields Hyprland resolves itself.
+const MODE_KEYWORDS: [&str; 4] = ["preferred", "highres", "highrr", "maxwidth"];
+
+/// Something wrong with one `monitor=` line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub severity: Severity,
+    pub file: &'static str,
+    /// 1-based.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Problem {
+    fn new(severity: Severity, rule: &RuleLine, message: String) -> Self {
+        Self {
+            severity,
+            file: rule.file,
+            line: rule.line,
+            message,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        };
+        format!(
+            "{}:{}: {}: {}",
+            self.file, self.line, severity, self.message
+        )
+    }
+}
+
+/// Checks the `monitor=` lines of `hyprland.conf` and the saved file:
+/// syntax, scales and duplicate or overridden rules, and with `outputs`
+/// (the connected monitors) whether the outputs and modes exist.
+pub fn check(
+    hyprland_conf: &str,
+    monitors_conf: &str,
+    outputs: Option<&[Monitor]>,
+) -> Vec<Problem> {
+    let rules = hyprconf::ordered_rule_lines(hyprland_conf, monitors_conf);
+    let mut problems = Vec::new();
+    for (i, rule) in rules.iter().enumerate() {
+        problems.extend(check_rule(rule, outputs));
+
+        // Hyprland uses the last line for an output; earlier ones only
+        // confuse whoever reads the file.
+        let later = rules[i + 1..]
+            .iter()
+            .rev()
+            .find(|other| other.name() == rule.name() && other.is_reserved() == rule.is_reserved());
+        if let Some(later) = later {
+            let what = if normalized(&later.value) == normalized(&rule.value) {
+                "duplicates"
+            } else {
+                "overridden by"
+            };
+            problems.push(Problem::new(
+                Severity::Warning,
+                rule,
+                format!("{} {}:{}", what, later.file, later.line),
+            ));
+        }
+    }
+    problems
+}
+
+fn check_rule(rule: &RuleLine, outputs: Option<&[Monitor]>) -> Vec<Problem> {
+    let fields: Vec<&str> = rule.value.split(',').map(str::trim).collect();
+    let mut problems = Vec::new();
+
+    // The fallback rule, with an empty name, applies to any output.
+    let output = match outputs {
+        Some(outputs) if !fields[0].is_empty() => {
+            let output = find_output(outputs, fields[0]);
+            if output.is_none() {
+                problems.push(Problem::new(
+                    Severity::Warning,
+                    rule,
+                    format!("no connected output matches {:?}", fields[0]),
+                ));
+            }
+            output
+        }
+        _ => None,
+    };
+    let mut error = |message: String| problems.push(Problem::new(Severity::Error, rule, message));
+
+    match fields.get(1).copied() {
+        None => error(String::from("expected name, mode, position and scale")),
+        Some("disable") => {}
+        Some("addreserved") => {
+            let sizes = &fields[2..];
+            if sizes.len() != 4 || sizes.iter().any(|size| size.parse::<u32>().is_err()) {
+                error(String::from(
+                    "addreserved takes four sizes: top, bottom, left, right",
+                ));
+            }
+        }
+        Some(mode) => {
+            if fields.len() < 4 {
+                error(String::from("expected name, mode, position and scale"));
+                return problems;
+            }
+            let resolution = match check_mode(mode) {
+                Ok(resolution) => resolution,
+                Err(message) => {
+                    error(message);
+                    None
+                }
+            };
+            if !valid_position(fields[2]) {
+                error(format!(
+                    "invalid position {:?}; expected XxY or auto",
+                    fields[2]
+                ));
+            }
+            match fields[3] {
+                "auto" => {}
+                scale => match scale.parse::<f64>() {
+                    Ok(scale) if scale > 0.0 => {
+                        if let Some((w, h)) = resolution.map(|(w, h, _)| (w, h)) {
+                            if !divides_evenly(w, scale) || !divides_evenly(h, scale) {
+                                problems.push(Problem::new(
+                                    Severity::Warning,
+                                    rule,
+                                    format!(
+                                        "scale {} doesn't divide {}x{} into whole pixels; Hyprland picks a nearby one",
+                                        fields[3], w, h
+                                    ),
+                                ));
+                            }
+                        }
+                    }
+                    _ => error(format!("invalid scale {:?}", scale)),
+                },
+            }
+            if let (Some(monitor), Some((w, h, rate))) = (output, resolution) {
+                if !advertises(monitor, w, h, rate) {
+                    problems.push(Problem::new(
+                        Severity::Warning,
+                        rule,
+                        format!(
+                            "{} doesn't advertise {}; Hyprland picks the closest mode",
+                            monitor.name, mode
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// The width, height and rate of a `WxH[@RATE]` mode field, `None` for
+/// keywords and modelines, or why the field is invalid.
+#[allow(clippy::type_complexity)]
+fn check_mode(mode: &str) -> Result<Option<(u32, u32, Option<f64>)>, String> {
+    if MODE_KEYWORDS.contains(&mode) {
+        return Ok(None);
+    }
+    if let Some(spec) = mode.strip_prefix("modeline") {
+        return Modeline::from_spec(spec)
+            .map(|_| None)
+            .ok_or_else(|| format!("invalid modeline {:?}", spec.trim()));
+    }
+    let (resolution, rate) = match mode.split_once('@') {
+        Some((resolution, rate)) => (resolution, Some(rate)),
+        None => (mode, None),
+    };
+    let invalid = || format!("invalid mode {:?}; expected WxH@RATE or preferred", mode);
+    let (w, h) = parse_resolution(resolution).ok_or_else(invalid)?;
+    let rate = match rate {
+        Some(rate) => Some(
+            rate.trim_end_matches("Hz")
+                .parse::<f64>()
+                .map_err(|_| invalid())?,
+        ),
+        None => None,
+    };
+    Ok(Some((w, h, rate)))
+}
+
+fn valid_position(position: &str) -> bool {
+    position.starts_with("auto")
+        || position
+            .split_once('x')
+            .is_some_and(|(x, y)| x.parse::<i32>().is_ok() && y.parse::<i32>().is_ok())
+}
+
+fn divides_evenly(pixels: u32, scale: f64) -> bool {
+    let logical = pixels as f64 / scale;
+    (logical - logical.round()).abs() < 0.01
+}
+
+/// The connected monitor a rule's name field refers to, by connector or
+/// `desc:` description.
+fn find_output<'a>(outputs: &'a [Monitor], name: &str) -> Option<&'a Monitor> {
+    outputs.iter().find(|monitor| monitor.matches(name))
+}
+
+fn advertises(monitor: &Monitor, w: u32, h: u32, rate: Option<f64>) -> bool {
+    monitor
+        .modes
+        .get(&format!("{}x{}", w, h))
+        .is_some_and(|rates| {
+            rate.is_none_or(|rate| rates.iter().any(|r| (r.value - rate).abs() < 0.5))
+        })
+}
+
+/// A rule with the spaces around its fields removed.
+fn normalized(value: &str) -> String {
+    value
+        .split(',')
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Runs `hypr-tui check`: prints each problem and exits with 1 for errors,
+/// 2 for warnings only and 0 for a clean config.
+pub fn run(hyprland_conf: Option<PathBuf>, monitors_conf: Option<PathBuf>) -> io::Result<ExitCode> {
+    let hyprland_conf = read(hyprland_conf.map_or_else(hyprconf::hyprland_conf_path, Ok)?)?;
+    let monitors_conf = read(monitors_conf.map_or_else(hyprconf::monitors_conf_path, Ok)?)?;
+
+    let app = App::new().ok();
+    if app.is_none() {
+        eprintln!("Hyprland isn't running; not checking outputs and modes");
+    }
+    let outputs = app.as_ref().map(|app| app.monitors.as_slice());
+    let problems = check(&hyprland_conf, &monitors_conf, outputs);
+    for problem in &problems {
+        println!("{}", problem.describe());
+    }
+
+    let errors = problems
+        .iter()
+        .filter(|problem| problem.severity == Severity::Error)
+        .count();
+    let warnings = problems.len() - errors;
+    println!("{} errors, {} warnings", errors, warnings);
+    Ok(ExitCode::from(if errors > 0 {
+        1
+    } else if warnings > 0 {
+        2
+    } else {
+        0
+    }))
+}
+
+fn read(path: PathBuf) -> io::Result<String> {
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(io::Error::new(
+            e.kind(),
+            format!("{}: {}", path.display(), e),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_bad_fields_unknown_outputs_and_overrides() {
+        let hyprland = "\
+monitor=DP-1,1920x1080@60,0x0,1
+monitor=eDP-1,2880x1800@90,auto,1.7
+source = ~/.config/hypr/monitors.conf
+monitor=HDMI-A-1,huge,0x0,0
+";
+        let saved = "\
+monitor=DP-1,2560x1440@144.00,-2560x0,1.00
+monitor = DP-1 , 2560x1440@144.00 , -2560x0 , 1.00
+monitor=desc:Dell U2720Q,3840x2160@60,auto,1
+";
+        let mut dp1: Monitor = serde_json::from_value(serde_json::json!({
+            "name": "DP-1",
+            "description": "Dell U2720Q 0x1234",
+        }))
+        .expect("monitor parses");
+        dp1.modes = [
+            (String::from("1920x1080"), vec![60.0.into()]),
+            (String::from("2560x1440"), vec![143.97.into()]),
+        ]
+        .into_iter()
+        .collect();
+        let outputs = [dp1];
+
+        let problems: Vec<String> = check(hyprland, saved, Some(&outputs))
+            .iter()
+            .map(Problem::describe)
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                "hyprland.conf:1: warning: overridden by monitors.conf:2",
+                "hyprland.conf:2: warning: no connected output matches \"eDP-1\"",
+                "hyprland.conf:2: warning: scale 1.7 doesn't divide 2880x1800 into whole pixels; Hyprland picks a nearby one",
+                "monitors.conf:1: warning: duplicates monitors.conf:2",
+                "monitors.conf:3: warning: DP-1 doesn't advertise 3840x2160@60; Hyprland picks the closest mode",
+                "hyprland.conf:4: warning: no connected output matches \"HDMI-A-1\"",
+                "hyprland.conf:4: error: invalid mode \"huge\"; expected WxH@RATE or preferred",
+                "hyprland.conf:4: error: invalid scale \"0\"",
+            ]
+        );
+        assert!(check(hyprland, saved, None)
+            .iter()
+            .all(|problem| !problem.message.contains("output")));
+    }
+}
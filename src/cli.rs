@@ -0,0 +1,534 @@
+use chrono::Local;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::{fs, io, path::PathBuf, process::ExitCode};
+
+use crate::app::App;
+use crate::autostart;
+use crate::bundle;
+use crate::check;
+use crate::config::Config;
+use crate::control::ControlServer;
+use crate::daemon::Daemon;
+use crate::doctor;
+use crate::events;
+use crate::hyprconf;
+use crate::idle;
+use crate::import::ImportSource;
+use crate::layout_log;
+use crate::monitorv2::{self, Syntax};
+use crate::notify;
+use crate::presets::Preset;
+use crate::profile::{self, Profile};
+use crate::state::{State, StateFormat};
+use crate::status::Status;
+use crate::svg;
+
+#[derive(Parser)]
+#[command(
+    version,
+    about = "Terminal UI for managing Hyprland monitors",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    /// Use plain ASCII markers instead of emoji and unicode arrows
+    #[arg(long)]
+    pub ascii: bool,
+    /// Ask questions line by line instead of drawing the TUI, for screen
+    /// readers and braille displays
+    #[arg(long)]
+    pub plain: bool,
+    /// Apply a saved profile and exit, without opening the TUI
+    #[arg(long, value_name = "PROFILE")]
+    pub apply: Option<String>,
+    /// Press the keys listed in FILE, one per line, before handing the TUI
+    /// over
+    #[arg(long, value_name = "FILE")]
+    pub script: Option<PathBuf>,
+    /// Write every key pressed to FILE, for replaying with --script
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Apply a display-switcher preset without opening the TUI
+    Preset { preset: Preset },
+    /// Apply a saved profile without opening the TUI
+    ApplyProfile { name: String },
+    /// Manage saved profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+    /// Save another compositor's output layout as a profile
+    Import {
+        /// Name of the profile to create
+        name: String,
+        /// Tool whose JSON output describes the layout
+        #[arg(long, value_enum)]
+        from: ImportSource,
+        /// Read JSON saved earlier instead of running the tool now
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+    },
+    /// Write the current layout as a diagram
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Svg)]
+        format: ExportFormat,
+        /// File to write instead of standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Back up or restore every monitor's settings
+    State {
+        #[command(subcommand)]
+        action: StateAction,
+    },
+    /// Move the app config, aliases and profiles to another machine as one
+    /// file
+    Bundle {
+        #[command(subcommand)]
+        action: BundleAction,
+    },
+    /// Print (or install) a login snippet that applies a profile automatically
+    Autostart {
+        profile: String,
+        /// Generate a systemd user unit instead of a Hyprland exec-once line
+        #[arg(long)]
+        systemd: bool,
+        /// Write the snippet to disk (and enable the unit) instead of printing it
+        #[arg(long)]
+        install: bool,
+    },
+    /// Print (or write into hypridle.conf) listeners that blank each monitor
+    /// after its [idle] timeout
+    Idle {
+        /// Update ~/.config/hypr/hypridle.conf instead of printing
+        #[arg(long)]
+        install: bool,
+    },
+    /// Run in the background, reacting to power source changes and blanking
+    /// the monitors at night
+    Daemon {
+        /// Profile to apply on start; its settings override the app config
+        #[arg(long)]
+        profile: Option<String>,
+        #[command(subcommand)]
+        action: Option<DaemonAction>,
+    },
+    /// Print the active profile and monitors
+    Status {
+        /// Print the JSON a waybar or eww custom module reads
+        #[arg(long)]
+        waybar: bool,
+        /// Keep running and print again whenever the monitors change
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Print the logged layout changes, newest last
+    History {
+        /// Only print the last N changes
+        #[arg(short = 'n', long, value_name = "N")]
+        limit: Option<usize>,
+        /// Only print changes to this monitor (connector name or alias)
+        #[arg(long)]
+        monitor: Option<String>,
+    },
+    /// Print monitor-related Hyprland events as they happen
+    Watch {
+        /// Print one JSON object per line instead of plain text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the monitor= lines of hyprland.conf and monitors.conf; exits
+    /// with 1 on errors and 2 on warnings only
+    Check {
+        /// hyprland.conf to read instead of the one in ~/.config/hypr
+        #[arg(long, value_name = "FILE")]
+        hyprland: Option<PathBuf>,
+        /// monitors.conf to read instead of the one in ~/.config/hypr
+        #[arg(long, value_name = "FILE")]
+        monitors: Option<PathBuf>,
+    },
+    /// Rewrite the saved monitor rules as monitorv2 blocks (Hyprland 0.50+) or
+    /// back as monitor= lines, checked against the running Hyprland
+    Migrate {
+        /// Syntax to write the rules in
+        #[arg(long, value_enum, default_value_t = Syntax::V2)]
+        to: Syntax,
+        /// File to rewrite instead of ~/.config/hypr/monitors.conf
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// Replace the file, keeping a .bak copy, instead of printing the result
+        #[arg(long)]
+        write: bool,
+    },
+    /// Print a report for bug reports: versions, raw and parsed monitors and
+    /// config files, with serial numbers, hostname and user name removed
+    Doctor,
+    /// Handle a laptop lid switch event (used by the saved `bindl` rules)
+    Lid {
+        #[command(subcommand)]
+        action: LidAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// Save the current layout as a profile
+    Save { name: String },
+    /// List saved profiles
+    List,
+    /// Apply the next saved profile that fits the connected monitors
+    Next,
+    /// Apply the previous saved profile that fits the connected monitors
+    Prev,
+    /// Print the Hyprland config lines for per-hostname layouts
+    HostSnippet,
+}
+
+/// Requests to the running daemon (or TUI), over its control socket.
+#[derive(Subcommand)]
+pub enum DaemonAction {
+    /// Print its pid, the active profile and the active monitors
+    Status,
+    /// Make it read its config and profile again and re-check its rules
+    Reload,
+    /// Shut it down cleanly
+    Stop,
+}
+
+#[derive(Subcommand)]
+pub enum StateAction {
+    /// Print the full layout of all monitors
+    Dump {
+        #[arg(long, value_enum, default_value_t = StateFormat::Json)]
+        format: StateFormat,
+    },
+    /// Apply a layout printed by `state dump` (TOML if the file ends in .toml)
+    Restore { file: PathBuf },
+}
+
+#[derive(Subcommand)]
+pub enum BundleAction {
+    /// Write config.toml and every profile to a .tar.zst file
+    Export { file: PathBuf },
+    /// Unpack a bundle into ~/.config/hyprmonitor
+    Import {
+        file: PathBuf,
+        /// Replace files that already exist instead of stopping
+        #[arg(long)]
+        overwrite: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// Monitors drawn to scale at their layout positions
+    Svg,
+}
+
+#[derive(Subcommand)]
+pub enum LidAction {
+    /// Disable the internal panel
+    Close {
+        /// Only disable it when an external monitor is active
+        #[arg(long)]
+        only_with_external: bool,
+    },
+    /// Re-enable the internal panel with its saved settings
+    Open,
+}
+
+pub fn run(command: Command) -> io::Result<ExitCode> {
+    let result = match command {
+        Command::Preset { preset } => {
+            let mut app = App::new()?;
+            let result = app.apply_preset(preset);
+            report(&app, result)
+        }
+        Command::ApplyProfile { name } => {
+            // Load first so a typo fails before Hyprland is queried.
+            let profile = Profile::load(&name)?;
+            let mut app = App::new()?;
+            let result = app.apply_profile(&profile);
+            report(&app, result)
+        }
+        Command::Profile { action } => match action {
+            ProfileAction::Save { name } => {
+                let app = App::new()?;
+                let mut profile = app.current_profile(&name);
+                // Re-saving a layout keeps the hooks, images, power settings
+                // and blanking schedule written into the file.
+                if let Ok(existing) = Profile::load(&name) {
+                    profile.on_apply = existing.on_apply;
+                    profile.wallpapers = existing.wallpapers;
+                    profile.power = existing.power;
+                    profile.blanking = existing.blanking;
+                }
+                let result = profile
+                    .save()
+                    .map(|path| format!("Saved profile {} to {}", name, path.display()))
+                    .map_err(|e| e.to_string());
+                report(&app, result)
+            }
+            ProfileAction::List => {
+                for name in Profile::list()? {
+                    println!("{}", name);
+                }
+                Ok(())
+            }
+            ProfileAction::Next | ProfileAction::Prev => {
+                let mut app = App::new()?;
+                let result = app.cycle_profile(matches!(action, ProfileAction::Next));
+                report(&app, result)
+            }
+            ProfileAction::HostSnippet => {
+                let host_path = hyprconf::host_monitors_conf_path(&profile::hostname()?)?;
+                println!("# hyprland.conf (shared between machines)");
+                println!("source = {}", hyprconf::monitors_conf_path()?.display());
+                println!();
+                println!("# monitors.conf (per machine, written by Save with [profiles] per_host)");
+                print!("{}", hyprconf::host_source_stub(&host_path));
+                Ok(())
+            }
+        },
+        Command::Import { name, from, file } => {
+            let json = match file {
+                Some(path) => fs::read_to_string(path)?,
+                None => from.query()?,
+            };
+            let profile = from.parse(&name, &json)?;
+            let path = profile.save()?;
+            println!(
+                "Saved profile {} with {} monitors to {}",
+                name,
+                profile.monitors.len(),
+                path.display()
+            );
+            Ok(())
+        }
+        Command::Export { format, output } => {
+            let app = App::new()?;
+            let contents = match format {
+                ExportFormat::Svg => svg::layout(&app.monitors, &app.configs),
+            };
+            match output {
+                Some(path) => fs::write(path, contents),
+                None => {
+                    print!("{}", contents);
+                    Ok(())
+                }
+            }
+        }
+        Command::State { action } => match action {
+            StateAction::Dump { format } => {
+                let app = App::new()?;
+                print!(
+                    "{}",
+                    State::capture(&app.monitors, &app.configs).dump(format)?
+                );
+                Ok(())
+            }
+            StateAction::Restore { file } => {
+                // Parse first so a broken file fails before Hyprland is queried.
+                let contents = fs::read_to_string(&file)?;
+                let state = State::parse(&contents, StateFormat::for_path(&file))?;
+                let mut app = App::new()?;
+                let result = app.restore_state(&state);
+                report(&app, result)
+            }
+        },
+        Command::Bundle { action } => {
+            let (verb, files) = match action {
+                BundleAction::Export { file } => ("Exported", bundle::export(&file)?),
+                BundleAction::Import { file, overwrite } => {
+                    ("Imported", bundle::import(&file, overwrite)?)
+                }
+            };
+            println!("{} {} files:", verb, files.len());
+            for file in files {
+                println!("  {}", file);
+            }
+            Ok(())
+        }
+        Command::Autostart {
+            profile,
+            systemd,
+            install,
+        } => {
+            // Fail early on typos rather than generating a broken snippet.
+            let profile = Profile::load(&profile)?;
+            match (systemd, install) {
+                (false, false) => print!("{}", autostart::exec_once_snippet(&profile)),
+                (true, false) => print!("{}", autostart::systemd_unit(&profile)?),
+                (false, true) => {
+                    let path = autostart::install_exec_once(&profile)?;
+                    println!("Wrote {}", path.display());
+                    println!("Add 'source = {}' to your hyprland.conf", path.display());
+                }
+                (true, true) => {
+                    let path = autostart::install_systemd(&profile)?;
+                    println!("Installed and enabled {}", path.display());
+                }
+            }
+            Ok(())
+        }
+        Command::Idle { install } => {
+            let config = Config::load()?;
+            if config.idle.is_empty() {
+                return Err(io::Error::other(
+                    "No idle timeouts configured; add an [idle] table to config.toml",
+                ));
+            }
+            if install {
+                let path = idle::install(&config.idle)?;
+                println!("Updated {}; restart hypridle to use it", path.display());
+            } else {
+                print!("{}", idle::listener_blocks(&config.idle));
+            }
+            Ok(())
+        }
+        Command::Daemon {
+            profile: _,
+            action: Some(action),
+        } => {
+            let command = match action {
+                DaemonAction::Status => "status",
+                DaemonAction::Reload => "reload",
+                DaemonAction::Stop => "stop",
+            };
+            let response = ControlServer::send(&serde_json::json!({ "command": command }))?;
+            if response["ok"] != true {
+                return Err(io::Error::other(
+                    response["error"]
+                        .as_str()
+                        .unwrap_or("Request failed")
+                        .to_string(),
+                ));
+            }
+            match response["status"].as_str() {
+                Some(status) => {
+                    println!("Running (pid {})", response["pid"]);
+                    println!("{}", status);
+                }
+                None => println!("{}", response["message"].as_str().unwrap_or_default()),
+            }
+            Ok(())
+        }
+        Command::Daemon {
+            profile,
+            action: None,
+        } => Daemon::new(profile)?.run(),
+        Command::Status { waybar, follow } => {
+            let mut app = App::new()?;
+            let print = |app: &App| {
+                let status = Status::capture(&app.monitors, &app.configs, profile::last_applied());
+                if waybar {
+                    status.waybar().to_string()
+                } else {
+                    status.plain()
+                }
+            };
+            let mut last = print(&app);
+            println!("{}", last);
+            if !follow {
+                return Ok(ExitCode::SUCCESS);
+            }
+            for event in events::subscribe()? {
+                if !event?.is_monitor_event() {
+                    continue;
+                }
+                // Start over so settings read back fresh rather than kept
+                // as pending edits.
+                app.monitors.clear();
+                app.configs.clear();
+                app.refresh_monitors()?;
+                let current = print(&app);
+                if current != last {
+                    println!("{}", current);
+                    last = current;
+                }
+            }
+            Ok(())
+        }
+        Command::History { limit, monitor } => {
+            // An alias only resolves while Hyprland can say which output
+            // it is.
+            let monitor = monitor.map(|name| {
+                App::new()
+                    .ok()
+                    .and_then(|app| {
+                        app.find_monitor(&name)
+                            .map(|i| app.monitors[i].name.clone())
+                    })
+                    .unwrap_or(name)
+            });
+            let changes: Vec<_> = layout_log::load()?
+                .into_iter()
+                .filter(|change| monitor.as_ref().is_none_or(|m| *m == change.monitor))
+                .collect();
+            if changes.is_empty() {
+                println!("No layout changes logged yet");
+            }
+            let start = limit.map_or(0, |n| changes.len().saturating_sub(n));
+            for change in &changes[start..] {
+                println!("{}", change.describe());
+            }
+            Ok(())
+        }
+        Command::Watch { json } => {
+            for event in events::subscribe()? {
+                let event = event?;
+                if !event.is_monitor_event() {
+                    continue;
+                }
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::json!({
+                            "time": Local::now().to_rfc3339(),
+                            "event": event.name,
+                            "data": event.data,
+                        })
+                    );
+                } else {
+                    println!(
+                        "{} {} {}",
+                        Local::now().format("%H:%M:%S"),
+                        event.name,
+                        event.data
+                    );
+                }
+            }
+            Ok(())
+        }
+        Command::Lid { action } => {
+            let mut app = App::new()?;
+            let result = match action {
+                LidAction::Close { only_with_external } => app.lid_closed(only_with_external),
+                LidAction::Open => app.lid_opened(),
+            };
+            println!("{}", result.map_err(io::Error::other)?);
+            Ok(())
+        }
+        Command::Check { hyprland, monitors } => return check::run(hyprland, monitors),
+        Command::Migrate { to, file, write } => monitorv2::run(file, to, write),
+        Command::Doctor => {
+            print!("{}", doctor::report());
+            Ok(())
+        }
+    };
+    result.map(|()| ExitCode::SUCCESS)
+}
+
+/// Prints the outcome of a headless action, mirroring it as a desktop
+/// notification when those are enabled.
+fn report(app: &App, result: Result<String, String>) -> io::Result<()> {
+    notify::send(&app.config.notifications, &result);
+    let message = result.map_err(io::Error::other)?;
+    println!("{}", message);
+    Ok(())
+}
@@ -1,9 +1,73 @@
+use std::fmt;
 use std::process::{Command, Stdio};
 
-pub fn execute_hyprctl(command: &str) -> bool {
-    Command::new("sh")
-        .arg("-c")
-        .arg(command)
+/// A hyprctl invocation kept as an argument vector. Arguments reach hyprctl
+/// verbatim, so monitor names or descriptions containing quotes, spaces or
+/// backticks can't break the command or be interpreted by a shell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyprCommand {
+    args: Vec<String>,
+}
+
+impl HyprCommand {
+    pub fn new<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// `hyprctl keyword monitor <rule>`
+    pub fn monitor(rule: impl Into<String>) -> Self {
+        Self::new(["keyword".to_string(), "monitor".to_string(), rule.into()])
+    }
+
+    /// `hyprctl dispatch <dispatcher> <arg>`
+    pub fn dispatch(dispatcher: &str, arg: impl Into<String>) -> Self {
+        Self::new(["dispatch".to_string(), dispatcher.to_string(), arg.into()])
+    }
+
+    /// `hyprctl dispatch moveworkspacetomonitor <workspace> <monitor>`
+    pub fn move_workspace(workspace: i64, monitor: &str) -> Self {
+        Self::new([
+            "dispatch".to_string(),
+            "moveworkspacetomonitor".to_string(),
+            workspace.to_string(),
+            monitor.to_string(),
+        ])
+    }
+
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+}
+
+impl fmt::Display for HyprCommand {
+    /// Renders the command the way it would be typed in a shell, quoting
+    /// arguments where needed. Only used for display, never executed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hyprctl")?;
+        for arg in &self.args {
+            let plain = !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_.,:@/+=".contains(c));
+            if plain {
+                write!(f, " {}", arg)?;
+            } else {
+                write!(f, " '{}'", arg.replace('\'', r"'\''"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn execute_hyprctl(command: &HyprCommand) -> bool {
+    Command::new("hyprctl")
+        .args(command.args())
         .stdout(Stdio::null())
         .stderr(Stdio::null())
         .status()
@@ -11,10 +75,86 @@ pub fn execute_hyprctl(command: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Runs `commands` in order with a single `hyprctl --batch` call, returning
+/// whether each one succeeded. Falls back to one call per command when an
+/// argument contains the batch separator.
+pub fn execute_batch(commands: &[HyprCommand]) -> Vec<bool> {
+    let splittable = commands
+        .iter()
+        .flat_map(HyprCommand::args)
+        .any(|arg| arg.contains(';') || arg.contains(char::is_whitespace));
+    if commands.len() < 2 || splittable {
+        return commands.iter().map(execute_hyprctl).collect();
+    }
+
+    let batch = commands
+        .iter()
+        .map(|command| command.args().join(" "))
+        .collect::<Vec<_>>()
+        .join(" ; ");
+    let output = match Command::new("hyprctl").args(["--batch", &batch]).output() {
+        Ok(output) => output,
+        Err(_) => return vec![false; commands.len()],
+    };
+
+    // hyprctl answers each command in turn, separated by blank lines.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let responses: Vec<&str> = stdout
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|response| !response.is_empty())
+        .collect();
+    if responses.len() == commands.len() {
+        responses.iter().map(|response| *response == "ok").collect()
+    } else {
+        vec![output.status.success() && responses.iter().all(|r| *r == "ok"); commands.len()]
+    }
+}
+
 pub fn fetch_monitors() -> std::io::Result<Vec<serde_json::Value>> {
-    let output = Command::new("hyprctl")
-        .args(["monitors", "all", "-j"])
-        .output()?;
+    fetch_json(&["monitors", "all"])
+}
+
+pub fn fetch_workspaces() -> std::io::Result<Vec<serde_json::Value>> {
+    fetch_json(&["workspaces"])
+}
+
+pub fn fetch_clients() -> std::io::Result<Vec<serde_json::Value>> {
+    fetch_json(&["clients"])
+}
+
+pub fn fetch_version() -> std::io::Result<serde_json::Value> {
+    fetch_json(&["version"])
+}
+
+/// Whether Hyprland currently lets windows tear.
+pub fn fetch_allow_tearing() -> std::io::Result<bool> {
+    let option: serde_json::Value = fetch_json(&["getoption", "general:allow_tearing"])?;
+    option["int"]
+        .as_i64()
+        .map(|value| value != 0)
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "general:allow_tearing without int",
+            )
+        })
+}
+
+/// Where the pointer is, in layout coordinates.
+pub fn fetch_cursor_position() -> std::io::Result<(i64, i64)> {
+    let position: serde_json::Value = fetch_json(&["cursorpos"])?;
+    match (position["x"].as_i64(), position["y"].as_i64()) {
+        (Some(x), Some(y)) => Ok((x, y)),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "cursorpos without x and y",
+        )),
+    }
+}
+
+fn fetch_json<T: serde::de::DeserializeOwned>(args: &[&str]) -> std::io::Result<T> {
+    let output = Command::new("hyprctl").args(args).arg("-j").output()?;
 
     serde_json::from_slice(&output.stdout)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
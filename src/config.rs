@@ -0,0 +1,314 @@
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, io, path::PathBuf, time::SystemTime};
+
+use crate::blanking::BlankingSettings;
+use crate::hooks::HookSettings;
+use crate::i18n::tr;
+use crate::main_monitor::MainSettings;
+use crate::monitor::{parse_resolution, Rate};
+use crate::notify::NotificationSettings;
+use crate::power::PowerSettings;
+use crate::profile::ProfileSettings;
+use crate::theme::ThemeName;
+use crate::wallpaper::WallpaperSettings;
+use crate::xdg;
+
+const CONFIG_PATH: &str = "hyprmonitor/config.toml";
+
+/// Hyprmonitor's own settings, read from `~/.config/hyprmonitor/config.toml`.
+/// Every field has a default so a missing file or section is not an error.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Config {
+    pub mode_filter: ModeFilter,
+    pub modes: ModeSettings,
+    pub scale: ScaleSettings,
+    pub history: HistorySettings,
+    pub notifications: NotificationSettings,
+    pub hooks: HookSettings,
+    pub wallpaper: WallpaperSettings,
+    pub power: PowerSettings,
+    pub blanking: BlankingSettings,
+    pub profiles: ProfileSettings,
+    pub main: MainSettings,
+    /// Named sets of monitors, e.g. `"side panels" = ["DP-2", "DP-3"]`,
+    /// listed together and acted on at once.
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// Friendly names by serial number or EDID description, e.g.
+    /// `"Dell Inc. DELL U2720Q 9MTF423" = "Left Dell"`, shown next to the
+    /// connector and accepted wherever a monitor name is.
+    pub aliases: BTreeMap<String, String>,
+    /// Seconds without input before each monitor sleeps, e.g.
+    /// `"HDMI-A-1" = 120`, written to hypridle.conf by `hypr-tui idle`.
+    pub idle: BTreeMap<String, u32>,
+    /// Outputs to leave alone, by connector or `desc:` description prefix,
+    /// e.g. a ghost HDMI port or a capture card's EDID emulator. They are
+    /// left out of the TUI, arranging and the saved file.
+    pub ignore: Vec<String>,
+    pub ui: UiSettings,
+    pub keys: KeySettings,
+}
+
+/// Rebindable keys.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct KeySettings {
+    /// Apply the next saved profile that fits the connected monitors.
+    pub profile_next: char,
+    pub profile_previous: char,
+}
+
+impl Default for KeySettings {
+    fn default() -> Self {
+        Self {
+            profile_next: ']',
+            profile_previous: '[',
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct UiSettings {
+    /// Draw with plain ASCII markers instead of emoji and unicode arrows.
+    pub ascii: bool,
+    pub monitor_order: MonitorOrder,
+    pub theme: ThemeName,
+    /// Language code such as `de`; the locale environment when unset.
+    pub language: Option<String>,
+}
+
+/// How the Monitors pane lists monitors.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MonitorOrder {
+    /// Left to right, then top to bottom, as the screens sit on the desk.
+    /// Disabled monitors have no position and come last.
+    #[default]
+    Position,
+    Name,
+}
+
+impl MonitorOrder {
+    pub fn label(self) -> &'static str {
+        match self {
+            MonitorOrder::Position => tr("order.position"),
+            MonitorOrder::Name => tr("order.name"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct HistorySettings {
+    /// Also keep the command history in `~/.local/state/hyprmonitor`.
+    pub persist: bool,
+}
+
+/// Step sizes and bounds for scale adjustment, as scale factors (1.0 = 100%).
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ScaleSettings {
+    pub step: f64,
+    pub fine_step: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Bounds for scales set from a virtual resolution, which may go past
+    /// `min` to supersample or past `max` to zoom.
+    pub virtual_min: f64,
+    pub virtual_max: f64,
+}
+
+impl Default for ScaleSettings {
+    fn default() -> Self {
+        Self {
+            step: 0.25,
+            fine_step: 0.05,
+            min: 0.5,
+            max: 3.0,
+            virtual_min: 0.25,
+            virtual_max: 4.0,
+        }
+    }
+}
+
+impl ScaleSettings {
+    /// The settings as percentages, matching `MonitorConfig::scale`.
+    pub fn step_percent(&self, fine: bool) -> i32 {
+        to_percent(if fine { self.fine_step } else { self.step }).max(1)
+    }
+
+    pub fn min_percent(&self) -> i32 {
+        to_percent(self.min).max(1)
+    }
+
+    pub fn max_percent(&self) -> i32 {
+        to_percent(self.max).max(self.min_percent())
+    }
+
+    pub fn virtual_min_percent(&self) -> i32 {
+        to_percent(self.virtual_min).max(1)
+    }
+
+    pub fn virtual_max_percent(&self) -> i32 {
+        to_percent(self.virtual_max).max(self.virtual_min_percent())
+    }
+}
+
+fn to_percent(value: f64) -> i32 {
+    (value * 100.0).round() as i32
+}
+
+/// Hides modes that are rarely useful on a desktop, such as the interlaced and
+/// low-resolution modes many TVs advertise.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ModeFilter {
+    pub enabled: bool,
+    pub hide_interlaced: bool,
+    pub min_refresh_rate: f64,
+    pub min_width: u32,
+    pub min_height: u32,
+}
+
+/// How modes are picked and written in the rules sent to Hyprland.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ModeSettings {
+    /// Pass the `availableModes` token through unaltered instead of
+    /// rebuilding it from the parsed resolution and rate.
+    pub strict: bool,
+    /// The rate picked when changing the resolution.
+    pub rate_policy: RatePolicy,
+}
+
+/// Which refresh rate a new resolution starts at.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RatePolicy {
+    #[default]
+    Highest,
+    /// The rate nearest the one the previous resolution ran at.
+    Closest,
+    /// 60 Hz (or 59.94) where offered, the highest otherwise; for TVs whose
+    /// audio or HDR handling breaks at high rates.
+    #[serde(rename = "60hz")]
+    Prefer60,
+}
+
+impl RatePolicy {
+    /// The rate to use from `rates`, highest first, after running at
+    /// `previous`.
+    pub fn pick(self, rates: &[f64], previous: f64) -> Option<f64> {
+        let nearest = |target: f64| {
+            rates
+                .iter()
+                .copied()
+                .min_by(|a, b| (a - target).abs().total_cmp(&(b - target).abs()))
+        };
+        match self {
+            RatePolicy::Highest => rates.first().copied(),
+            RatePolicy::Closest => nearest(previous),
+            RatePolicy::Prefer60 => nearest(60.0)
+                .filter(|rate| (rate - 60.0).abs() < 1.0)
+                .or_else(|| rates.first().copied()),
+        }
+    }
+}
+
+impl Default for ModeFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hide_interlaced: true,
+            // Just under 60 so NTSC-style 59.94 Hz modes are kept.
+            min_refresh_rate: 59.0,
+            min_width: 1280,
+            min_height: 720,
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> io::Result<Self> {
+        let path = Self::path()?;
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+
+        toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e.message()),
+            )
+        })
+    }
+
+    pub fn path() -> io::Result<PathBuf> {
+        xdg::config_path(CONFIG_PATH)
+    }
+
+    /// When the config file last changed, or `None` if there is none.
+    pub fn modified() -> Option<SystemTime> {
+        fs::metadata(Self::path().ok()?).ok()?.modified().ok()
+    }
+}
+
+impl ModeFilter {
+    pub fn allows(&self, resolution: &str, refresh_rate: f64) -> bool {
+        if self.hide_interlaced && resolution.ends_with('i') {
+            return false;
+        }
+        if refresh_rate < self.min_refresh_rate {
+            return false;
+        }
+        match parse_resolution(resolution) {
+            Some((w, h)) => w >= self.min_width && h >= self.min_height,
+            None => true,
+        }
+    }
+
+    /// Filters `modes`, always keeping the `current` mode so the monitor's
+    /// configured state never disappears from the list.
+    pub fn apply(
+        &self,
+        modes: &BTreeMap<String, Vec<Rate>>,
+        current: (&str, f64),
+    ) -> BTreeMap<String, Vec<Rate>> {
+        modes
+            .iter()
+            .filter_map(|(res, rates)| {
+                let kept: Vec<Rate> = rates
+                    .iter()
+                    .filter(|rate| {
+                        self.allows(res, rate.value)
+                            || (res == current.0 && (rate.value - current.1).abs() < 0.01)
+                    })
+                    .cloned()
+                    .collect();
+                (!kept.is_empty()).then(|| (res.clone(), kept))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_policies_pick_from_the_new_resolution() {
+        let rates = [119.88, 100.0, 60.0, 59.94, 50.0];
+        assert_eq!(RatePolicy::Highest.pick(&rates, 50.0), Some(119.88));
+        assert_eq!(RatePolicy::Closest.pick(&rates, 98.0), Some(100.0));
+        assert_eq!(RatePolicy::Prefer60.pick(&rates, 120.0), Some(60.0));
+        assert_eq!(
+            RatePolicy::Prefer60.pick(&[144.0, 75.0], 60.0),
+            Some(144.0),
+            "falls back to the highest"
+        );
+        assert_eq!(RatePolicy::Closest.pick(&[], 60.0), None);
+    }
+}
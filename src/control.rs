@@ -0,0 +1,253 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    cell::Cell,
+    env, fs,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+    process,
+    time::Duration,
+};
+
+use crate::app::App;
+use crate::profile::{self, Profile};
+use crate::status::Status;
+
+const SOCKET_NAME: &str = "hyprmonitor.sock";
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// One JSON request per connection, e.g. `{"command":"apply_profile","name":"work"}`.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum Request {
+    ApplyProfile {
+        name: String,
+    },
+    /// Toggles the named monitor, or the focused one.
+    ToggleDpms {
+        monitor: Option<String>,
+    },
+    State,
+    /// Identifies the running instance to one that is starting up.
+    Instance,
+    /// The pid, the profile applied last and the active monitors.
+    Status,
+    /// Reads the config again and re-evaluates the daemon's rules.
+    Reload,
+    Stop,
+}
+
+/// What the loop serving the socket is asked to do beyond the request
+/// itself, once the response is sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lifecycle {
+    Reload,
+    Stop,
+}
+
+/// Control socket at `$XDG_RUNTIME_DIR/hyprmonitor.sock`, polled from the
+/// TUI and daemon loops so scripts can drive the running instance.
+pub struct ControlServer {
+    listener: UnixListener,
+    path: PathBuf,
+    lifecycle: Cell<Option<Lifecycle>>,
+}
+
+impl ControlServer {
+    fn path() -> io::Result<PathBuf> {
+        let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "XDG_RUNTIME_DIR is not set"))?;
+        Ok(PathBuf::from(runtime_dir).join(SOCKET_NAME))
+    }
+
+    pub fn bind() -> io::Result<Self> {
+        let path = Self::path()?;
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("{} is in use by another instance", path.display()),
+                ));
+            }
+            // Left behind by an instance that didn't shut down cleanly.
+            fs::remove_file(&path)?;
+        }
+
+        let listener = UnixListener::bind(&path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            path,
+            lifecycle: Cell::new(None),
+        })
+    }
+
+    /// Fails if another instance is serving the socket, so a second TUI or
+    /// daemon doesn't fight the first over hotplug events.
+    pub fn ensure_single_instance() -> io::Result<()> {
+        let Ok(path) = Self::path() else {
+            return Ok(());
+        };
+        let Ok(stream) = UnixStream::connect(&path) else {
+            return Ok(());
+        };
+        let who = match Self::instance_pid(stream) {
+            Some(pid) => format!("Hyprmonitor is already running (pid {})", pid),
+            None => String::from("Hyprmonitor is already running"),
+        };
+        Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!(
+                "{}; quit it first, or send it commands through {}",
+                who,
+                path.display()
+            ),
+        ))
+    }
+
+    fn instance_pid(stream: UnixStream) -> Option<u32> {
+        Self::exchange(stream, &json!({ "command": "instance" })).ok()?["pid"]
+            .as_u64()
+            .and_then(|pid| u32::try_from(pid).ok())
+    }
+
+    /// Sends `request` to the running instance and returns its response.
+    pub fn send(request: &serde_json::Value) -> io::Result<serde_json::Value> {
+        let path = Self::path()?;
+        let stream = UnixStream::connect(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Hyprmonitor isn't running ({}: {})", path.display(), e),
+            )
+        })?;
+        Self::exchange(stream, request)
+    }
+
+    fn exchange(stream: UnixStream, request: &serde_json::Value) -> io::Result<serde_json::Value> {
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        // In one write, so a server answering on its first read has the
+        // whole line.
+        (&stream).write_all(format!("{}\n", request).as_bytes())?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// What the last requests asked of the serving loop, if anything.
+    pub fn take_lifecycle(&self) -> Option<Lifecycle> {
+        self.lifecycle.take()
+    }
+
+    /// Serves every connection that is waiting, without blocking. Returns
+    /// whether there were any.
+    pub fn poll(&self, app: &mut App) -> bool {
+        let mut served = false;
+        while let Ok((stream, _)) = self.listener.accept() {
+            // A misbehaving client only loses its own response.
+            let _ = self.serve(stream, app);
+            served = true;
+        }
+        served
+    }
+
+    fn serve(&self, stream: UnixStream, app: &mut App) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => self.handle(request, app),
+            Err(e) => json!({ "ok": false, "error": e.to_string() }),
+        };
+        writeln!(&stream, "{}", response)
+    }
+
+    fn handle(&self, request: Request, app: &mut App) -> serde_json::Value {
+        let result = match request {
+            Request::ApplyProfile { name } => Profile::load(&name)
+                .map_err(|e| e.to_string())
+                .and_then(|profile| app.apply_profile(&profile)),
+            Request::ToggleDpms { monitor } => {
+                let idx = match &monitor {
+                    Some(name) => app.find_monitor(name),
+                    None => app.monitors.iter().position(|m| m.focused),
+                };
+                match idx {
+                    Some(idx) => app.set_dpms(idx, !app.monitors[idx].dpms_on),
+                    None => Err(format!(
+                        "No such monitor: {}",
+                        monitor.as_deref().unwrap_or("focused")
+                    )),
+                }
+            }
+            Request::Instance => return json!({ "ok": true, "pid": process::id() }),
+            Request::Status => {
+                if let Err(e) = app.refresh_monitors() {
+                    return json!({ "ok": false, "error": e.to_string() });
+                }
+                let profile = profile::last_applied();
+                let status = Status::capture(&app.monitors, &app.configs, profile.clone());
+                return json!({
+                    "ok": true,
+                    "pid": process::id(),
+                    "profile": profile,
+                    "status": status.plain(),
+                });
+            }
+            Request::Reload => {
+                let result = app.reload_config();
+                if result.is_ok() {
+                    self.lifecycle.set(Some(Lifecycle::Reload));
+                }
+                result
+            }
+            Request::Stop => {
+                self.lifecycle.set(Some(Lifecycle::Stop));
+                Ok(String::from("Stopping"))
+            }
+            Request::State => {
+                if let Err(e) = app.refresh_monitors() {
+                    return json!({ "ok": false, "error": e.to_string() });
+                }
+                return json!({ "ok": true, "monitors": Self::state(app) });
+            }
+        };
+
+        match result {
+            Ok(message) => json!({ "ok": true, "message": message }),
+            Err(error) => json!({ "ok": false, "error": error }),
+        }
+    }
+
+    fn state(app: &App) -> Vec<serde_json::Value> {
+        app.monitors
+            .iter()
+            .zip(&app.configs)
+            .map(|(monitor, config)| {
+                json!({
+                    "name": monitor.name,
+                    "description": monitor.description,
+                    "active": monitor.active,
+                    "focused": monitor.focused,
+                    "resolution": config.resolution,
+                    "refresh_rate": config.refresh_rate,
+                    "scale": config.scale_as_float(),
+                    "x": monitor.x,
+                    "y": monitor.y,
+                    "dpms": monitor.dpms_on,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::Local;
+use std::{
+    io, thread,
+    time::{Duration, Instant},
+};
+
+use crate::app::App;
+use crate::blanking::BlankingSettings;
+use crate::commands;
+use crate::control::{ControlServer, Lifecycle};
+use crate::dbus::DbusService;
+use crate::notify;
+use crate::power::{self, PowerSettings};
+use crate::profile::Profile;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How often the control socket is checked for requests.
+const CONTROL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Long-running background mode: applies an optional profile on start and
+/// then reacts to environment changes until killed or stopped through the
+/// control socket.
+pub struct Daemon {
+    app: App,
+    profile: Option<Profile>,
+    on_ac: Option<bool>,
+    /// Whether the blanking schedule turned the monitors off.
+    blanked: bool,
+    /// Last known pointer position and when it was last seen to move.
+    cursor: Option<(i64, i64)>,
+    last_input: Instant,
+    control: Option<ControlServer>,
+    dbus: Option<DbusService>,
+}
+
+impl Daemon {
+    pub fn new(profile: Option<String>) -> io::Result<Self> {
+        ControlServer::ensure_single_instance()?;
+        let profile = profile.map(|name| Profile::load(&name)).transpose()?;
+        Ok(Self {
+            app: App::new()?,
+            profile,
+            on_ac: None,
+            blanked: false,
+            cursor: None,
+            last_input: Instant::now(),
+            control: None,
+            dbus: None,
+        })
+    }
+
+    pub fn run(mut self) -> io::Result<()> {
+        if let Some(profile) = self.profile.clone() {
+            let result = self.app.apply_profile(&profile);
+            self.report(result);
+        }
+
+        match ControlServer::bind() {
+            Ok(control) => self.control = Some(control),
+            Err(e) => self.report(Err(format!("Control socket disabled: {}", e))),
+        }
+        match DbusService::connect(&self.app) {
+            Ok(dbus) => self.dbus = Some(dbus),
+            Err(e) => self.report(Err(format!("D-Bus service disabled: {}", e))),
+        }
+
+        let mut last_tick: Option<Instant> = None;
+        loop {
+            if last_tick.is_none_or(|at| at.elapsed() >= POLL_INTERVAL) {
+                self.tick();
+                last_tick = Some(Instant::now());
+            }
+            if let Some(control) = &self.control {
+                control.poll(&mut self.app);
+                match control.take_lifecycle() {
+                    Some(Lifecycle::Stop) => return Ok(()),
+                    Some(Lifecycle::Reload) => self.reload(),
+                    None => {}
+                }
+            }
+            if let Some(dbus) = &mut self.dbus {
+                dbus.poll(&mut self.app);
+            }
+            thread::sleep(CONTROL_INTERVAL);
+        }
+    }
+
+    fn tick(&mut self) {
+        self.check_power_source();
+        self.check_blanking();
+    }
+
+    /// Re-reads the start profile and evaluates the rules again as if
+    /// nothing had been seen yet. The app config was reloaded already.
+    fn reload(&mut self) {
+        if let Some(name) = self.profile.as_ref().map(|profile| profile.name.clone()) {
+            match Profile::load(&name) {
+                Ok(profile) => self.profile = Some(profile),
+                Err(e) => self.report(Err(e.to_string())),
+            }
+        }
+        self.on_ac = None;
+        self.tick();
+    }
+
+    /// Power settings from the active profile, falling back to the app config.
+    fn power_settings(&self) -> &PowerSettings {
+        self.profile
+            .as_ref()
+            .and_then(|profile| profile.power.as_ref())
+            .unwrap_or(&self.app.config.power)
+    }
+
+    /// Blanking schedule from the active profile, falling back to the app
+    /// config.
+    fn blanking_settings(&self) -> &BlankingSettings {
+        self.profile
+            .as_ref()
+            .and_then(|profile| profile.blanking.as_ref())
+            .unwrap_or(&self.app.config.blanking)
+    }
+
+    /// Turns the monitors off inside the blanking window once the pointer
+    /// has been still long enough, and back on when it moves or the window
+    /// ends.
+    fn check_blanking(&mut self) {
+        let settings = self.blanking_settings();
+        if !settings.enabled && !self.blanked {
+            return;
+        }
+        let in_window = settings.enabled && settings.in_window(Local::now().time());
+        let idle_for = Duration::from_secs(u64::from(settings.unless_input_within) * 60);
+
+        let cursor = commands::fetch_cursor_position().ok();
+        let moved = cursor.is_some() && self.cursor.is_some() && cursor != self.cursor;
+        self.cursor = cursor;
+        if moved {
+            self.last_input = Instant::now();
+        }
+
+        if self.blanked && (moved || !in_window) {
+            self.blanked = false;
+            let result = self.app.wake_all();
+            self.report(result);
+        } else if !self.blanked && in_window && self.last_input.elapsed() >= idle_for {
+            let result = self.app.blank_all();
+            self.blanked = result.is_ok();
+            self.report(result);
+        }
+    }
+
+    fn check_power_source(&mut self) {
+        if !self.power_settings().enabled {
+            return;
+        }
+        let on_ac = power::on_ac_power();
+        if on_ac.is_none() || on_ac == self.on_ac {
+            return;
+        }
+        self.on_ac = on_ac;
+
+        if let Err(e) = self.app.refresh_monitors() {
+            self.report(Err(format!("Error refreshing monitors: {}", e)));
+            return;
+        }
+        let Some(idx) = self
+            .app
+            .monitors
+            .iter()
+            .position(|m| m.is_internal() && m.active)
+        else {
+            return;
+        };
+
+        let config = &self.app.configs[idx];
+        let rate = self.app.monitors[idx]
+            .modes
+            .get(&config.resolution)
+            .and_then(|rates| {
+                let values: Vec<f64> = rates.iter().map(|rate| rate.value).collect();
+                self.power_settings()
+                    .pick_rate(&values, on_ac == Some(true))
+            });
+        let Some(rate) = rate else {
+            return;
+        };
+        if (rate - config.refresh_rate).abs() < 0.01 {
+            return;
+        }
+
+        let result = self.app.set_refresh_rate(idx, rate);
+        self.report(result);
+    }
+
+    fn report(&self, result: Result<String, String>) {
+        match &result {
+            Ok(message) => println!("{}", message),
+            Err(message) => eprintln!("{}", message),
+        }
+        notify::send(&self.app.config.notifications, &result);
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::{
+    mpsc::{self, Receiver, Sender},
+    Mutex,
+};
+use zbus::{blocking::Connection, fdo, interface, SignalContext};
+
+use crate::app::App;
+use crate::profile::Profile;
+
+const BUS_NAME: &str = "org.hyprmonitor";
+const OBJECT_PATH: &str = "/org/hyprmonitor";
+
+/// Name, active, resolution, refresh rate and scale of one monitor.
+type MonitorSummary = (String, bool, String, f64, f64);
+
+/// A method call forwarded from the bus thread to the thread owning the app.
+enum Call {
+    ApplyProfile(String, Sender<Result<String, String>>),
+    ListMonitors(Sender<Vec<MonitorSummary>>),
+}
+
+struct Interface {
+    calls: Mutex<Sender<Call>>,
+}
+
+impl Interface {
+    fn forward<T>(&self, call: impl FnOnce(Sender<T>) -> Call) -> fdo::Result<T> {
+        let (reply, response) = mpsc::channel();
+        self.calls
+            .lock()
+            .map_err(|_| fdo::Error::Failed(String::from("Service unavailable")))?
+            .send(call(reply))
+            .map_err(|_| fdo::Error::Failed(String::from("Service unavailable")))?;
+        response
+            .recv()
+            .map_err(|_| fdo::Error::Failed(String::from("Service unavailable")))
+    }
+}
+
+#[interface(name = "org.hyprmonitor")]
+impl Interface {
+    fn apply_profile(&self, name: String) -> fdo::Result<String> {
+        self.forward(|reply| Call::ApplyProfile(name, reply))?
+            .map_err(fdo::Error::Failed)
+    }
+
+    fn list_monitors(&self) -> fdo::Result<Vec<MonitorSummary>> {
+        self.forward(Call::ListMonitors)
+    }
+
+    /// Emitted after a monitor rule was applied.
+    #[zbus(signal)]
+    async fn layout_changed(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+}
+
+/// `org.hyprmonitor` on the session bus. Calls are answered from `poll`, so
+/// they run on the same thread and `App` as everything else.
+pub struct DbusService {
+    connection: Connection,
+    calls: Receiver<Call>,
+    layout_generation: u64,
+}
+
+impl DbusService {
+    pub fn connect(app: &App) -> zbus::Result<Self> {
+        let (sender, calls) = mpsc::channel();
+        let connection = zbus::blocking::connection::Builder::session()?
+            .name(BUS_NAME)?
+            .serve_at(
+                OBJECT_PATH,
+                Interface {
+                    calls: Mutex::new(sender),
+                },
+            )?
+            .build()?;
+
+        Ok(Self {
+            connection,
+            calls,
+            layout_generation: app.layout_generation,
+        })
+    }
+
+    /// Answers pending calls and emits `LayoutChanged` if the app changed a
+    /// monitor rule since the last poll. Returns whether there were calls.
+    pub fn poll(&mut self, app: &mut App) -> bool {
+        let mut answered = false;
+        while let Ok(call) = self.calls.try_recv() {
+            answered = true;
+            match call {
+                Call::ApplyProfile(name, reply) => {
+                    let result = Profile::load(&name)
+                        .map_err(|e| e.to_string())
+                        .and_then(|profile| app.apply_profile(&profile));
+                    let _ = reply.send(result);
+                }
+                Call::ListMonitors(reply) => {
+                    let _ = app.refresh_monitors();
+                    let monitors = app
+                        .monitors
+                        .iter()
+                        .zip(&app.configs)
+                        .map(|(monitor, config)| {
+                            (
+                                monitor.name.clone(),
+                                monitor.active,
+                                config.resolution.clone(),
+                                config.refresh_rate,
+                                config.scale_as_float(),
+                            )
+                        })
+                        .collect();
+                    let _ = reply.send(monitors);
+                }
+            }
+        }
+
+        if app.layout_generation != self.layout_generation {
+            self.layout_generation = app.layout_generation;
+            if let Ok(ctxt) = SignalContext::new(self.connection.inner(), OBJECT_PATH) {
+                let _ = zbus::block_on(Interface::layout_changed(&ctxt));
+            }
+        }
+        answered
+    }
+}
@@ -0,0 +1,215 @@
+use std::{env, fmt::Write, fs, io, path::Path};
+
+use crate::app::App;
+use crate::commands;
+use crate::config::Config;
+use crate::hyprconf;
+use crate::profile::{self, Profile};
+use crate::xdg;
+
+/// Environment variables worth knowing about when output handling goes
+/// wrong.
+const SESSION_VARS: [&str; 4] = [
+    "XDG_SESSION_TYPE",
+    "XDG_CURRENT_DESKTOP",
+    "WAYLAND_DISPLAY",
+    "HYPRLAND_INSTANCE_SIGNATURE",
+];
+
+/// What the report replaces each private value with.
+const REDACTED: &str = "<redacted>";
+
+/// A Markdown report for bug reports: versions, how commands reach
+/// Hyprland, where files are looked for, the raw and parsed monitors, and
+/// the config files, with serial
+/// numbers, the hostname, the user name and the home directory taken out.
+pub fn report() -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "# hypr-tui doctor\n");
+    let _ = writeln!(report, "hypr-tui {}", env!("CARGO_PKG_VERSION"));
+
+    let _ = writeln!(report, "\n## Hyprland\n");
+    match commands::fetch_version() {
+        Ok(version) => code_block(&mut report, "json", &pretty(&version)),
+        Err(e) => {
+            let _ = writeln!(report, "hyprctl version failed: {}", e);
+        }
+    }
+
+    let _ = writeln!(report, "\n## Backend\n");
+    let hyprctl = env::var_os("PATH")
+        .and_then(|path| {
+            env::split_paths(&path)
+                .map(|dir| dir.join("hyprctl"))
+                .find(|candidate| candidate.is_file())
+        })
+        .map_or_else(
+            || String::from("not found"),
+            |path| path.display().to_string(),
+        );
+    let _ = writeln!(report, "- commands: hyprctl --batch ({})", hyprctl);
+    for var in SESSION_VARS {
+        let value = env::var(var).unwrap_or_else(|_| String::from("unset"));
+        let _ = writeln!(report, "- {}: {}", var, value);
+    }
+
+    let _ = writeln!(report, "\n## Paths\n");
+    let paths = [
+        ("config home", xdg::config_home()),
+        ("state home", xdg::state_home()),
+        ("config.toml", Config::path()),
+        ("profiles", Profile::dir()),
+        ("monitors.conf", hyprconf::monitors_conf_path()),
+        ("hyprland.conf", hyprconf::hyprland_conf_path()),
+    ];
+    for (what, path) in paths {
+        match path {
+            Ok(path) => {
+                let _ = writeln!(report, "- {}: {}", what, path.display());
+            }
+            Err(e) => {
+                let _ = writeln!(report, "- {}: {}", what, e);
+            }
+        }
+    }
+
+    let _ = writeln!(report, "\n## Monitors (hyprctl monitors all -j)\n");
+    let raw = commands::fetch_monitors();
+    match &raw {
+        Ok(monitors) => code_block(&mut report, "json", &pretty(&serde_json::json!(monitors))),
+        Err(e) => {
+            let _ = writeln!(report, "hyprctl monitors failed: {}", e);
+        }
+    }
+
+    let _ = writeln!(report, "\n## Parsed state\n");
+    match App::new() {
+        Ok(app) => {
+            let lines: Vec<String> = app
+                .monitors
+                .iter()
+                .zip(&app.configs)
+                .map(|(monitor, config)| {
+                    format!(
+                        "{} active={} dpms={} mirror_of={:?}\n  {}",
+                        monitor.name,
+                        monitor.active,
+                        monitor.dpms_on,
+                        monitor.mirror_of,
+                        monitor.rule(config)
+                    )
+                })
+                .collect();
+            code_block(&mut report, "", &lines.join("\n"));
+        }
+        Err(e) => {
+            let _ = writeln!(report, "Reading monitors failed: {}", e);
+        }
+    }
+
+    let _ = writeln!(report, "\n## Files");
+    if let Ok(path) = Config::path() {
+        file_section(&mut report, &path, "toml", |contents| contents.to_string());
+    }
+    if let Ok(path) = hyprconf::monitors_conf_path() {
+        file_section(&mut report, &path, "ini", |contents| contents.to_string());
+    }
+    // The rest of hyprland.conf is personal and rarely matters here.
+    if let Ok(path) = hyprconf::hyprland_conf_path() {
+        file_section(&mut report, &path, "ini", |contents| {
+            contents
+                .lines()
+                .filter(|line| {
+                    let key = line.split('=').next().unwrap_or_default().trim();
+                    key == "monitor" || key == "source"
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+    }
+
+    let serials: Vec<String> = raw
+        .iter()
+        .flatten()
+        .filter_map(|monitor| monitor["serial"].as_str())
+        .map(String::from)
+        .collect();
+    redact(&report, &private_values(serials))
+}
+
+/// Serial numbers, longest first so one inside another doesn't split it,
+/// then the home directory, hostname and user name.
+fn private_values(mut serials: Vec<String>) -> Vec<(String, &'static str)> {
+    serials.sort_by_key(|serial| std::cmp::Reverse(serial.len()));
+    let mut values: Vec<(String, &'static str)> = serials
+        .into_iter()
+        .map(|serial| (serial, REDACTED))
+        .collect();
+    if let Some(home) = env::var("HOME").ok().filter(|home| home.len() > 1) {
+        values.push((home, "~"));
+    }
+    if let Ok(host) = profile::hostname() {
+        values.push((host, "<hostname>"));
+    }
+    if let Ok(user) = env::var("USER") {
+        values.push((user, "<user>"));
+    }
+    values
+}
+
+/// `text` with every private value replaced by its placeholder. Values too
+/// short to be told apart from ordinary words are left alone.
+fn redact(text: &str, values: &[(String, &str)]) -> String {
+    values
+        .iter()
+        .filter(|(value, _)| value.len() >= 3)
+        .fold(text.to_string(), |text, (value, placeholder)| {
+            text.replace(value.as_str(), placeholder)
+        })
+}
+
+fn pretty(value: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn code_block(report: &mut String, language: &str, contents: &str) {
+    let _ = writeln!(report, "```{}\n{}\n```", language, contents.trim_end());
+}
+
+fn file_section(
+    report: &mut String,
+    path: &Path,
+    language: &str,
+    excerpt: impl Fn(&str) -> String,
+) {
+    let _ = writeln!(report, "\n### {}\n", path.display());
+    match fs::read_to_string(path) {
+        Ok(contents) => code_block(report, language, &excerpt(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let _ = writeln!(report, "(missing)");
+        }
+        Err(e) => {
+            let _ = writeln!(report, "(unreadable: {})", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_serials_paths_and_names() {
+        let values = [
+            (String::from("ABC123456"), REDACTED),
+            (String::from("/home/alex"), "~"),
+            (String::from("desk"), "<hostname>"),
+            (String::from("al"), "<user>"),
+        ];
+        let text = "\"serial\": \"ABC123456\",\n\"description\": \"Dell U2720Q ABC123456\"\n### /home/alex/.config/hypr/monitors-desk.conf\nsource = /home/alex/x.conf";
+        assert_eq!(
+            redact(text, &values),
+            "\"serial\": \"<redacted>\",\n\"description\": \"Dell U2720Q <redacted>\"\n### ~/.config/hypr/monitors-<hostname>.conf\nsource = ~/x.conf"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    env,
+    io::{self, BufRead, BufReader},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// Events that concern outputs rather than windows or workspaces, including
+/// config reloads, which apply the `monitor=` lines again.
+const MONITOR_EVENTS: [&str; 7] = [
+    "monitoradded",
+    "monitoraddedv2",
+    "monitorremoved",
+    "monitorremovedv2",
+    "focusedmon",
+    "focusedmonv2",
+    "configreloaded",
+];
+
+/// One line from Hyprland's event socket, `NAME>>DATA`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub name: String,
+    pub data: String,
+}
+
+impl Event {
+    pub fn parse(line: &str) -> Option<Self> {
+        let (name, data) = line.split_once(">>")?;
+        Some(Self {
+            name: name.to_string(),
+            data: data.to_string(),
+        })
+    }
+
+    pub fn is_monitor_event(&self) -> bool {
+        MONITOR_EVENTS.contains(&self.name.as_str())
+    }
+
+    /// The output a version 1 monitor event is about. The v2 variants
+    /// repeat the same event with other fields and are left out.
+    pub fn monitor(&self) -> Option<&str> {
+        match self.name.as_str() {
+            "monitoradded" | "monitorremoved" => Some(&self.data),
+            "focusedmon" => self.data.split(',').next(),
+            _ => None,
+        }
+    }
+}
+
+/// Hyprland's event socket (`.socket2.sock`) for the running instance.
+fn socket_path() -> io::Result<PathBuf> {
+    let signature = env::var_os("HYPRLAND_INSTANCE_SIGNATURE").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "HYPRLAND_INSTANCE_SIGNATURE is not set; is Hyprland running?",
+        )
+    })?;
+
+    // Hyprland 0.40 moved its sockets from /tmp/hypr to the runtime dir.
+    let mut candidates = Vec::new();
+    if let Some(runtime_dir) = env::var_os("XDG_RUNTIME_DIR") {
+        candidates.push(PathBuf::from(runtime_dir).join("hypr"));
+    }
+    candidates.push(PathBuf::from("/tmp/hypr"));
+
+    candidates
+        .into_iter()
+        .map(|dir| dir.join(&signature).join(".socket2.sock"))
+        .find(|path| path.exists())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Hyprland event socket not found"))
+}
+
+/// Events from Hyprland as they happen. Blocks until the next one arrives.
+pub fn subscribe() -> io::Result<impl Iterator<Item = io::Result<Event>>> {
+    let stream = UnixStream::connect(socket_path()?)?;
+    Ok(BufReader::new(stream)
+        .lines()
+        .filter_map(|line| line.map(|line| Event::parse(&line)).transpose()))
+}
+
+/// Monitor events passed to a channel by a background thread, for event
+/// loops that can't block on the socket.
+pub fn monitor_feed() -> io::Result<Receiver<Event>> {
+    let events = subscribe()?;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for event in events.map_while(Result::ok) {
+            if event.is_monitor_event() && sender.send(event).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_monitor_of_v1_events() {
+        let event = |line| Event::parse(line).expect("event parses");
+        assert_eq!(event("monitoradded>>DP-1").monitor(), Some("DP-1"));
+        assert_eq!(event("focusedmon>>HDMI-A-1,3").monitor(), Some("HDMI-A-1"));
+        assert_eq!(event("monitoraddedv2>>1,DP-1,Dell U2720Q").monitor(), None);
+        assert_eq!(event("workspace>>3").monitor(), None);
+        assert!(event("configreloaded>>").is_monitor_event());
+    }
+}
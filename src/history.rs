@@ -0,0 +1,103 @@
+use chrono::{DateTime, Local};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::commands::HyprCommand;
+use crate::xdg;
+
+const HISTORY_PATH: &str = "hyprmonitor/commands.log";
+const HISTORY_LIMIT: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub command: HyprCommand,
+    pub timestamp: DateTime<Local>,
+    pub success: bool,
+}
+
+/// Commands issued to Hyprland during this session, optionally mirrored to a
+/// tab-separated log (with arguments as a JSON array) so they survive restarts.
+pub struct CommandHistory {
+    entries: Vec<HistoryEntry>,
+    persist_path: Option<PathBuf>,
+}
+
+impl CommandHistory {
+    pub fn new(persist: bool) -> Self {
+        let persist_path = if persist {
+            xdg::state_path(HISTORY_PATH).ok()
+        } else {
+            None
+        };
+
+        let mut entries = persist_path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| contents.lines().filter_map(Self::parse_line).collect())
+            .unwrap_or_else(Vec::new);
+        Self::truncate(&mut entries);
+
+        Self {
+            entries,
+            persist_path,
+        }
+    }
+
+    /// Entries from oldest to newest.
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn record(&mut self, command: &HyprCommand, success: bool) {
+        let entry = HistoryEntry {
+            command: command.clone(),
+            timestamp: Local::now(),
+            success,
+        };
+
+        if let Some(path) = &self.persist_path {
+            // History is a convenience; failing to persist it must not block
+            // the command that was just run.
+            let _ = Self::append_line(path, &entry);
+        }
+
+        self.entries.push(entry);
+        Self::truncate(&mut self.entries);
+    }
+
+    fn truncate(entries: &mut Vec<HistoryEntry>) {
+        if entries.len() > HISTORY_LIMIT {
+            entries.drain(..entries.len() - HISTORY_LIMIT);
+        }
+    }
+
+    fn append_line(path: &PathBuf, entry: &HistoryEntry) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}",
+            entry.timestamp.to_rfc3339(),
+            if entry.success { "ok" } else { "failed" },
+            serde_json::to_string(entry.command.args())?
+        )
+    }
+
+    fn parse_line(line: &str) -> Option<HistoryEntry> {
+        let mut parts = line.splitn(3, '\t');
+        let timestamp = DateTime::parse_from_rfc3339(parts.next()?).ok()?;
+        let success = parts.next()? == "ok";
+        let args: Vec<String> = serde_json::from_str(parts.next()?).ok()?;
+        let command = HyprCommand::new(args);
+        Some(HistoryEntry {
+            command,
+            timestamp: timestamp.with_timezone(&Local),
+            success,
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use serde::Deserialize;
+use std::{
+    process::{Command, Stdio},
+    thread,
+};
+
+/// User commands run after layout changes, e.g. to nudge bars and wallpaper
+/// daemons that don't notice new output geometry on their own.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct HookSettings {
+    pub on_apply: Vec<String>,
+}
+
+/// Starts each hook through the user's shell without waiting for it, so a
+/// slow hook can't stall the UI. A background thread reaps each child.
+pub fn run(hooks: &[String]) {
+    for hook in hooks {
+        let child = Command::new("sh")
+            .arg("-c")
+            .arg(hook)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        if let Ok(mut child) = child {
+            thread::spawn(move || child.wait());
+        }
+    }
+}
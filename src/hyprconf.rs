@@ -0,0 +1,521 @@
+use chrono::Local;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::modeline::Modeline;
+use crate::monitor::{Monitor, MonitorConfig};
+use crate::monitorv2;
+use crate::xdg;
+
+/// Under the XDG config directory, where Hyprland looks too.
+const MONITORS_CONF_PATH: &str = "hypr/monitors.conf";
+const HYPRLAND_CONF_PATH: &str = "hypr/hyprland.conf";
+const HOST_MONITORS_CONF_PATH: &str = "hypr/monitors-{host}.conf";
+
+/// Location of the monitor configuration written by "Save to File".
+pub fn monitors_conf_path() -> io::Result<PathBuf> {
+    xdg::config_path(MONITORS_CONF_PATH)
+}
+
+pub fn hyprland_conf_path() -> io::Result<PathBuf> {
+    xdg::config_path(HYPRLAND_CONF_PATH)
+}
+
+/// Per-host monitor configuration, sourced from `monitors.conf` when
+/// layouts are saved per hostname.
+pub fn host_monitors_conf_path(host: &str) -> io::Result<PathBuf> {
+    xdg::config_path(HOST_MONITORS_CONF_PATH.replace("{host}", host))
+}
+
+/// Contents of the saved monitor configuration, empty if nothing was saved
+/// yet. Files pulled in with `source =` lines are appended, so per-host
+/// layouts read back like a single file, and `monitorv2` blocks read back as
+/// the `monitor=` lines they stand for.
+pub fn read_monitors_conf() -> io::Result<String> {
+    let mut contents = read_optional(&monitors_conf_path()?)?;
+    let sourced: Vec<String> = contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "source").then(|| value.trim().to_string())
+        })
+        .collect();
+    for path in sourced {
+        if let Ok(path) = shellexpand::full(&path) {
+            contents.push('\n');
+            contents.push_str(&read_optional(Path::new(path.as_ref()))?);
+        }
+    }
+    Ok(monitorv2::to_legacy(&contents).contents)
+}
+
+/// Contents of `hyprland.conf`, empty if there is none, with `monitorv2`
+/// blocks read back as `monitor=` lines.
+pub fn read_hyprland_conf() -> io::Result<String> {
+    Ok(monitorv2::to_legacy(&read_optional(&hyprland_conf_path()?)?).contents)
+}
+
+/// The `monitor=` rules Hyprland applies at startup from `hyprland.conf`
+/// and the saved file, read in that order: each output's effective lines,
+/// with the outputs that stay on first.
+pub fn saved_rules(hyprland_conf: &str, monitors_conf: &str) -> Vec<String> {
+    let contents = format!("{}\n{}", hyprland_conf, monitors_conf);
+    let rules: Vec<String> = monitor_names(&contents)
+        .iter()
+        .flat_map(|name| effective_lines(&contents, name))
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect();
+    let (disabled, enabled): (Vec<String>, Vec<String>) = rules
+        .into_iter()
+        .partition(|rule| rule.split(',').nth(1) == Some("disable"));
+    enabled.into_iter().chain(disabled).collect()
+}
+
+/// The `monitors.conf` written in per-host mode: it only sources the layout
+/// of the machine it is on.
+pub fn host_source_stub(host_path: &Path) -> String {
+    format!(
+        "# Generated by hypr-tui: layouts are saved per hostname\nsource = {}\n",
+        host_path.display()
+    )
+}
+
+fn read_optional(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// The values of the `monitor=` lines "Save to File" writes for `monitor`;
+/// none for a monitor left out of the saved file, or one that is off and not
+/// saved as disabled.
+pub fn monitor_conf_lines(monitor: &Monitor, config: &MonitorConfig) -> Vec<String> {
+    if !config.persist {
+        return Vec::new();
+    }
+    if config.save_disabled {
+        return vec![format!("{},disable", monitor.name)];
+    }
+    if !monitor.active {
+        return Vec::new();
+    }
+
+    // Pin the live position unless one was set explicitly, so the layout
+    // comes back exactly as it is now.
+    let mut config = config.clone();
+    config.position = config.position.or(Some((monitor.x, monitor.y)));
+    let mut rule = monitor.rule(&config);
+    if let Some(source) = &monitor.mirror_of {
+        rule.push_str(&format!(",mirror,{}", source));
+    }
+    let mut lines = vec![rule];
+    if config.reserved != [0; 4] {
+        lines.push(config.reserved_rule(&monitor.name));
+    }
+    lines
+}
+
+/// Values of all `monitor=` lines for `name`, in file order.
+pub fn monitor_lines<'a>(contents: &'a str, name: &str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "monitor").then(|| value.trim())
+        })
+        .filter(|value| value.split(',').next().map(str::trim) == Some(name))
+        .collect()
+}
+
+/// Every output named by a `monitor=` line, once each, in file order.
+pub fn monitor_names(contents: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "monitor" {
+            continue;
+        }
+        let name = value.split(',').next().unwrap_or_default().trim();
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// A `monitor=` mode line and where it was read from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleLine {
+    pub file: &'static str,
+    /// 1-based.
+    pub line: usize,
+    pub value: String,
+}
+
+/// Several different mode lines for one output. Hyprland reads them in
+/// order and the last one wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub name: String,
+    pub lines: Vec<RuleLine>,
+}
+
+impl Conflict {
+    pub fn winner(&self) -> &RuleLine {
+        &self.lines[self.lines.len() - 1]
+    }
+
+    /// Whether any of the lines are in `hyprland.conf` itself.
+    pub fn in_hyprland_conf(&self) -> bool {
+        self.lines.iter().any(|line| line.file == "hyprland.conf")
+    }
+}
+
+/// Every `monitor=` line of `hyprland.conf` and the saved monitor
+/// configuration in the order Hyprland reads them: the saved file where
+/// hyprland.conf sources it, or after it if the source line isn't found.
+pub fn ordered_rule_lines(hyprland_conf: &str, monitors_conf: &str) -> Vec<RuleLine> {
+    let rule_lines = |file: &'static str, contents: &str| -> Vec<RuleLine> {
+        contents
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "monitor").then(|| RuleLine {
+                    file,
+                    line: i + 1,
+                    value: value.trim().to_string(),
+                })
+            })
+            .collect()
+    };
+
+    let source_line = monitors_conf_source_line(hyprland_conf);
+    let (before, after): (Vec<_>, Vec<_>) = rule_lines("hyprland.conf", hyprland_conf)
+        .into_iter()
+        .partition(|rule| source_line.is_none_or(|source| rule.line < source));
+    before
+        .into_iter()
+        .chain(rule_lines("monitors.conf", monitors_conf))
+        .chain(after)
+        .collect()
+}
+
+impl RuleLine {
+    /// The output the line is for: a connector name, `desc:...`, or empty
+    /// for the fallback rule.
+    pub fn name(&self) -> &str {
+        self.value.split(',').next().unwrap_or_default().trim()
+    }
+
+    pub fn is_reserved(&self) -> bool {
+        is_reserved_line(&self.value)
+    }
+}
+
+/// Mode lines for the same output that disagree, across `hyprland.conf` and
+/// the saved monitor configuration.
+pub fn find_conflicts(hyprland_conf: &str, monitors_conf: &str) -> Vec<Conflict> {
+    let ordered = ordered_rule_lines(hyprland_conf, monitors_conf)
+        .into_iter()
+        .filter(|rule| !rule.is_reserved())
+        .map(|rule| (rule.name().to_string(), rule));
+
+    let mut conflicts: Vec<Conflict> = Vec::new();
+    for (name, rule) in ordered {
+        match conflicts.iter_mut().find(|c| c.name == name) {
+            Some(conflict) => conflict.lines.push(rule),
+            None => conflicts.push(Conflict {
+                name,
+                lines: vec![rule],
+            }),
+        }
+    }
+    conflicts.retain(|conflict| {
+        conflict
+            .lines
+            .iter()
+            .any(|rule| rule.value != conflict.lines[0].value)
+    });
+    conflicts
+}
+
+/// The 1-based line of `hyprland_conf` that sources `monitors.conf`.
+fn monitors_conf_source_line(hyprland_conf: &str) -> Option<usize> {
+    hyprland_conf
+        .lines()
+        .position(|line| {
+            line.split_once('=').is_some_and(|(key, value)| {
+                key.trim() == "source"
+                    && Path::new(value.trim()).file_name() == Some("monitors.conf".as_ref())
+            })
+        })
+        .map(|i| i + 1)
+}
+
+/// Whether Hyprland reads the saved monitor configuration at all.
+pub fn sources_monitors_conf(hyprland_conf: &str) -> bool {
+    monitors_conf_source_line(hyprland_conf).is_some()
+}
+
+/// `hyprland_conf` with a line sourcing `monitors_conf` added at the end,
+/// where it overrides earlier `monitor=` lines.
+pub fn with_source_line(hyprland_conf: &str, monitors_conf: &Path) -> String {
+    let mut contents = hyprland_conf.to_string();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&format!(
+        "\n# Monitor layout saved by hypr-tui\nsource = {}\n",
+        monitors_conf.display()
+    ));
+    contents
+}
+
+/// Makes `hyprland.conf` source the saved monitor configuration, unless it
+/// already does. A timestamped backup is written next to it first.
+pub fn add_source_line() -> io::Result<PathBuf> {
+    let path = hyprland_conf_path()?;
+    let contents = read_optional(&path)?;
+    if sources_monitors_conf(&contents) {
+        return Ok(path);
+    }
+    if !contents.is_empty() {
+        backup(&path, &contents)?;
+    }
+    fs::write(&path, with_source_line(&contents, &monitors_conf_path()?))?;
+    Ok(path)
+}
+
+/// Writes `contents` to a backup of `path` named after the current time,
+/// such as `hyprland.conf.20261015-093000.bak`, so earlier backups survive.
+pub fn backup(path: &Path, contents: &str) -> io::Result<PathBuf> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let backup = path.with_file_name(format!(
+        "{}.{}.bak",
+        name,
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::write(&backup, contents)?;
+    Ok(backup)
+}
+
+/// `contents` with the `monitor=` lines for `names` commented out, and how
+/// many lines that was.
+pub fn comment_out_monitors(contents: &str, names: &[String]) -> (String, usize) {
+    let mut count = 0;
+    let mut result: String = contents
+        .lines()
+        .map(|line| {
+            let name = line.split_once('=').and_then(|(key, value)| {
+                (key.trim() == "monitor")
+                    .then(|| value.split(',').next().unwrap_or_default().trim())
+            });
+            if name.is_some_and(|name| names.iter().any(|n| n == name)) {
+                count += 1;
+                format!("# {} # superseded by monitors.conf (hypr-tui)\n", line)
+            } else {
+                format!("{}\n", line)
+            }
+        })
+        .collect();
+    if !contents.ends_with('\n') {
+        result.pop();
+    }
+    (result, count)
+}
+
+/// The lines that take effect for `name`: its last mode line and its last
+/// reserved-area line, dropping earlier ones they override.
+pub fn effective_lines<'a>(contents: &'a str, name: &str) -> Vec<&'a str> {
+    let lines = monitor_lines(contents, name);
+    let mode = lines.iter().rev().find(|value| !is_reserved_line(value));
+    let reserved = lines.iter().rev().find(|value| is_reserved_line(value));
+    mode.into_iter().chain(reserved).copied().collect()
+}
+
+fn is_reserved_line(value: &str) -> bool {
+    value.split(',').nth(1).map(str::trim) == Some("addreserved")
+}
+
+/// The value of the last `monitor=` mode line for `name`, i.e. the one
+/// Hyprland ends up using.
+pub fn find_monitor_rule<'a>(contents: &'a str, name: &str) -> Option<&'a str> {
+    monitor_lines(contents, name)
+        .into_iter()
+        .rev()
+        .find(|value| !is_reserved_line(value))
+}
+
+/// The reserved area (top, bottom, left, right) from the last
+/// `monitor=NAME,addreserved,...` line for `name`.
+pub fn find_reserved(contents: &str, name: &str) -> Option<[u32; 4]> {
+    let value = monitor_lines(contents, name)
+        .into_iter()
+        .rev()
+        .find(|value| is_reserved_line(value))?;
+    let sides: Vec<u32> = value
+        .split(',')
+        .skip(2)
+        .filter_map(|v| v.trim().parse().ok())
+        .collect();
+    sides.try_into().ok()
+}
+
+/// Whether the saved mode line for `name` disables it.
+pub fn is_disabled(contents: &str, name: &str) -> bool {
+    find_monitor_rule(contents, name)
+        .and_then(|value| value.split(',').nth(1))
+        .map(str::trim)
+        == Some("disable")
+}
+
+/// The explicit position (`XxY`) from the saved mode line for `name`, if it
+/// has one rather than `auto`.
+pub fn find_position(contents: &str, name: &str) -> Option<(i32, i32)> {
+    let position = find_monitor_rule(contents, name)?.split(',').nth(2)?.trim();
+    let (x, y) = position.split_once('x')?;
+    Some((x.parse().ok()?, y.parse().ok()?))
+}
+
+/// The custom timings from the saved mode line for `name`, if it uses a
+/// `modeline` mode.
+pub fn find_modeline(contents: &str, name: &str) -> Option<Modeline> {
+    let mode = find_monitor_rule(contents, name)?.split(',').nth(1)?.trim();
+    Modeline::from_spec(mode.strip_prefix("modeline ")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAVED: &str = "\
+# Monitor settings generated by hypr-tui
+monitor=eDP-1,1920x1200@60.00,0x0,1.25,transform,0,vrr,0
+monitor=eDP-1,addreserved,0,30,0,0
+monitor = DP-1 , 2560x1440@144.00 , -2560x0 , 1.00
+monitor=DP-10,disable
+monitor=HDMI-A-1,preferred,auto,1
+monitor=HDMI-A-1,disable
+";
+
+    #[test]
+    fn last_mode_line_wins() {
+        assert_eq!(
+            find_monitor_rule(SAVED, "HDMI-A-1"),
+            Some("HDMI-A-1,disable")
+        );
+        assert_eq!(
+            find_monitor_rule(SAVED, "eDP-1"),
+            Some("eDP-1,1920x1200@60.00,0x0,1.25,transform,0,vrr,0"),
+            "addreserved lines are not mode lines"
+        );
+        assert_eq!(find_monitor_rule(SAVED, "DP-2"), None);
+    }
+
+    #[test]
+    fn saved_rules_follow_the_saved_file() {
+        let hyprland_conf = "monitor=DP-1,preferred,auto,1\nmonitor=DP-2,1920x1080@60,0x0,1\n";
+        assert_eq!(
+            saved_rules(hyprland_conf, SAVED),
+            [
+                "DP-1,2560x1440@144.00,-2560x0,1.00",
+                "DP-2,1920x1080@60,0x0,1",
+                "eDP-1,1920x1200@60.00,0x0,1.25,transform,0,vrr,0",
+                "eDP-1,addreserved,0,30,0,0",
+                "DP-10,disable",
+                "HDMI-A-1,disable",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_each_monitor_once() {
+        assert_eq!(monitor_names(SAVED), ["eDP-1", "DP-1", "DP-10", "HDMI-A-1"]);
+    }
+
+    #[test]
+    fn finds_conflicts_in_reading_order() {
+        let hyprland = "\
+monitor=DP-1,1920x1080@60,0x0,1
+source = ~/.config/hypr/monitors.conf
+monitor=HDMI-A-1,preferred,auto,2
+";
+        let conflicts = find_conflicts(hyprland, SAVED);
+        let names: Vec<&str> = conflicts.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["DP-1", "HDMI-A-1"]);
+
+        // DP-1's line in hyprland.conf comes before the source line.
+        assert_eq!(conflicts[0].winner().file, "monitors.conf");
+        // HDMI-A-1's comes after it, so it overrides the saved disable.
+        assert_eq!(conflicts[1].winner().file, "hyprland.conf");
+        assert_eq!(conflicts[1].winner().line, 3);
+        assert!(find_conflicts("", "monitor=DP-1,disable\nmonitor=DP-1,disable").is_empty());
+    }
+
+    #[test]
+    fn comments_out_only_named_monitors() {
+        let (contents, count) = comment_out_monitors(
+            "monitor=DP-1,preferred,auto,1\nmonitor=,preferred,auto,1\n",
+            &[String::from("DP-1")],
+        );
+        assert_eq!(count, 1);
+        assert!(contents.starts_with("# monitor=DP-1"));
+        assert!(contents.ends_with("\nmonitor=,preferred,auto,1\n"));
+        assert_eq!(
+            effective_lines(SAVED, "HDMI-A-1"),
+            ["HDMI-A-1,disable"],
+            "earlier mode lines are dropped"
+        );
+    }
+
+    #[test]
+    fn names_match_exactly() {
+        assert!(is_disabled(SAVED, "DP-10"));
+        assert!(!is_disabled(SAVED, "DP-1"));
+        assert!(is_disabled(SAVED, "HDMI-A-1"));
+    }
+
+    #[test]
+    fn reads_reserved_area() {
+        assert_eq!(find_reserved(SAVED, "eDP-1"), Some([0, 30, 0, 0]));
+        assert_eq!(find_reserved(SAVED, "DP-1"), None);
+        assert_eq!(find_reserved("monitor=DP-1,addreserved,1,2", "DP-1"), None);
+    }
+
+    #[test]
+    fn reads_explicit_positions_only() {
+        assert_eq!(find_position(SAVED, "eDP-1"), Some((0, 0)));
+        assert_eq!(find_position(SAVED, "DP-1"), Some((-2560, 0)));
+        assert_eq!(find_position(SAVED, "HDMI-A-1"), None);
+        assert_eq!(find_position("monitor=DP-3,preferred,auto,1", "DP-3"), None);
+    }
+
+    #[test]
+    fn appends_a_recognized_source_line() {
+        let path = Path::new("/home/me/.config/hypr/monitors.conf");
+        let contents = with_source_line("monitor=,preferred,auto,1", path);
+        assert!(contents.starts_with("monitor=,preferred,auto,1\n\n#"));
+        assert!(contents.ends_with("source = /home/me/.config/hypr/monitors.conf\n"));
+        assert!(sources_monitors_conf(&contents));
+    }
+
+    #[test]
+    fn host_stub_sources_the_host_file() {
+        let stub = host_source_stub(Path::new("/home/me/.config/hypr/monitors-desk.conf"));
+        assert!(stub.starts_with('#'));
+        assert!(stub.ends_with("source = /home/me/.config/hypr/monitors-desk.conf\n"));
+    }
+}
@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs,
+    path::PathBuf,
+    sync::{LazyLock, OnceLock},
+};
+
+use crate::xdg;
+
+/// Translations shipped with the binary, by language code.
+const BUILT_IN: [(&str, &str); 2] = [
+    ("en", include_str!("../locales/en.toml")),
+    ("de", include_str!("../locales/de.toml")),
+];
+/// Extra or replacement translations, `<language>.toml` with the same keys.
+const USER_LOCALES_DIR: &str = "hyprmonitor/locales";
+
+static ENGLISH: LazyLock<HashMap<String, String>> =
+    LazyLock::new(|| parse(BUILT_IN[0].1).unwrap_or_default());
+static ACTIVE: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Picks the language for the rest of the process: `language` from the
+/// config, otherwise the one in `LC_ALL`, `LC_MESSAGES` or `LANG`. Only
+/// the first call has an effect; until then everything is in English.
+pub fn init(language: Option<&str>) {
+    let language = language.map(str::to_string).or_else(env_language);
+    let Some(language) = language.filter(|lang| lang != "en") else {
+        return;
+    };
+    let mut strings = BUILT_IN
+        .iter()
+        .find(|(code, _)| *code == language)
+        .and_then(|(_, contents)| parse(contents))
+        .unwrap_or_default();
+    if let Some(user) = user_locale_path(&language)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| parse(&contents))
+    {
+        strings.extend(user);
+    }
+    let _ = ACTIVE.set(strings);
+}
+
+/// The text for `key` in the current language, falling back to English and
+/// then to the key itself.
+pub fn tr(key: &'static str) -> &'static str {
+    ACTIVE
+        .get()
+        .and_then(|strings| strings.get(key))
+        .or_else(|| ENGLISH.get(key))
+        .map_or(key, String::as_str)
+}
+
+/// [`tr`] with each `{name}` placeholder replaced by its value.
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter()
+        .fold(tr(key).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+}
+
+/// The language code from the locale environment, e.g. `de` for
+/// `de_DE.UTF-8`.
+fn env_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .split(['_', '.', '@'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        })
+        .filter(|lang| lang != "c" && lang != "posix")
+}
+
+fn user_locale_path(language: &str) -> Option<PathBuf> {
+    xdg::config_path(USER_LOCALES_DIR)
+        .ok()
+        .map(|dir| dir.join(format!("{}.toml", language)))
+}
+
+/// Flattens a locale file's sections into `section.key` entries.
+fn parse(contents: &str) -> Option<HashMap<String, String>> {
+    let table: toml::Table = toml::from_str(contents).ok()?;
+    let mut strings = HashMap::new();
+    for (section, entries) in table {
+        let Some(entries) = entries.as_table() else {
+            continue;
+        };
+        for (key, value) in entries {
+            if let Some(text) = value.as_str() {
+                strings.insert(format!("{}.{}", section, key), text.to_string());
+            }
+        }
+    }
+    Some(strings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn every_locale_translates_every_key() {
+        for (code, contents) in BUILT_IN {
+            let strings = parse(contents).expect("locale parses");
+            for (key, english) in ENGLISH.iter() {
+                let text = strings
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{} is missing {}", code, key));
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(english),
+                    "{} {}",
+                    code,
+                    key
+                );
+            }
+            assert_eq!(strings.len(), ENGLISH.len(), "{} has unknown keys", code);
+        }
+    }
+
+    #[test]
+    fn fills_placeholders_and_falls_back_to_the_key() {
+        assert_eq!(tr_args("status.unsaved", &[("count", &3)]), "3 unsaved");
+        assert_eq!(tr("no.such.key"), "no.such.key");
+    }
+}
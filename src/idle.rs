@@ -0,0 +1,96 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::xdg;
+
+const HYPRIDLE_CONF_PATH: &str = "hypr/hypridle.conf";
+const BEGIN_MARKER: &str = "# BEGIN hypr-tui monitor idle";
+const END_MARKER: &str = "# END hypr-tui monitor idle";
+
+fn path() -> io::Result<PathBuf> {
+    xdg::config_path(HYPRIDLE_CONF_PATH)
+}
+
+/// hypridle `listener` blocks that blank each monitor after its timeout in
+/// seconds and wake it on activity, shortest timeout first, between markers
+/// so a later run can replace them.
+pub fn listener_blocks(timeouts: &BTreeMap<String, u32>) -> String {
+    let mut monitors: Vec<(&String, &u32)> = timeouts.iter().collect();
+    monitors.sort_by_key(|&(_, &seconds)| seconds);
+
+    let mut blocks = format!("{}\n", BEGIN_MARKER);
+    for (monitor, seconds) in monitors {
+        blocks.push_str(&format!(
+            "listener {{\n    timeout = {}\n    on-timeout = hyprctl dispatch dpms off {}\n    on-resume = hyprctl dispatch dpms on {}\n}}\n",
+            seconds, monitor, monitor
+        ));
+    }
+    blocks.push_str(END_MARKER);
+    blocks.push('\n');
+    blocks
+}
+
+/// `contents` with the blocks written by an earlier run replaced by
+/// `blocks`, or with `blocks` appended if there are none yet. Everything
+/// else in the file is kept.
+pub fn with_blocks(contents: &str, blocks: &str) -> String {
+    let start = contents.find(BEGIN_MARKER);
+    let end = contents
+        .find(END_MARKER)
+        .map(|end| end + END_MARKER.len())
+        .map(|end| end + usize::from(contents[end..].starts_with('\n')));
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}{}", &contents[..start], blocks, &contents[end..])
+        }
+        _ if contents.is_empty() => blocks.to_string(),
+        _ if contents.ends_with('\n') => format!("{}\n{}", contents, blocks),
+        _ => format!("{}\n\n{}", contents, blocks),
+    }
+}
+
+/// Writes the blocks into `hypridle.conf`, which hypridle reads on its
+/// next start.
+pub fn install(timeouts: &BTreeMap<String, u32>) -> io::Result<PathBuf> {
+    let path = path()?;
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, with_blocks(&contents, &listener_blocks(timeouts)))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_earlier_blocks_and_keeps_the_rest() {
+        let timeouts: BTreeMap<String, u32> = [
+            (String::from("eDP-1"), 900),
+            (String::from("HDMI-A-1"), 120),
+        ]
+        .into_iter()
+        .collect();
+        let blocks = listener_blocks(&timeouts);
+        assert!(
+            blocks.find("HDMI-A-1") < blocks.find("eDP-1"),
+            "the monitor that sleeps first comes first"
+        );
+        assert!(blocks
+            .contains("    timeout = 120\n    on-timeout = hyprctl dispatch dpms off HDMI-A-1\n"));
+
+        let own = "general {\n    lock_cmd = hyprlock\n}";
+        let installed = with_blocks(own, &blocks);
+        assert_eq!(installed, format!("{}\n\n{}", own, blocks));
+
+        let fewer = listener_blocks(&timeouts.into_iter().take(1).collect());
+        let updated = with_blocks(&installed, &fewer);
+        assert_eq!(updated, format!("{}\n\n{}", own, fewer));
+        assert_eq!(with_blocks("", &fewer), fewer);
+    }
+}
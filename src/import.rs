@@ -0,0 +1,189 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{io, process::Command};
+
+use crate::profile::{Profile, ProfileMonitor};
+use crate::wallpaper::Wallpapers;
+
+/// Tools whose output layout can be turned into a profile.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ImportSource {
+    /// `wlr-randr --json`, which works on any wlroots compositor
+    WlrRandr,
+    /// `swaymsg -t get_outputs`
+    Sway,
+}
+
+impl ImportSource {
+    /// Runs the tool against the running compositor and returns its JSON.
+    pub fn query(self) -> io::Result<String> {
+        let (program, args): (&str, &[&str]) = match self {
+            ImportSource::WlrRandr => ("wlr-randr", &["--json"]),
+            ImportSource::Sway => ("swaymsg", &["-r", "-t", "get_outputs"]),
+        };
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|e| io::Error::new(e.kind(), format!("Failed to run {}: {}", program, e)))?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "{} failed: {}",
+                program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Builds a profile called `name` from the tool's JSON output.
+    pub fn parse(self, name: &str, json: &str) -> io::Result<Profile> {
+        let monitors = match self {
+            ImportSource::WlrRandr => serde_json::from_str::<Vec<WlrOutput>>(json)
+                .map(|outputs| outputs.into_iter().map(WlrOutput::into_monitor).collect()),
+            ImportSource::Sway => serde_json::from_str::<Vec<SwayOutput>>(json)
+                .map(|outputs| outputs.into_iter().map(SwayOutput::into_monitor).collect()),
+        }
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Profile {
+            name: name.to_string(),
+            on_apply: Vec::new(),
+            wallpapers: Wallpapers::new(),
+            power: None,
+            blanking: None,
+            monitors,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Deserialize)]
+struct WlrMode {
+    width: u32,
+    height: u32,
+    refresh: f64,
+    #[serde(default)]
+    current: bool,
+}
+
+#[derive(Deserialize)]
+struct WlrOutput {
+    name: String,
+    enabled: bool,
+    #[serde(default)]
+    modes: Vec<WlrMode>,
+    position: Option<Position>,
+    scale: Option<f64>,
+}
+
+impl WlrOutput {
+    fn into_monitor(self) -> ProfileMonitor {
+        let mode = self.modes.iter().find(|mode| mode.current);
+        ProfileMonitor {
+            name: self.name,
+            enabled: self.enabled,
+            mode: mode_spec(mode.map(|m| (m.width, m.height, m.refresh))),
+            position: position_spec(self.position),
+            scale: self.scale.unwrap_or(1.0),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SwayMode {
+    width: u32,
+    height: u32,
+    /// In millihertz.
+    refresh: u32,
+}
+
+#[derive(Deserialize)]
+struct SwayOutput {
+    name: String,
+    active: bool,
+    current_mode: Option<SwayMode>,
+    rect: Option<Position>,
+    scale: Option<f64>,
+}
+
+impl SwayOutput {
+    fn into_monitor(self) -> ProfileMonitor {
+        let mode = self.current_mode.filter(|_| self.active);
+        ProfileMonitor {
+            name: self.name,
+            enabled: self.active,
+            mode: mode_spec(mode.map(|m| (m.width, m.height, f64::from(m.refresh) / 1000.0))),
+            position: position_spec(self.rect.filter(|_| self.active)),
+            // Sway reports -1 for outputs that are off.
+            scale: self.scale.filter(|&scale| scale > 0.0).unwrap_or(1.0),
+        }
+    }
+}
+
+fn mode_spec(mode: Option<(u32, u32, f64)>) -> String {
+    match mode {
+        Some((width, height, refresh)) => format!("{}x{}@{:.2}", width, height, refresh),
+        None => String::from("preferred"),
+    }
+}
+
+fn position_spec(position: Option<Position>) -> String {
+    match position {
+        Some(Position { x, y }) => format!("{}x{}", x, y),
+        None => String::from("auto"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WLR_RANDR: &str = include_str!("../tests/fixtures/wlr-randr.json");
+
+    #[test]
+    fn imports_wlr_randr_layout() {
+        let profile = ImportSource::WlrRandr.parse("desk", WLR_RANDR).unwrap();
+        let monitors: Vec<_> = profile
+            .monitors
+            .iter()
+            .map(|m| {
+                (
+                    m.name.as_str(),
+                    m.enabled,
+                    m.mode.as_str(),
+                    m.position.as_str(),
+                    m.scale,
+                )
+            })
+            .collect();
+        assert_eq!(
+            monitors,
+            [
+                ("eDP-1", false, "preferred", "auto", 1.0),
+                ("DP-1", true, "2560x1440@143.97", "0x0", 1.0),
+                ("HDMI-A-1", true, "1920x1080@60.00", "2560x0", 1.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn imports_sway_outputs() {
+        let json = r#"[
+            {"name": "eDP-1", "active": false, "scale": -1.0, "rect": {"x": 0, "y": 0, "width": 0, "height": 0}},
+            {"name": "DP-1", "active": true, "scale": 1.5,
+             "current_mode": {"width": 3840, "height": 2160, "refresh": 59997},
+             "rect": {"x": 0, "y": 0, "width": 2560, "height": 1440}}
+        ]"#;
+        let profile = ImportSource::Sway.parse("desk", json).unwrap();
+        assert_eq!(profile.monitors[0].mode, "preferred");
+        assert_eq!(profile.monitors[0].position, "auto");
+        assert_eq!(profile.monitors[0].scale, 1.0);
+        assert_eq!(profile.monitors[1].mode, "3840x2160@60.00");
+        assert_eq!(profile.monitors[1].scale, 1.5);
+    }
+}
@@ -0,0 +1,174 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// What a text input popup is for, which decides what happens to the text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputPurpose {
+    /// Layout position of the monitor at this index.
+    Position(usize),
+    /// Width, height and rate of a custom mode for the monitor at this
+    /// index.
+    CustomMode(usize),
+    /// Logical size to scale the monitor at this index to.
+    VirtualResolution(usize),
+}
+
+/// The result of a key press in a text input.
+#[derive(Debug, PartialEq)]
+pub enum InputOutcome {
+    Editing,
+    Cancelled,
+    /// Enter on text that passed validation; surrounding spaces are trimmed.
+    Submitted(String),
+}
+
+/// A single-line text field with a cursor, shared by every popup that asks
+/// for typed input. Enter only submits text its validator accepts; otherwise
+/// the error is shown under the field and editing continues.
+pub struct TextInput {
+    pub purpose: InputPurpose,
+    pub title: String,
+    pub label: String,
+    /// Shown under the field, e.g. the expected format.
+    pub help: String,
+    pub error: Option<String>,
+    chars: Vec<char>,
+    /// Index into `chars`; equal to its length at the end of the text.
+    cursor: usize,
+    validate: fn(&str) -> Result<(), String>,
+}
+
+impl TextInput {
+    pub fn new(
+        purpose: InputPurpose,
+        title: impl Into<String>,
+        label: impl Into<String>,
+        initial: &str,
+        validate: fn(&str) -> Result<(), String>,
+    ) -> Self {
+        let chars: Vec<char> = initial.chars().collect();
+        Self {
+            purpose,
+            title: title.into(),
+            label: label.into(),
+            help: String::new(),
+            error: None,
+            cursor: chars.len(),
+            chars,
+            validate,
+        }
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = help.into();
+        self
+    }
+
+    pub fn value(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    /// The text split at the cursor.
+    pub fn split_at_cursor(&self) -> (String, String) {
+        (
+            self.chars[..self.cursor].iter().collect(),
+            self.chars[self.cursor..].iter().collect(),
+        )
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return InputOutcome::Cancelled,
+            KeyCode::Enter => {
+                let value = self.value().trim().to_string();
+                match (self.validate)(&value) {
+                    Ok(()) => return InputOutcome::Submitted(value),
+                    Err(error) => {
+                        self.error = Some(error);
+                        return InputOutcome::Editing;
+                    }
+                }
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.chars.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.chars.len(),
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.chars.len(),
+            KeyCode::Char('u') if ctrl => {
+                self.chars.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            KeyCode::Char('w') if ctrl => {
+                let mut start = self.cursor;
+                while start > 0 && self.chars[start - 1] == ' ' {
+                    start -= 1;
+                }
+                while start > 0 && self.chars[start - 1] != ' ' {
+                    start -= 1;
+                }
+                self.chars.drain(start..self.cursor);
+                self.cursor = start;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.chars.remove(self.cursor);
+            }
+            KeyCode::Delete if self.cursor < self.chars.len() => {
+                self.chars.remove(self.cursor);
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.chars.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            _ => return InputOutcome::Editing,
+        }
+        self.error = None;
+        InputOutcome::Editing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode) -> InputOutcome {
+        input.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn not_empty(text: &str) -> Result<(), String> {
+        if text.is_empty() {
+            Err(String::from("Required"))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn edits_at_the_cursor_and_validates_on_enter() {
+        let mut input =
+            TextInput::new(InputPurpose::Position(0), "Test", "Text", "2560", not_empty);
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Char('-'));
+        press(&mut input, KeyCode::End);
+        press(&mut input, KeyCode::Char(','));
+        press(&mut input, KeyCode::Char('0'));
+        assert_eq!(
+            input.split_at_cursor(),
+            (String::from("-2560,0"), String::new())
+        );
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "-25600");
+        assert_eq!(
+            press(&mut input, KeyCode::Enter),
+            InputOutcome::Submitted(String::from("-25600"))
+        );
+
+        input.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(press(&mut input, KeyCode::Enter), InputOutcome::Editing);
+        assert_eq!(input.error.as_deref(), Some("Required"));
+        assert_eq!(press(&mut input, KeyCode::Esc), InputOutcome::Cancelled);
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Local};
+use std::{fs, io, path::PathBuf};
+
+use crate::monitor::{rate_spec, Monitor};
+use crate::xdg;
+
+const LAYOUT_LOG_PATH: &str = "hyprmonitor/history.log";
+/// Older changes are dropped once the log holds this many.
+const LAYOUT_LOG_LIMIT: usize = 1000;
+
+/// One `monitor` rule that took effect, with what the monitor was running
+/// before it. Both sides are rule values without the monitor name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutChange {
+    pub timestamp: DateTime<Local>,
+    pub monitor: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl LayoutChange {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {}: {} -> {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            self.monitor,
+            self.before,
+            self.after
+        )
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.timestamp.to_rfc3339(),
+            self.monitor,
+            self.before,
+            self.after
+        )
+    }
+
+    fn parse_line(line: &str) -> Option<Self> {
+        let mut parts = line.splitn(4, '\t');
+        let timestamp = DateTime::parse_from_rfc3339(parts.next()?).ok()?;
+        Some(Self {
+            timestamp: timestamp.with_timezone(&Local),
+            monitor: parts.next()?.to_string(),
+            before: parts.next()?.to_string(),
+            after: parts.next()?.to_string(),
+        })
+    }
+}
+
+fn path() -> io::Result<PathBuf> {
+    xdg::state_path(LAYOUT_LOG_PATH)
+}
+
+/// Logged changes from oldest to newest.
+pub fn load() -> io::Result<Vec<LayoutChange>> {
+    match fs::read_to_string(path()?) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter_map(LayoutChange::parse_line)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Adds `change` to the log, dropping the oldest entries past the limit.
+pub fn append(change: &LayoutChange) -> io::Result<()> {
+    let path = path()?;
+    let mut changes = load()?;
+    changes.push(change.clone());
+    let start = changes.len().saturating_sub(LAYOUT_LOG_LIMIT);
+    let contents: String = changes[start..]
+        .iter()
+        .map(|change| change.to_line() + "\n")
+        .collect();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// The monitor name and settings of a `monitor` rule, or `None` for rules
+/// that don't change the layout, such as reserved areas.
+pub fn split_rule(rule: &str) -> Option<(&str, &str)> {
+    let (name, settings) = rule.split_once(',')?;
+    (!settings.starts_with("addreserved")).then_some((name, settings))
+}
+
+/// What Hyprland last reported `monitor` running at, written like the
+/// settings of a `monitor` rule.
+pub fn live_rule(monitor: &Monitor) -> String {
+    if !monitor.active {
+        return String::from("disable");
+    }
+    let raw = &monitor.raw;
+    format!(
+        "{}x{}@{},{}x{},{:.2},transform,{},vrr,{}",
+        raw["width"].as_u64().unwrap_or(0),
+        raw["height"].as_u64().unwrap_or(0),
+        rate_spec(raw["refreshRate"].as_f64().unwrap_or(0.0)),
+        monitor.x,
+        monitor.y,
+        raw["scale"].as_f64().unwrap_or(1.0),
+        raw["transform"].as_u64().unwrap_or(0),
+        u8::from(raw["vrr"].as_bool().unwrap_or(false))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_round_trip_through_log_lines() {
+        let (name, after) = split_rule("DP-1,1920x1080@60.00,0x0,1.00").expect("rule splits");
+        assert_eq!(split_rule("DP-1,addreserved,0,0,0,0"), None);
+
+        let change = LayoutChange {
+            timestamp: DateTime::parse_from_rfc3339("2024-05-01T03:12:01+02:00")
+                .expect("timestamp parses")
+                .with_timezone(&Local),
+            monitor: name.to_string(),
+            before: String::from("2560x1440@143.91,0x0,1.25,transform,0,vrr,0"),
+            after: after.to_string(),
+        };
+        assert_eq!(
+            LayoutChange::parse_line(&change.to_line()),
+            Some(change.clone())
+        );
+        assert!(change.describe().ends_with(
+            "DP-1: 2560x1440@143.91,0x0,1.25,transform,0,vrr,0 -> 1920x1080@60.00,0x0,1.00"
+        ));
+    }
+}
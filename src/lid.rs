@@ -0,0 +1,70 @@
+use crate::commands::HyprCommand;
+use crate::hyprconf;
+
+const LID_SWITCH: &str = "Lid Switch";
+
+/// What happens to the internal panel when the laptop lid is closed. The rule
+/// is persisted as `bindl` lines that call back into this binary.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LidRule {
+    Ignore,
+    Disable,
+    DisableWithExternal,
+}
+
+impl LidRule {
+    pub const ALL: [LidRule; 3] = [
+        LidRule::Ignore,
+        LidRule::Disable,
+        LidRule::DisableWithExternal,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LidRule::Ignore => "Do nothing",
+            LidRule::Disable => "Disable panel",
+            LidRule::DisableWithExternal => "Disable if external",
+        }
+    }
+
+    /// Recovers the rule from previously saved `bindl` lines.
+    pub fn from_config(contents: &str) -> Self {
+        let close_bind = contents
+            .lines()
+            .find(|line| line.contains(&format!("switch:on:{}", LID_SWITCH)));
+
+        match close_bind {
+            Some(line) if line.contains("--only-with-external") => LidRule::DisableWithExternal,
+            Some(_) => LidRule::Disable,
+            None => LidRule::Ignore,
+        }
+    }
+
+    /// The `bindl` lines to write into the saved config for this rule.
+    pub fn config_lines(&self) -> String {
+        let close_args = match self {
+            LidRule::Ignore => return String::new(),
+            LidRule::Disable => "lid close",
+            LidRule::DisableWithExternal => "lid close --only-with-external",
+        };
+
+        format!(
+            "\n# Lid switch handling managed by {bin}\n\
+             bindl = , switch:on:{switch}, exec, {bin} {close}\n\
+             bindl = , switch:off:{switch}, exec, {bin} lid open\n",
+            bin = env!("CARGO_BIN_NAME"),
+            switch = LID_SWITCH,
+            close = close_args
+        )
+    }
+}
+
+/// The command that turns the internal panel back on, reusing its saved
+/// mode when there is one.
+pub fn enable_command(name: &str) -> HyprCommand {
+    let saved = hyprconf::read_monitors_conf().unwrap_or_default();
+    match hyprconf::find_monitor_rule(&saved, name) {
+        Some(rule) if !rule.contains("disable") => HyprCommand::monitor(rule),
+        _ => HyprCommand::monitor(format!("{},preferred,auto,1", name)),
+    }
+}
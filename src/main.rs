@@ -1,3 +1,4 @@
+use clap::Parser;
 use crossterm::{
     event::{self, Event, KeyEventKind},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -5,21 +6,108 @@ use crossterm::{
 };
 use ratatui::{prelude::*, Terminal};
 use std::io::{self, stdout};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
 
 mod app;
+mod autostart;
+mod blanking;
+mod bundle;
+mod check;
+mod cli;
 mod commands;
+mod config;
+mod control;
+mod daemon;
+mod dbus;
+mod doctor;
+mod events;
+mod history;
+mod hooks;
+mod hyprconf;
+mod i18n;
+mod idle;
+mod import;
+mod input;
+mod layout_log;
+mod lid;
+mod main_monitor;
+mod message;
+mod modeline;
 mod monitor;
+mod monitorv2;
+mod notify;
+mod palette;
+mod picker;
+mod plain;
+mod power;
+mod presets;
+mod profile;
+mod script;
+mod seen;
+mod setup;
+mod state;
+mod status;
+mod svg;
+mod template;
+mod theme;
 mod ui;
+mod version;
+mod wallpaper;
+mod xdg;
+mod xwayland;
 
 use app::App;
+use cli::Cli;
+use control::{ControlServer, Lifecycle};
+use dbus::DbusService;
+use i18n::tr_args;
+use script::{Recorder, Step};
 
-fn main() -> io::Result<()> {
-    let mut terminal = setup_terminal()?;
-    let result = run_app(&mut terminal);
+/// How often the UI wakes up without input, for debounced and timed actions.
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match (cli.apply, cli.command) {
+        (Some(name), _) => cli::run(cli::Command::ApplyProfile { name }),
+        (None, Some(command)) => cli::run(command),
+        (None, None) if cli.plain => plain::run().map(|()| ExitCode::SUCCESS),
+        (None, None) => run_tui(cli.ascii, cli.script, cli.record).map(|()| ExitCode::SUCCESS),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("hypr-tui: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_tui(ascii: bool, script: Option<PathBuf>, record: Option<PathBuf>) -> io::Result<()> {
+    ControlServer::ensure_single_instance()?;
+    // Read both before taking over the terminal, so mistakes print plainly.
+    let script = script.as_deref().map(script::load).transpose()?;
+    let recorder = record.as_deref().map(Recorder::create).transpose()?;
+    install_panic_hook();
+    let result =
+        setup_terminal().and_then(|mut terminal| run_app(&mut terminal, ascii, script, recorder));
     restore_terminal()?;
     result
 }
 
+/// Restores the terminal before the default hook prints the panic, so a crash
+/// doesn't leave the shell in raw mode on the alternate screen.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+}
+
 fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<std::io::Stdout>>> {
     stdout().execute(EnterAlternateScreen)?;
     enable_raw_mode()?;
@@ -32,17 +120,90 @@ fn restore_terminal() -> io::Result<()> {
     Ok(())
 }
 
-fn run_app(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> io::Result<()> {
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ascii: bool,
+    script: Option<Vec<Step>>,
+    mut recorder: Option<Recorder>,
+) -> io::Result<()> {
     let mut app = App::new()?;
+    let mut script = script.unwrap_or_default().into_iter();
+    app.force_ascii = ascii;
+    app.config.ui.ascii |= ascii;
+    app.offer_setup();
+    let control = ControlServer::bind()
+        .map_err(|e| app.warn(format!("Control socket disabled: {}", e)))
+        .ok();
+    let mut dbus = DbusService::connect(&app)
+        .map_err(|e| app.warn(format!("D-Bus service disabled: {}", e)))
+        .ok();
+    let events = events::monitor_feed()
+        .map_err(|e| app.warn(format!("Hotplug updates disabled: {}", e)))
+        .ok();
 
-    loop {
-        terminal.draw(|f| ui::render(f, &app))?;
+    // Only redraw when something may have changed, so an idle UI costs
+    // nothing over a slow SSH connection.
+    let mut dirty = true;
+    'run: loop {
+        if dirty {
+            terminal.draw(|f| ui::render(f, &mut app))?;
+            dirty = false;
+        }
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press && app.handle_key(key.code) {
-                break;
+        // Scripted keys go first, one per frame, so the screen shows each
+        // step; the keyboard takes over once the script runs out.
+        if let Some(step) = script.next() {
+            match step {
+                Step::Key(key) => {
+                    if app.handle_key(key) {
+                        break 'run;
+                    }
+                    dirty = true;
+                }
+                Step::Wait(duration) => std::thread::sleep(duration),
             }
+        } else if event::poll(TICK_INTERVAL)? {
+            // Handle everything already queued before drawing again, so held
+            // keys don't each cost a frame.
+            loop {
+                match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        if let Some(recorder) = &mut recorder {
+                            recorder.record(&key)?;
+                        }
+                        if app.handle_key(key) {
+                            break 'run;
+                        }
+                        dirty = true;
+                    }
+                    Event::Resize(..) => dirty = true,
+                    _ => {}
+                }
+                if !event::poll(Duration::ZERO)? {
+                    break;
+                }
+            }
+        }
+        if let Some(control) = &control {
+            dirty |= control.poll(&mut app);
+            match control.take_lifecycle() {
+                Some(Lifecycle::Stop) => break 'run,
+                Some(Lifecycle::Reload) => {
+                    if let Err(e) = app.refresh_monitors() {
+                        app.error(tr_args("messages.refresh_failed", &[("error", &e)]));
+                    }
+                }
+                None => {}
+            }
+        }
+        if let Some(dbus) = &mut dbus {
+            dirty |= dbus.poll(&mut app);
+        }
+        for event in events.iter().flat_map(|events| events.try_iter()) {
+            app.handle_event(&event);
+            dirty = true;
         }
+        dirty |= app.tick();
     }
 
     Ok(())
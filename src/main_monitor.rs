@@ -0,0 +1,57 @@
+use serde::Deserialize;
+use std::{fs, io, path::PathBuf};
+
+use crate::xdg;
+
+/// The output picked with "Set as Main Screen", kept across sessions.
+const MAIN_MONITOR_PATH: &str = "hyprmonitor/main-monitor";
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct MainSettings {
+    /// Open workspace 1 on the main screen, now and in the saved file.
+    pub bind_workspace: bool,
+}
+
+fn path() -> io::Result<PathBuf> {
+    xdg::state_path(MAIN_MONITOR_PATH)
+}
+
+/// The designated main monitor, if one was set.
+pub fn load() -> Option<String> {
+    let name = fs::read_to_string(path().ok()?).ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+pub fn store(name: &str) -> io::Result<()> {
+    let path = path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, name)
+}
+
+/// The value of the `workspace=` rule that puts workspace 1 on `name`.
+pub fn workspace_rule(name: &str) -> String {
+    format!("1,monitor:{},default:true", name)
+}
+
+/// Indices of `names` with the main monitor's first and the rest in order.
+pub fn main_first(names: &[&str], main: Option<&str>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..names.len()).collect();
+    order.sort_by_key(|&i| Some(names[i]) != main);
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_monitor_goes_first() {
+        let names = ["eDP-1", "DP-1", "HDMI-A-1"];
+        assert_eq!(main_first(&names, Some("DP-1")), [1, 0, 2]);
+        assert_eq!(main_first(&names, Some("DP-9")), [0, 1, 2]);
+        assert_eq!(main_first(&names, None), [0, 1, 2]);
+    }
+}
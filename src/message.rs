@@ -0,0 +1,84 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Messages waiting beyond this many drop the oldest waiting one.
+const MAX_QUEUED: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// How long a message stays up once it is shown. Problems linger a bit
+    /// longer so they aren't missed.
+    fn duration(&self) -> Duration {
+        match self {
+            Severity::Info => Duration::from_secs(4),
+            Severity::Warning => Duration::from_secs(6),
+            Severity::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub text: String,
+    pub severity: Severity,
+}
+
+/// Messages shown one at a time in the status bar, each dismissed after its
+/// severity's duration rather than by the next key press.
+#[derive(Default)]
+pub struct MessageQueue {
+    queue: VecDeque<Message>,
+    /// When the front message goes away; set once it is first shown.
+    expires_at: Option<Instant>,
+}
+
+impl MessageQueue {
+    pub fn push(&mut self, severity: Severity, text: impl Into<String>) {
+        if self.queue.len() > MAX_QUEUED {
+            self.queue.remove(1);
+        }
+        self.queue.push_back(Message {
+            text: text.into(),
+            severity,
+        });
+    }
+
+    /// The message on display, if any.
+    pub fn current(&self) -> Option<&Message> {
+        self.queue.front()
+    }
+
+    /// Messages waiting behind the current one.
+    pub fn pending(&self) -> usize {
+        self.queue.len().saturating_sub(1)
+    }
+
+    /// Removes every message, shown or waiting, for output that isn't a
+    /// status bar.
+    pub fn take_all(&mut self) -> Vec<Message> {
+        self.expires_at = None;
+        self.queue.drain(..).collect()
+    }
+
+    /// Drops the current message once it has been up long enough and
+    /// returns it.
+    pub fn expire(&mut self, now: Instant) -> Option<Message> {
+        let current = self.queue.front()?;
+        let expires_at = *self
+            .expires_at
+            .get_or_insert_with(|| now + current.severity.duration());
+        if expires_at > now {
+            return None;
+        }
+        self.expires_at = None;
+        self.queue.pop_front()
+    }
+}
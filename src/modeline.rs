@@ -0,0 +1,227 @@
+use std::fmt;
+
+use crate::monitor::parse_resolution;
+
+/// Horizontal timings are multiples of this many pixels.
+const H_GRANULARITY: u32 = 8;
+/// Minimum vertical front porch, in lines.
+const MIN_V_PORCH: u32 = 3;
+/// Minimum vertical back porch, in lines.
+const MIN_V_BPORCH: u32 = 6;
+/// The pixel clock is rounded down to a multiple of this many kHz.
+const CLOCK_STEP: u32 = 250;
+
+/// Minimum time for vertical sync and back porch with normal blanking, µs.
+const MIN_VSYNC_BP: f64 = 550.0;
+/// Horizontal sync width as a percentage of the line.
+const HSYNC_PERCENTAGE: u32 = 8;
+/// Blanking formula gradient and offset.
+const C_PRIME: f64 = 30.0;
+const M_PRIME: f64 = 300.0;
+
+/// Minimum vertical blanking time with reduced blanking, µs.
+const RB_MIN_VBLANK: f64 = 460.0;
+const RB_H_SYNC: u32 = 32;
+const RB_H_BLANK: u32 = 160;
+
+/// A mode computed with the VESA Coordinated Video Timings formula, the
+/// same numbers `cvt` prints, for forcing resolutions a monitor (or the KVM
+/// in front of it) doesn't advertise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Modeline {
+    pub clock_khz: u32,
+    /// Display, sync start, sync end and total, in pixels.
+    pub horizontal: [u32; 4],
+    /// Display, sync start, sync end and total, in lines.
+    pub vertical: [u32; 4],
+    /// CVT-RB: shorter blanking for digital links, with a lower clock.
+    pub reduced: bool,
+}
+
+impl Modeline {
+    /// CVT timings for `width`x`height` at `rate` Hz. `None` for sizes or
+    /// rates too small to time.
+    pub fn cvt(width: u32, height: u32, rate: f64, reduced: bool) -> Option<Self> {
+        let hdisplay = width - width % H_GRANULARITY;
+        if hdisplay == 0 || height == 0 || rate <= 0.0 {
+            return None;
+        }
+        let vsync = vsync_width(hdisplay, height);
+        let frame_us = 1_000_000.0 / rate;
+
+        let (clock_khz, horizontal, vtotal) = if reduced {
+            let hperiod = (frame_us - RB_MIN_VBLANK) / height as f64;
+            if hperiod <= 0.0 {
+                return None;
+            }
+            let vblank =
+                ((RB_MIN_VBLANK / hperiod) as u32 + 1).max(MIN_V_PORCH + vsync + MIN_V_BPORCH);
+            let htotal = hdisplay + RB_H_BLANK;
+            let hsync_end = hdisplay + RB_H_BLANK / 2;
+            (
+                clock(htotal, hperiod),
+                [hdisplay, hsync_end - RB_H_SYNC, hsync_end, htotal],
+                height + vblank,
+            )
+        } else {
+            let hperiod = (frame_us - MIN_VSYNC_BP) / (height + MIN_V_PORCH) as f64;
+            if hperiod <= 0.0 {
+                return None;
+            }
+            let sync_and_back_porch =
+                ((MIN_VSYNC_BP / hperiod) as u32 + 1).max(vsync + MIN_V_PORCH);
+            let blank_percentage = (C_PRIME - M_PRIME * hperiod / 1000.0).max(20.0);
+            let hblank = (hdisplay as f64 * blank_percentage / (100.0 - blank_percentage)) as u32;
+            let hblank = hblank - hblank % (2 * H_GRANULARITY);
+            let htotal = hdisplay + hblank;
+            let hsync_end = hdisplay + hblank / 2;
+            let hsync_start = hsync_end - htotal * HSYNC_PERCENTAGE / 100;
+            let hsync_start = hsync_start + H_GRANULARITY - hsync_start % H_GRANULARITY;
+            (
+                clock(htotal, hperiod),
+                [hdisplay, hsync_start, hsync_end, htotal],
+                height + sync_and_back_porch + MIN_V_PORCH,
+            )
+        };
+
+        let vsync_start = height + MIN_V_PORCH;
+        Some(Self {
+            clock_khz,
+            horizontal,
+            vertical: [height, vsync_start, vsync_start + vsync, vtotal],
+            reduced,
+        })
+    }
+
+    /// Reads `WxH@RATE`, with ` rb` after it for reduced blanking, and
+    /// computes its timings.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut words = text.split_whitespace();
+        let (resolution, rate) = words.next()?.split_once('@')?;
+        let reduced = match words.next() {
+            None => false,
+            Some(word) if word.eq_ignore_ascii_case("rb") => true,
+            Some(_) => return None,
+        };
+        if words.next().is_some() {
+            return None;
+        }
+        let (width, height) = parse_resolution(resolution)?;
+        Self::cvt(
+            width,
+            height,
+            rate.trim_end_matches("Hz").parse().ok()?,
+            reduced,
+        )
+    }
+
+    /// Reads timings back from the form [`Modeline`]'s `Display` writes.
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        let words: Vec<&str> = spec.split_whitespace().collect();
+        let [clock, timings @ .., hsync, vsync] = words.as_slice() else {
+            return None;
+        };
+        let timings: Vec<u32> = timings
+            .iter()
+            .map(|word| word.parse().ok())
+            .collect::<Option<_>>()?;
+        let [h0, h1, h2, h3, v0, v1, v2, v3] = timings.as_slice() else {
+            return None;
+        };
+        let clock: f64 = clock.parse().ok()?;
+        Some(Self {
+            clock_khz: (clock * 1000.0).round() as u32,
+            horizontal: [*h0, *h1, *h2, *h3],
+            vertical: [*v0, *v1, *v2, *v3],
+            reduced: hsync.starts_with('+') && vsync.starts_with('-'),
+        })
+    }
+
+    pub fn resolution(&self) -> String {
+        format!("{}x{}", self.horizontal[0], self.vertical[0])
+    }
+
+    /// The rate the timings actually give, which the rounding leaves a
+    /// little off the one asked for.
+    pub fn refresh_rate(&self) -> f64 {
+        self.clock_khz as f64 * 1000.0 / (self.horizontal[3] as f64 * self.vertical[3] as f64)
+    }
+}
+
+/// What Hyprland's `modeline` mode field takes: the clock in MHz, the
+/// timings and the sync polarities.
+impl fmt::Display for Modeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [h0, h1, h2, h3] = self.horizontal;
+        let [v0, v1, v2, v3] = self.vertical;
+        write!(
+            f,
+            "{:.2} {} {} {} {} {} {} {} {} {}",
+            self.clock_khz as f64 / 1000.0,
+            h0,
+            h1,
+            h2,
+            h3,
+            v0,
+            v1,
+            v2,
+            v3,
+            if self.reduced {
+                "+hsync -vsync"
+            } else {
+                "-hsync +vsync"
+            }
+        )
+    }
+}
+
+/// Lines of vertical sync, which CVT uses to tell the aspect ratio.
+fn vsync_width(hdisplay: u32, vdisplay: u32) -> u32 {
+    let matches = |w: u32, h: u32| (vdisplay * w / h) & !(H_GRANULARITY - 1) == hdisplay;
+    if vdisplay.is_multiple_of(3) && vdisplay * 4 / 3 == hdisplay {
+        4
+    } else if matches(16, 9) {
+        5
+    } else if matches(16, 10) {
+        6
+    } else if (vdisplay.is_multiple_of(4) && vdisplay * 5 / 4 == hdisplay) || matches(15, 9) {
+        7
+    } else {
+        10
+    }
+}
+
+fn clock(htotal: u32, hperiod: f64) -> u32 {
+    let khz = (htotal as f64 * 1000.0 / hperiod) as u32;
+    khz - khz % CLOCK_STEP
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timings_match_the_cvt_tool() {
+        let normal = Modeline::parse("1920x1080@60").expect("mode parses");
+        assert_eq!(
+            normal.to_string(),
+            "173.00 1920 2048 2248 2576 1080 1083 1088 1120 -hsync +vsync"
+        );
+        assert_eq!(normal.resolution(), "1920x1080");
+        assert_eq!(
+            Modeline::from_spec(&normal.to_string()),
+            Some(normal.clone())
+        );
+        assert!((normal.refresh_rate() - 59.96).abs() < 0.01);
+
+        assert_eq!(
+            Modeline::parse("1920x1080@60 rb")
+                .expect("mode parses")
+                .to_string(),
+            "138.50 1920 1968 2000 2080 1080 1083 1088 1111 +hsync -vsync"
+        );
+        assert_eq!(Modeline::parse("1920x1080"), None);
+        assert_eq!(Modeline::parse("1920x1080@60 fast"), None);
+        assert_eq!(Modeline::parse("0x1080@60"), None);
+    }
+}
@@ -1,14 +1,471 @@
 use serde::Deserialize;
 use std::collections::BTreeMap;
 
+use crate::modeline::Modeline;
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Monitor {
+    #[serde(default)]
+    pub id: i64,
     pub name: String,
     #[serde(default)]
+    pub description: String,
+    #[serde(default)]
     pub active: bool,
+    /// Whether the panel is powered rather than blanked with DPMS. Only
+    /// meaningful while `active`.
+    #[serde(rename = "dpmsStatus", default = "default_dpms")]
+    pub dpms_on: bool,
+    /// Whether the compositor's input focus is on this monitor.
+    #[serde(default)]
+    pub focused: bool,
     #[serde(skip)]
-    pub modes: BTreeMap<String, Vec<f64>>,
+    pub modes: BTreeMap<String, Vec<Rate>>,
+    /// Position in the layout, in logical pixels.
+    #[serde(default)]
+    pub x: i32,
+    #[serde(default)]
+    pub y: i32,
+    #[serde(default)]
+    pub physical_width: u32,
+    #[serde(default)]
+    pub physical_height: u32,
+    /// The output this one is mirroring, if any.
+    #[serde(skip)]
+    pub mirror_of: Option<String>,
+    /// The panel's preferred resolution: the first one it advertises.
+    #[serde(skip)]
+    pub native_resolution: Option<String>,
+    /// The object `hyprctl monitors all -j` returned for this monitor.
+    #[serde(skip)]
+    pub raw: serde_json::Value,
+    /// What Hyprland is running the monitor at, as opposed to the pending
+    /// settings in its [`MonitorConfig`].
+    #[serde(skip)]
+    pub applied: AppliedMode,
+}
+
+fn default_dpms() -> bool {
+    true
+}
+
+/// Whether a monitor shows anything. A disabled output is out of the layout
+/// entirely; a sleeping one keeps its place and workspaces with the panel
+/// blanked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PowerState {
+    On,
+    Sleeping,
+    Disabled,
+}
+
+/// A capability or setting worth seeing in the monitor list, read from the
+/// output's hyprctl JSON.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Badge {
+    /// Variable refresh rate is on.
+    Vrr,
+    /// An HDR color preset is in use.
+    Hdr,
+    /// The output scans out 10 bits per channel.
+    TenBit,
+    /// A rotated or flipped transform, 1-7.
+    Transform(u8),
+}
+
+impl Badge {
+    pub fn label(self) -> String {
+        match self {
+            Badge::Vrr => String::from("VRR"),
+            Badge::Hdr => String::from("HDR"),
+            Badge::TenBit => String::from("10-bit"),
+            Badge::Transform(4) => String::from("flip"),
+            Badge::Transform(transform) if transform > 4 => {
+                format!("flip{}", (transform - 4) as u32 * 90)
+            }
+            Badge::Transform(transform) => format!("rot{}", transform as u32 * 90),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AppliedMode {
+    pub resolution: String,
+    pub refresh_rate: f64,
+    pub scale: i32,
+    /// Custom timings the mode was set with, if it isn't an advertised one.
+    pub modeline: Option<Modeline>,
+}
+
+impl AppliedMode {
+    pub fn of(config: &MonitorConfig) -> Self {
+        Self {
+            resolution: config.resolution.clone(),
+            refresh_rate: config.refresh_rate,
+            scale: config.scale,
+            modeline: config.modeline_spec().and(config.modeline.clone()),
+        }
+    }
+}
+
+/// Connector prefixes used by built-in laptop panels.
+const INTERNAL_PREFIXES: [&str; 3] = ["eDP", "LVDS", "DSI"];
+
+impl Monitor {
+    pub fn power_state(&self) -> PowerState {
+        match (self.active, self.dpms_on) {
+            (false, _) => PowerState::Disabled,
+            (true, false) => PowerState::Sleeping,
+            (true, true) => PowerState::On,
+        }
+    }
+
+    /// What the output is running with beyond mode and scale: VRR, HDR,
+    /// 10-bit color and any rotation. Empty for disabled outputs, whose
+    /// reported state is stale.
+    pub fn badges(&self) -> Vec<Badge> {
+        if !self.active {
+            return Vec::new();
+        }
+        let raw = &self.raw;
+        let mut badges = Vec::new();
+        if raw["vrr"].as_bool().unwrap_or(false) {
+            badges.push(Badge::Vrr);
+        }
+        if raw["colorManagementPreset"].as_str().is_some_and(|preset| {
+            matches!(
+                ColorMode::from_keyword(preset),
+                ColorMode::Hdr | ColorMode::HdrEdid
+            )
+        }) {
+            badges.push(Badge::Hdr);
+        }
+        // 10-bit formats are reported as e.g. XRGB2101010.
+        if raw["currentFormat"]
+            .as_str()
+            .is_some_and(|format| format.contains("2101010"))
+        {
+            badges.push(Badge::TenBit);
+        }
+        if let Some(transform @ 1..=7) = raw["transform"].as_u64() {
+            badges.push(Badge::Transform(transform as u8));
+        }
+        badges
+    }
+
+    /// Whether `pattern` names this output the way a `monitor=` rule does:
+    /// by connector, or `desc:` and the start of its EDID description.
+    pub fn matches(&self, pattern: &str) -> bool {
+        match pattern.strip_prefix("desc:") {
+            Some(description) => self.description.starts_with(description.trim()),
+            None => self.name == pattern,
+        }
+    }
+
+    /// Headless outputs created with `hyprctl output create headless`.
+    pub fn is_virtual(&self) -> bool {
+        self.name.starts_with("HEADLESS-") || self.description.contains("Headless")
+    }
+
+    pub fn is_internal(&self) -> bool {
+        INTERNAL_PREFIXES
+            .iter()
+            .any(|prefix| self.name.starts_with(prefix))
+    }
+
+    /// Pixel density for `resolution` based on the physical size reported by
+    /// the EDID. Returns `None` for outputs without a known size (projectors,
+    /// headless outputs).
+    pub fn dpi(&self, resolution: &str) -> Option<f64> {
+        if self.physical_width == 0 || self.physical_height == 0 {
+            return None;
+        }
+        let (w, h) = parse_resolution(resolution)?;
+        let diagonal_px = (w as f64).hypot(h as f64);
+        let diagonal_in = (self.physical_width as f64).hypot(self.physical_height as f64) / 25.4;
+        Some(diagonal_px / diagonal_in)
+    }
+
+    /// The friendly name `aliases` give this monitor, looked up by its
+    /// serial number first and its EDID description second.
+    pub fn alias<'a>(&self, aliases: &'a BTreeMap<String, String>) -> Option<&'a str> {
+        self.raw["serial"]
+            .as_str()
+            .filter(|serial| !serial.is_empty())
+            .and_then(|serial| aliases.get(serial))
+            .or_else(|| aliases.get(&self.description))
+            .map(String::as_str)
+    }
+
+    /// The mode field of a `monitor` rule for `config`, with the rate
+    /// written exactly as this monitor advertises it.
+    pub fn mode_spec(&self, config: &MonitorConfig) -> String {
+        let spec = self
+            .modes
+            .get(&config.resolution)
+            .and_then(|rates| closest_rate(rates, config.refresh_rate, 0.0005))
+            .map_or_else(|| rate_spec(config.refresh_rate), |rate| rate.spec.clone());
+        format!("{}@{}", config.resolution, spec)
+    }
+
+    /// The full `monitor` rule for `config`: its custom modeline if it has
+    /// one, the advertised mode otherwise.
+    pub fn rule(&self, config: &MonitorConfig) -> String {
+        let mode = config
+            .modeline_spec()
+            .unwrap_or_else(|| self.mode_spec(config));
+        config.rule_with_mode(&self.name, &mode)
+    }
+
+    /// The `availableModes` token, e.g. `2560x1440@143.91Hz`, for a parsed
+    /// resolution and rate, exactly as Hyprland advertised it.
+    pub fn advertised_mode(&self, resolution: &str, rate: f64) -> Option<&str> {
+        self.raw["availableModes"]
+            .as_array()?
+            .iter()
+            .filter_map(|v| v.as_str())
+            .find(|token| {
+                token.split_once('@').is_some_and(|(res, rate_str)| {
+                    res == resolution
+                        && rate_str
+                            .trim_end_matches("Hz")
+                            .parse::<f64>()
+                            .is_ok_and(|r| (r - rate).abs() < 0.0005)
+                })
+            })
+    }
+}
+
+/// A workspace as reported by `hyprctl workspaces`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workspace {
+    pub id: i64,
+    pub name: String,
+    pub monitor: String,
+    pub windows: usize,
+}
+
+impl Workspace {
+    pub fn from_json(data: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            id: data["id"].as_i64()?,
+            name: data["name"].as_str().unwrap_or_default().to_string(),
+            monitor: data["monitor"].as_str().unwrap_or_default().to_string(),
+            windows: data["windows"].as_u64().unwrap_or(0) as usize,
+        })
+    }
+}
+
+/// A workspace with windows on a particular monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceWindows {
+    pub id: i64,
+    pub name: String,
+    pub windows: usize,
+}
+
+pub fn parse_resolution(resolution: &str) -> Option<(u32, u32)> {
+    let (w, h) = resolution.split_once('x')?;
+    Some((w.parse().ok()?, h.parse().ok()?))
+}
+
+/// A refresh rate a monitor offers, along with the text it was advertised
+/// as, which is what goes into rules: `60.000` and `143.998` are written
+/// back unchanged rather than rebuilt from the parsed value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rate {
+    pub value: f64,
+    pub spec: String,
+}
+
+impl Rate {
+    /// Parses the rate half of an `availableModes` token, e.g. `59.951Hz`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let spec = text.trim_end_matches("Hz");
+        Some(Self {
+            value: spec.parse().ok()?,
+            spec: spec.to_string(),
+        })
+    }
+}
+
+impl From<f64> for Rate {
+    /// A rate nothing was advertised for, such as a headless output's.
+    fn from(value: f64) -> Self {
+        Self {
+            value,
+            spec: rate_spec(value),
+        }
+    }
+}
+
+/// The rate in `rates` nearest to `rate`, if one is within `tolerance`.
+pub fn closest_rate(rates: &[Rate], rate: f64, tolerance: f64) -> Option<&Rate> {
+    rates
+        .iter()
+        .filter(|r| (r.value - rate).abs() < tolerance)
+        .min_by(|a, b| (a.value - rate).abs().total_cmp(&(b.value - rate).abs()))
+}
+
+/// A refresh rate as written in commands and saved files when the monitor
+/// didn't advertise it. f64 prints back the shortest text that parses to
+/// the same value, so `143.998` stays `143.998`; at least two decimals are
+/// kept to match how Hyprland lists modes.
+pub fn rate_spec(rate: f64) -> String {
+    let exact = rate.to_string();
+    match exact.split_once('.') {
+        Some((_, decimals)) if decimals.len() > 2 => exact,
+        _ => format!("{:.2}", rate),
+    }
+}
+
+/// Ratios that displays are sold by, so near misses such as 2560x1080
+/// (64:27) read as the familiar 21:9.
+const COMMON_RATIOS: [(u32, u32); 8] = [
+    (16, 9),
+    (16, 10),
+    (4, 3),
+    (5, 4),
+    (3, 2),
+    (21, 9),
+    (32, 9),
+    (1, 1),
+];
+
+/// The aspect ratio of a `WxH` resolution, e.g. `16:9`: the common ratio
+/// within 3% if there is one, the exact ratio otherwise.
+pub fn aspect_ratio(resolution: &str) -> Option<String> {
+    let (w, h) = parse_resolution(resolution)?;
+    if w == 0 || h == 0 {
+        return None;
+    }
+    let ratio = w as f64 / h as f64;
+    let (w, h) = COMMON_RATIOS
+        .iter()
+        .copied()
+        .find(|&(cw, ch)| (ratio / (cw as f64 / ch as f64) - 1.0).abs() < 0.03)
+        .unwrap_or_else(|| {
+            let divisor = gcd(w, h);
+            (w / divisor, h / divisor)
+        });
+    Some(format!("{}:{}", w, h))
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Parses a layout position typed as `x,y` (e.g. `2560,0` or `-1920,0`).
+pub fn parse_position(text: &str) -> Option<(i32, i32)> {
+    let (x, y) = text.split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+/// How well a mirror's mode fits the picture it shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MirrorFit {
+    /// Same resolution as the source, shown pixel for pixel.
+    Exact,
+    /// Same aspect ratio, so the picture is scaled without borders.
+    Scaled,
+    /// No mode shares the source's aspect ratio; expect black bars.
+    Letterboxed,
+}
+
+/// Picks the mode of a mirroring monitor for showing a source running
+/// `source_resolution` at `source_rate`: the source's own resolution if the
+/// mirror has it, else its largest resolution with the same aspect ratio,
+/// else its largest resolution. The refresh rate closest to the source's is
+/// used so the copy doesn't judder.
+pub fn mirror_mode(
+    modes: &BTreeMap<String, Vec<Rate>>,
+    source_resolution: &str,
+    source_rate: f64,
+) -> Option<(String, Rate, MirrorFit)> {
+    let (sw, sh) = parse_resolution(source_resolution)?;
+    let fit = |w: u32, h: u32| {
+        if (w, h) == (sw, sh) {
+            MirrorFit::Exact
+        } else if u64::from(w) * u64::from(sh) == u64::from(h) * u64::from(sw) {
+            MirrorFit::Scaled
+        } else {
+            MirrorFit::Letterboxed
+        }
+    };
+    let rank = |fit: MirrorFit| match fit {
+        MirrorFit::Exact => 2,
+        MirrorFit::Scaled => 1,
+        MirrorFit::Letterboxed => 0,
+    };
+
+    let (resolution, fit, _) = modes
+        .keys()
+        .filter_map(|res| {
+            let (w, h) = parse_resolution(res)?;
+            Some((res, fit(w, h), u64::from(w) * u64::from(h)))
+        })
+        .max_by_key(|&(_, fit, area)| (rank(fit), area))?;
+    let rate = closest_rate(&modes[resolution], source_rate, f64::INFINITY)?;
+    Some((resolution.clone(), rate.clone(), fit))
+}
+
+/// Color management preset for the `cm` monitor option.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorMode {
+    /// Leave `cm` out of the rule and let Hyprland decide.
+    #[default]
+    Default,
+    Srgb,
+    Wide,
+    Edid,
+    Hdr,
+    HdrEdid,
+}
+
+impl ColorMode {
+    pub const ALL: [ColorMode; 6] = [
+        ColorMode::Default,
+        ColorMode::Srgb,
+        ColorMode::Wide,
+        ColorMode::Edid,
+        ColorMode::Hdr,
+        ColorMode::HdrEdid,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorMode::Default => "Default",
+            ColorMode::Srgb => "sRGB",
+            ColorMode::Wide => "Wide gamut",
+            ColorMode::Edid => "EDID",
+            ColorMode::Hdr => "HDR",
+            ColorMode::HdrEdid => "HDR (EDID)",
+        }
+    }
+
+    /// The value written after `cm,` in a monitor rule.
+    pub fn keyword(&self) -> Option<&'static str> {
+        match self {
+            ColorMode::Default => None,
+            ColorMode::Srgb => Some("srgb"),
+            ColorMode::Wide => Some("wide"),
+            ColorMode::Edid => Some("edid"),
+            ColorMode::Hdr => Some("hdr"),
+            ColorMode::HdrEdid => Some("hdredid"),
+        }
+    }
+
+    pub fn from_keyword(keyword: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.keyword() == Some(keyword))
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -18,11 +475,289 @@ pub struct MonitorConfig {
     pub scale: i32,
     pub resolution_index: usize,
     pub refresh_rate_index: usize,
-    pub dpms_on: bool,
+    pub vrr: bool,
+    /// Refresh rate to return to when leaving gaming mode.
+    pub previous_refresh_rate: Option<f64>,
+    /// VRR and tearing to return to when leaving gaming mode.
+    pub previous_vrr: Option<bool>,
+    pub previous_tearing: Option<bool>,
+    /// Pixels kept free of windows, in `addreserved` order: top, bottom,
+    /// left, right.
+    pub reserved: [u32; 4],
+    /// Explicit layout position; `None` leaves placement to Hyprland.
+    pub position: Option<(i32, i32)>,
+    /// Save a `disable` line for this monitor so it stays off after a
+    /// Hyprland reload. Set when the monitor is disabled from the TUI.
+    pub save_disabled: bool,
+    /// Include this monitor in "Save to File". Cleared for temporary
+    /// setups, such as a hotel TV, that shouldn't end up in monitors.conf.
+    pub persist: bool,
+    /// Hyprland transform, 0-7 (rotation in 90° steps, 4-7 flipped).
+    pub transform: u8,
+    /// Output bit depth when it isn't the default 8.
+    pub bitdepth: Option<u8>,
+    pub color_mode: ColorMode,
+    /// Custom timings for a mode the monitor doesn't advertise. Only used
+    /// while the resolution and rate still match it.
+    pub modeline: Option<Modeline>,
 }
 
 impl MonitorConfig {
     pub fn scale_as_float(&self) -> f64 {
         self.scale as f64 / 100.0
     }
+
+    /// Size in the layout, in logical pixels: the resolution divided by the
+    /// scale, turned sideways by 90° and 270° transforms.
+    pub fn logical_size(&self) -> Option<(u32, u32)> {
+        let (w, h) = parse_resolution(&self.resolution)?;
+        let scale = self.scale_as_float();
+        let (w, h) = (
+            (w as f64 / scale).round() as u32,
+            (h as f64 / scale).round() as u32,
+        );
+        Some(if self.transform % 2 == 1 {
+            (h, w)
+        } else {
+            (w, h)
+        })
+    }
+
+    /// The position field of a `monitor` rule: `XxY`, or `auto`.
+    pub fn position_spec(&self) -> String {
+        match self.position {
+            Some((x, y)) => format!("{}x{}", x, y),
+            None => String::from("auto"),
+        }
+    }
+
+    /// The custom modeline, written as a mode field, if the current mode is
+    /// the one it was computed for.
+    pub fn modeline_spec(&self) -> Option<String> {
+        self.modeline
+            .as_ref()
+            .filter(|modeline| {
+                modeline.resolution() == self.resolution
+                    && (modeline.refresh_rate() - self.refresh_rate).abs() < 0.01
+            })
+            .map(|modeline| format!("modeline {}", modeline))
+    }
+
+    /// The `monitor` rule for this configuration on output `name`, with
+    /// `mode` as its mode field; see [`Monitor::rule`].
+    pub fn rule_with_mode(&self, name: &str, mode: &str) -> String {
+        let mut rule = format!(
+            "{},{},{},{:.2},transform,{},vrr,{}",
+            name,
+            mode,
+            self.position_spec(),
+            self.scale_as_float(),
+            self.transform,
+            u8::from(self.vrr)
+        );
+        if let Some(bitdepth) = self.bitdepth {
+            rule.push_str(&format!(",bitdepth,{}", bitdepth));
+        }
+        if let Some(preset) = self.color_mode.keyword() {
+            rule.push_str(&format!(",cm,{}", preset));
+        }
+        rule
+    }
+
+    /// The `addreserved` rule for this configuration; all zeroes clears it.
+    pub fn reserved_rule(&self, name: &str) -> String {
+        let [top, bottom, left, right] = self.reserved;
+        format!("{},addreserved,{},{},{},{}", name, top, bottom, left, right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modes(entries: &[(&str, &[f64])]) -> BTreeMap<String, Vec<Rate>> {
+        entries
+            .iter()
+            .map(|(res, rates)| (res.to_string(), rates.iter().map(|&r| r.into()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn mirror_prefers_source_resolution_then_aspect_ratio() {
+        let tv = modes(&[
+            ("3840x2160", &[30.0, 60.0]),
+            ("1920x1080", &[50.0, 60.0, 120.0]),
+            ("1280x1024", &[60.0]),
+        ]);
+        assert_eq!(
+            mirror_mode(&tv, "1920x1080", 59.95),
+            Some(("1920x1080".to_string(), 60.0.into(), MirrorFit::Exact))
+        );
+        assert_eq!(
+            mirror_mode(&tv, "2560x1440", 144.0),
+            Some(("3840x2160".to_string(), 60.0.into(), MirrorFit::Scaled))
+        );
+        assert_eq!(
+            mirror_mode(&tv, "2560x1600", 60.0),
+            Some(("3840x2160".to_string(), 60.0.into(), MirrorFit::Letterboxed))
+        );
+        assert_eq!(mirror_mode(&BTreeMap::new(), "1920x1080", 60.0), None);
+    }
+
+    #[test]
+    fn rates_keep_their_advertised_precision() {
+        assert_eq!(rate_spec(143.998), "143.998");
+        assert_eq!(rate_spec(59.95), "59.95");
+        assert_eq!(rate_spec(60.0), "60.00");
+        assert_eq!(rate_spec(144.0), "144.00");
+    }
+
+    #[test]
+    fn rules_carry_the_advertised_rate() {
+        let mut monitor: Monitor =
+            serde_json::from_value(serde_json::json!({ "name": "DP-1" })).expect("monitor parses");
+        monitor.modes = [(
+            String::from("2560x1440"),
+            ["144.00", "143.998", "60.000"]
+                .iter()
+                .filter_map(|text| Rate::parse(text))
+                .collect(),
+        )]
+        .into_iter()
+        .collect();
+        let mut config = MonitorConfig {
+            resolution: String::from("2560x1440"),
+            refresh_rate: 60.0,
+            scale: 100,
+            resolution_index: 0,
+            refresh_rate_index: 0,
+            vrr: false,
+            previous_refresh_rate: None,
+            previous_vrr: None,
+            previous_tearing: None,
+            reserved: [0; 4],
+            position: None,
+            save_disabled: false,
+            persist: true,
+            transform: 0,
+            bitdepth: None,
+            color_mode: ColorMode::Default,
+            modeline: None,
+        };
+        assert_eq!(monitor.mode_spec(&config), "2560x1440@60.000");
+        config.refresh_rate = 143.998;
+        assert_eq!(monitor.mode_spec(&config), "2560x1440@143.998");
+        config.refresh_rate = 144.0;
+        assert_eq!(monitor.mode_spec(&config), "2560x1440@144.00");
+        config.refresh_rate = 75.0;
+        assert_eq!(monitor.mode_spec(&config), "2560x1440@75.00");
+    }
+
+    #[test]
+    fn aliases_match_serial_before_description() {
+        let raw = serde_json::json!({
+            "name": "DP-1",
+            "description": "Dell Inc. DELL U2720Q 9MTF423",
+            "serial": "9MTF423",
+        });
+        let mut monitor: Monitor = serde_json::from_value(raw.clone()).expect("monitor parses");
+        monitor.raw = raw;
+        let mut aliases: BTreeMap<String, String> = [(
+            String::from("Dell Inc. DELL U2720Q 9MTF423"),
+            String::from("Left Dell"),
+        )]
+        .into_iter()
+        .collect();
+        assert_eq!(monitor.alias(&aliases), Some("Left Dell"));
+        aliases.insert(String::from("9MTF423"), String::from("Desk"));
+        assert_eq!(monitor.alias(&aliases), Some("Desk"));
+        assert_eq!(monitor.alias(&BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn advertised_mode_is_the_original_token() {
+        let mut monitor: Monitor =
+            serde_json::from_value(serde_json::json!({ "name": "DP-1" })).expect("monitor parses");
+        monitor.raw = serde_json::json!({
+            "availableModes": ["2560x1440@143.91Hz", "2560x1440@59.951Hz", "1920x1080@60.00Hz"]
+        });
+        assert_eq!(
+            monitor.advertised_mode("2560x1440", 59.951),
+            Some("2560x1440@59.951Hz")
+        );
+        assert_eq!(
+            monitor.advertised_mode("1920x1080", 60.0),
+            Some("1920x1080@60.00Hz")
+        );
+        assert_eq!(monitor.advertised_mode("1920x1080", 144.0), None);
+    }
+
+    #[test]
+    fn badges_come_from_the_reported_state() {
+        let mut monitor: Monitor =
+            serde_json::from_value(serde_json::json!({ "name": "DP-1", "active": true }))
+                .expect("monitor parses");
+        monitor.raw = serde_json::json!({
+            "vrr": true,
+            "colorManagementPreset": "hdr",
+            "currentFormat": "XRGB2101010",
+            "transform": 5,
+        });
+        let badges = monitor.badges();
+        assert_eq!(
+            badges,
+            [Badge::Vrr, Badge::Hdr, Badge::TenBit, Badge::Transform(5)]
+        );
+        let labels: Vec<String> = badges.into_iter().map(Badge::label).collect();
+        assert_eq!(labels, ["VRR", "HDR", "10-bit", "flip90"]);
+
+        monitor.raw = serde_json::json!({
+            "vrr": false,
+            "colorManagementPreset": "srgb",
+            "currentFormat": "XRGB8888",
+            "transform": 0,
+        });
+        assert_eq!(monitor.badges(), []);
+        monitor.raw["vrr"] = serde_json::json!(true);
+        monitor.active = false;
+        assert_eq!(monitor.badges(), []);
+    }
+
+    #[test]
+    fn aspect_ratio_rounds_to_marketed_ratios() {
+        assert_eq!(aspect_ratio("1920x1080").as_deref(), Some("16:9"));
+        assert_eq!(aspect_ratio("2560x1600").as_deref(), Some("16:10"));
+        assert_eq!(aspect_ratio("3440x1440").as_deref(), Some("21:9"));
+        assert_eq!(aspect_ratio("1366x768").as_deref(), Some("16:9"));
+        assert_eq!(aspect_ratio("1280x1024").as_deref(), Some("5:4"));
+        assert_eq!(aspect_ratio("2256x1504").as_deref(), Some("3:2"));
+        assert_eq!(aspect_ratio("1000x100").as_deref(), Some("10:1"));
+        assert_eq!(aspect_ratio("preferred"), None);
+    }
+
+    #[test]
+    fn logical_size_follows_scale_and_rotation() {
+        let mut config = MonitorConfig {
+            resolution: String::from("2560x1440"),
+            refresh_rate: 60.0,
+            scale: 125,
+            resolution_index: 0,
+            refresh_rate_index: 0,
+            vrr: false,
+            previous_refresh_rate: None,
+            previous_vrr: None,
+            previous_tearing: None,
+            reserved: [0; 4],
+            position: None,
+            save_disabled: false,
+            persist: true,
+            transform: 0,
+            bitdepth: None,
+            color_mode: ColorMode::Default,
+            modeline: None,
+        };
+        assert_eq!(config.logical_size(), Some((2048, 1152)));
+        config.transform = 1;
+        assert_eq!(config.logical_size(), Some((1152, 2048)));
+    }
 }
@@ -0,0 +1,384 @@
+use clap::ValueEnum;
+use std::{fs, io, path::PathBuf};
+
+use crate::app::App;
+use crate::check;
+use crate::hyprconf;
+use crate::message::Severity;
+use crate::version::Feature;
+
+/// Options both syntaxes spell the same way after the mode, position and
+/// scale, as `key,value` pairs in a `monitor=` line and `key = value` lines
+/// in a `monitorv2` block.
+const SHARED_OPTIONS: [&str; 7] = [
+    "transform",
+    "mirror",
+    "bitdepth",
+    "vrr",
+    "cm",
+    "sdrbrightness",
+    "sdrsaturation",
+];
+
+/// How monitor rules are written in Hyprland's config.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Syntax {
+    /// One `monitor=NAME,MODE,POSITION,SCALE,...` line per rule
+    Legacy,
+    /// `monitorv2 { ... }` blocks with one option per line (Hyprland 0.50+)
+    V2,
+}
+
+/// A config file rewritten into one syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    pub contents: String,
+    /// How many rules were rewritten.
+    pub converted: usize,
+    /// 1-based lines of rules left as they were, because the other syntax
+    /// can't say the same thing.
+    pub kept: Vec<usize>,
+}
+
+/// `contents` with its rules rewritten in `syntax`. Everything else,
+/// comments included, stays where it was.
+pub fn migrate(contents: &str, syntax: Syntax) -> Migration {
+    match syntax {
+        Syntax::Legacy => to_legacy(contents),
+        Syntax::V2 => to_v2(contents),
+    }
+}
+
+/// `contents` with every `monitor=` line that has a block equivalent
+/// rewritten as a `monitorv2` block. A reserved area joins the blocks of
+/// its output's mode lines, since a block has no way to only add one.
+pub fn to_v2(contents: &str) -> Migration {
+    let rules: Vec<Option<Vec<&str>>> = contents.lines().map(legacy_fields).collect();
+    let has_mode_block = |name: &str| {
+        rules
+            .iter()
+            .flatten()
+            .any(|fields| fields[0] == name && mode_block(fields, None).is_some())
+    };
+    let reserved = |name: &str| {
+        rules
+            .iter()
+            .flatten()
+            .rev()
+            .find(|fields| fields[0] == name && is_reserved(fields))
+            .map(|fields| fields[2..].join(", "))
+    };
+
+    let mut migration = Migration::new();
+    for (i, (line, fields)) in contents.lines().zip(&rules).enumerate() {
+        let Some(fields) = fields else {
+            migration.push(line);
+            continue;
+        };
+        if is_reserved(fields) && has_mode_block(fields[0]) {
+            migration.converted += 1;
+            continue;
+        }
+        match mode_block(fields, reserved(fields[0]).as_deref()) {
+            Some(block) => {
+                migration.converted += 1;
+                migration.push("monitorv2 {");
+                for (key, value) in block {
+                    migration.push(&format!("    {} = {}", key, value));
+                }
+                migration.push("}");
+            }
+            None => {
+                migration.kept.push(i + 1);
+                migration.push(line);
+            }
+        }
+    }
+    migration.finish(contents)
+}
+
+/// `contents` with every `monitorv2` block that has a line equivalent
+/// rewritten as `monitor=` lines: the mode line, then a reserved-area line
+/// if the block had one.
+pub fn to_legacy(contents: &str) -> Migration {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut migration = Migration::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if !opens_block(lines[i]) {
+            migration.push(lines[i]);
+            i += 1;
+            continue;
+        }
+        let Some(len) = lines[i..].iter().position(|line| line.trim() == "}") else {
+            // An unclosed block runs to the end; Hyprland rejects it too.
+            migration.kept.push(i + 1);
+            for line in &lines[i..] {
+                migration.push(line);
+            }
+            break;
+        };
+        let block = &lines[i..=i + len];
+        match legacy_lines(&block[1..len]) {
+            Some(rules) => {
+                migration.converted += 1;
+                for rule in rules {
+                    migration.push(&format!("monitor={}", rule));
+                }
+            }
+            None => {
+                migration.kept.push(i + 1);
+                for line in block {
+                    migration.push(line);
+                }
+            }
+        }
+        i += len + 1;
+    }
+    migration.finish(contents)
+}
+
+/// Runs `hypr-tui migrate`: rewrites the rules of `file` (the saved
+/// monitor configuration by default) in `syntax`, then prints the result or,
+/// with `write`, replaces the file after backing it up. Nothing is written
+/// unless both versions come down to the same rules, the running Hyprland
+/// reads the new syntax, and its outputs raise no errors with them.
+pub fn run(file: Option<PathBuf>, syntax: Syntax, write: bool) -> io::Result<()> {
+    let path = file.map_or_else(hyprconf::monitors_conf_path, Ok)?;
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+    let migration = migrate(&contents, syntax);
+    let rules = |contents: &str| hyprconf::saved_rules("", &to_legacy(contents).contents);
+    if rules(&migration.contents) != rules(&contents) {
+        return Err(io::Error::other(
+            "The rewritten rules would not match the original ones; nothing changed",
+        ));
+    }
+
+    match App::new() {
+        Ok(app) => {
+            if syntax == Syntax::V2 {
+                app.require(Feature::MonitorV2).map_err(io::Error::other)?;
+            }
+            // Checked as lines so the line numbers match whichever side is
+            // written that way.
+            let lines = match syntax {
+                Syntax::Legacy => &migration.contents,
+                Syntax::V2 => &contents,
+            };
+            let problems = check::check("", &to_legacy(lines).contents, Some(&app.monitors));
+            for problem in &problems {
+                eprintln!("{}", problem.describe());
+            }
+            if problems
+                .iter()
+                .any(|problem| problem.severity == Severity::Error)
+            {
+                return Err(io::Error::other(
+                    "Fix the errors above before migrating; nothing changed",
+                ));
+            }
+        }
+        Err(_) => eprintln!("Hyprland isn't running; not checking the result against it"),
+    }
+
+    let summary = format!(
+        "Rewrote {} rules of {}",
+        migration.converted,
+        path.display()
+    );
+    let kept = match migration.kept.as_slice() {
+        [] => String::new(),
+        lines => format!(
+            "; kept these lines as they were: {}",
+            lines
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    if write {
+        fs::write(path.with_extension("conf.bak"), &contents)?;
+        fs::write(&path, &migration.contents)?;
+        println!("{}{}", summary, kept);
+    } else {
+        print!("{}", migration.contents);
+        eprintln!("{}{} (not written; pass --write)", summary, kept);
+    }
+    Ok(())
+}
+
+impl Migration {
+    fn new() -> Self {
+        Self {
+            contents: String::new(),
+            converted: 0,
+            kept: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, line: &str) {
+        self.contents.push_str(line);
+        self.contents.push('\n');
+    }
+
+    /// Keeps a missing final newline missing.
+    fn finish(mut self, original: &str) -> Self {
+        if !original.ends_with('\n') {
+            self.contents.pop();
+        }
+        self
+    }
+}
+
+/// The trimmed fields of a `monitor=` line.
+fn legacy_fields(line: &str) -> Option<Vec<&str>> {
+    let (key, value) = line.split_once('=')?;
+    (key.trim() == "monitor").then(|| value.split(',').map(str::trim).collect())
+}
+
+fn is_reserved(fields: &[&str]) -> bool {
+    fields.get(1) == Some(&"addreserved") && fields.len() == 6
+}
+
+/// The block for a mode or `disable` line, `None` for the fallback rule
+/// (which names no output) and lines a block can't express.
+fn mode_block(fields: &[&str], reserved: Option<&str>) -> Option<Vec<(String, String)>> {
+    if fields[0].is_empty() {
+        return None;
+    }
+    let pair = |key: &str, value: &str| (key.to_string(), value.to_string());
+    if fields[1..] == ["disable"] {
+        return Some(vec![pair("output", fields[0]), pair("disabled", "true")]);
+    }
+    let [name, mode, position, scale, options @ ..] = fields else {
+        return None;
+    };
+    if *mode == "addreserved" || !options.len().is_multiple_of(2) {
+        return None;
+    }
+    let mut block = vec![
+        pair("output", name),
+        pair("mode", mode),
+        pair("position", position),
+        pair("scale", scale),
+    ];
+    for option in options.chunks(2) {
+        if !SHARED_OPTIONS.contains(&option[0]) {
+            return None;
+        }
+        block.push(pair(option[0], option[1]));
+    }
+    if let Some(reserved) = reserved {
+        block.push(pair("addreserved", reserved));
+    }
+    Some(block)
+}
+
+fn opens_block(line: &str) -> bool {
+    line.trim()
+        .strip_prefix("monitorv2")
+        .is_some_and(|rest| rest.trim() == "{")
+}
+
+/// The `monitor=` values for the `key = value` lines inside a block, `None`
+/// if it has no output or an option lines can't express.
+fn legacy_lines(body: &[&str]) -> Option<Vec<String>> {
+    let mut output = None;
+    let mut mode = "preferred";
+    let mut position = "auto";
+    let mut scale = "auto";
+    let mut disabled = false;
+    let mut reserved = None;
+    let mut options = Vec::new();
+    for line in body {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = line.split_once('=')?;
+        let value = value.trim();
+        match key.trim() {
+            "output" => output = Some(value),
+            "mode" => mode = value,
+            "position" => position = value,
+            "scale" => scale = value,
+            "disabled" => disabled = matches!(value, "true" | "yes" | "on" | "1"),
+            "addreserved" => reserved = Some(value),
+            key if SHARED_OPTIONS.contains(&key) => options.push(format!("{},{}", key, value)),
+            _ => return None,
+        }
+    }
+    let output = output.filter(|output| !output.is_empty())?;
+    if disabled {
+        return Some(vec![format!("{},disable", output)]);
+    }
+    let mut rule = format!("{},{},{},{}", output, mode, position, scale);
+    for option in options {
+        rule.push(',');
+        rule.push_str(&option);
+    }
+    let mut rules = vec![rule];
+    if let Some(reserved) = reserved {
+        let sides: Vec<&str> = reserved.split(',').map(str::trim).collect();
+        if sides.len() != 4 {
+            return None;
+        }
+        rules.push(format!("{},addreserved,{}", output, sides.join(",")));
+    }
+    Some(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hyprconf;
+
+    const SAVED: &str = "\
+# Monitor settings generated by hypr-tui
+monitor=eDP-1,1920x1200@60.00,0x0,1.25,transform,0,vrr,0
+monitor=eDP-1,addreserved,0,30,0,0
+monitor = DP-1 , 2560x1440@144.00 , -2560x0 , 1.00
+monitor=DP-10,disable
+monitor=,preferred,auto,1
+monitor=HDMI-A-1,preferred,auto,1,icc,/tmp/x.icc";
+
+    #[test]
+    fn converts_both_ways_without_changing_the_rules() {
+        let v2 = to_v2(SAVED);
+        assert_eq!(v2.converted, 4);
+        assert_eq!(v2.kept, [6, 7]);
+        assert!(v2.contents.starts_with(
+            "# Monitor settings generated by hypr-tui\nmonitorv2 {\n    output = eDP-1\n    mode = 1920x1200@60.00\n    position = 0x0\n    scale = 1.25\n    transform = 0\n    vrr = 0\n    addreserved = 0, 30, 0, 0\n}\nmonitorv2 {\n    output = DP-1\n"
+        ));
+        assert!(v2
+            .contents
+            .contains("monitorv2 {\n    output = DP-10\n    disabled = true\n}\n"));
+        assert!(v2.contents.ends_with(
+            "\nmonitor=,preferred,auto,1\nmonitor=HDMI-A-1,preferred,auto,1,icc,/tmp/x.icc"
+        ));
+
+        let legacy = to_legacy(&v2.contents);
+        assert_eq!(legacy.converted, 3);
+        assert!(legacy.kept.is_empty());
+        assert_eq!(
+            hyprconf::saved_rules("", &legacy.contents),
+            hyprconf::saved_rules("", SAVED)
+        );
+        assert_eq!(to_v2(&legacy.contents).contents, v2.contents);
+    }
+
+    #[test]
+    fn keeps_blocks_lines_cannot_express() {
+        let contents = "monitorv2 {\n  output = DP-1\n  supports_hdr = 1\n}\nmonitorv2 {\n  mode = preferred\n}\n";
+        let legacy = to_legacy(contents);
+        assert_eq!(legacy.converted, 0);
+        assert_eq!(legacy.kept, [1, 5]);
+        assert_eq!(legacy.contents, contents);
+        assert_eq!(
+            to_legacy("monitorv2{\n output = DP-2 # work\n}").contents,
+            "monitor=DP-2,preferred,auto,auto"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Deserialize;
+use std::process::{Command, Stdio};
+
+const NOTIFY_TIMEOUT_MS: u32 = 5000;
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyBackend {
+    #[default]
+    NotifySend,
+    Hyprctl,
+}
+
+/// Desktop notifications for headless (CLI/daemon) actions.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    pub backend: NotifyBackend,
+}
+
+/// Shows the outcome of an action as a desktop notification. Delivery is best
+/// effort: a missing notification daemon must not turn a successful apply
+/// into a failure.
+pub fn send(settings: &NotificationSettings, result: &Result<String, String>) {
+    if !settings.enabled {
+        return;
+    }
+
+    let (message, success) = match result {
+        Ok(message) => (message, true),
+        Err(message) => (message, false),
+    };
+
+    let mut command = match settings.backend {
+        NotifyBackend::NotifySend => {
+            let mut command = Command::new("notify-send");
+            command.args([
+                "--app-name=Hyprmonitor",
+                if success {
+                    "--urgency=normal"
+                } else {
+                    "--urgency=critical"
+                },
+                &format!("--expire-time={}", NOTIFY_TIMEOUT_MS),
+                "Hyprmonitor",
+                message,
+            ]);
+            command
+        }
+        NotifyBackend::Hyprctl => {
+            // Icons: 5 = ok, 3 = error; color 0 keeps the default.
+            let mut command = Command::new("hyprctl");
+            command.args([
+                "notify",
+                if success { "5" } else { "3" },
+                &NOTIFY_TIMEOUT_MS.to_string(),
+                "0",
+                message,
+            ]);
+            command
+        }
+    };
+
+    let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+}
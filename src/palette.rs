@@ -0,0 +1,214 @@
+use ratatui::widgets::ListState;
+
+use crate::app::{Motion, Tab};
+use crate::i18n::{tr, tr_args};
+use crate::picker::fuzzy_match;
+use crate::presets::{MonitorPreset, Preset};
+use crate::template::Template;
+
+/// Something that can be triggered by name from the command palette.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    PickMode,
+    CustomMode,
+    VirtualResolution,
+    SuggestScale,
+    ApplyChanges,
+    QueueChanges,
+    ReviewChanges,
+    SetPosition,
+    SetMain,
+    ExtendLeft,
+    ExtendRight,
+    ToggleMirror,
+    MonitorPreset(MonitorPreset),
+    ToggleBlackScreen,
+    WakeAll,
+    DisableMonitor,
+    Solo,
+    EnableAll,
+    TogglePersist,
+    SaveToFile,
+    ResetToSaved,
+    Identify,
+    ShowDetails,
+    ExportSvg,
+    ToggleMonitorOrder,
+    ShowConflicts,
+    CycleTheme,
+    FocusMonitor,
+    ToggleModeFilter,
+    ToggleLiveMode,
+    AddVirtual,
+    RemoveVirtual,
+    Preset(Preset),
+    Template(Template),
+    ToggleGroup(String),
+    ScaleGroup(String),
+    ToggleGroupDpms(String),
+    DisableGroup(String),
+    ApplyProfile(String),
+    SwitchTab(Tab),
+}
+
+impl Action {
+    /// Every action, with one entry per preset, saved profile and tab, and
+    /// a set per monitor group.
+    pub fn all(profiles: &[String], groups: &[String]) -> Vec<Action> {
+        let mut actions = vec![
+            Action::PickMode,
+            Action::CustomMode,
+            Action::VirtualResolution,
+            Action::SuggestScale,
+            Action::ApplyChanges,
+            Action::QueueChanges,
+            Action::ReviewChanges,
+            Action::SetPosition,
+            Action::SetMain,
+            Action::ExtendLeft,
+            Action::ExtendRight,
+            Action::ToggleMirror,
+            Action::MonitorPreset(MonitorPreset::Gaming),
+            Action::MonitorPreset(MonitorPreset::Desktop),
+            Action::ToggleBlackScreen,
+            Action::WakeAll,
+            Action::DisableMonitor,
+            Action::Solo,
+            Action::EnableAll,
+            Action::TogglePersist,
+            Action::SaveToFile,
+            Action::ResetToSaved,
+            Action::Identify,
+            Action::ShowDetails,
+            Action::ExportSvg,
+            Action::ToggleMonitorOrder,
+            Action::ShowConflicts,
+            Action::CycleTheme,
+            Action::FocusMonitor,
+            Action::ToggleModeFilter,
+            Action::ToggleLiveMode,
+            Action::AddVirtual,
+            Action::RemoveVirtual,
+        ];
+        actions.extend(Preset::ALL.iter().map(|&preset| Action::Preset(preset)));
+        actions.extend(
+            Template::ALL
+                .iter()
+                .map(|&template| Action::Template(template)),
+        );
+        for group in groups {
+            actions.extend([
+                Action::ToggleGroup(group.clone()),
+                Action::ScaleGroup(group.clone()),
+                Action::ToggleGroupDpms(group.clone()),
+                Action::DisableGroup(group.clone()),
+            ]);
+        }
+        actions.extend(profiles.iter().cloned().map(Action::ApplyProfile));
+        actions.extend(Tab::ALL.iter().map(|&tab| Action::SwitchTab(tab)));
+        actions
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Action::PickMode => tr("palette.pick_mode").to_string(),
+            Action::CustomMode => tr("palette.custom_mode").to_string(),
+            Action::VirtualResolution => tr("palette.virtual_resolution").to_string(),
+            Action::SuggestScale => tr("palette.suggest_scale").to_string(),
+            Action::ApplyChanges => tr("palette.apply_changes").to_string(),
+            Action::QueueChanges => tr("palette.queue_changes").to_string(),
+            Action::ReviewChanges => tr("palette.review_changes").to_string(),
+            Action::SetPosition => tr("palette.set_position").to_string(),
+            Action::SetMain => tr("palette.set_main").to_string(),
+            Action::ExtendLeft => tr("palette.extend_left").to_string(),
+            Action::ExtendRight => tr("palette.extend_right").to_string(),
+            Action::ToggleMirror => tr("palette.toggle_mirror").to_string(),
+            Action::MonitorPreset(preset) => preset.label().to_string(),
+            Action::ToggleBlackScreen => tr("palette.toggle_black_screen").to_string(),
+            Action::WakeAll => tr("palette.wake_all").to_string(),
+            Action::Solo => tr("palette.solo").to_string(),
+            Action::EnableAll => tr("palette.enable_all").to_string(),
+            Action::DisableMonitor => tr("palette.disable_monitor").to_string(),
+            Action::TogglePersist => tr("palette.toggle_persist").to_string(),
+            Action::SaveToFile => tr("palette.save_to_file").to_string(),
+            Action::ResetToSaved => tr("palette.reset_to_saved").to_string(),
+            Action::Identify => tr("palette.identify").to_string(),
+            Action::ShowDetails => tr("palette.show_details").to_string(),
+            Action::ExportSvg => tr("palette.export_svg").to_string(),
+            Action::ToggleMonitorOrder => tr("palette.toggle_monitor_order").to_string(),
+            Action::ShowConflicts => tr("palette.show_conflicts").to_string(),
+            Action::CycleTheme => tr("palette.cycle_theme").to_string(),
+            Action::FocusMonitor => tr("palette.focus_monitor").to_string(),
+            Action::ToggleModeFilter => tr("palette.toggle_mode_filter").to_string(),
+            Action::ToggleLiveMode => tr("palette.toggle_live_mode").to_string(),
+            Action::AddVirtual => tr("palette.add_virtual").to_string(),
+            Action::RemoveVirtual => tr("palette.remove_virtual").to_string(),
+            Action::Preset(preset) => tr_args("palette.preset", &[("preset", &preset.label())]),
+            Action::Template(template) => {
+                tr_args("palette.template", &[("template", &template.label())])
+            }
+            Action::ToggleGroup(group) => tr_args("palette.toggle_group", &[("group", group)]),
+            Action::ScaleGroup(group) => tr_args("palette.scale_group", &[("group", group)]),
+            Action::ToggleGroupDpms(group) => {
+                tr_args("palette.toggle_group_dpms", &[("group", group)])
+            }
+            Action::DisableGroup(group) => tr_args("palette.disable_group", &[("group", group)]),
+            Action::ApplyProfile(name) => tr_args("palette.apply_profile", &[("profile", name)]),
+            Action::SwitchTab(tab) => tr_args("palette.switch_tab", &[("tab", &tab.title())]),
+        }
+    }
+}
+
+/// Popup listing every action by name, narrowed down by a fuzzy filter.
+pub struct Palette {
+    pub filter: String,
+    pub state: ListState,
+    entries: Vec<Action>,
+}
+
+impl Palette {
+    pub fn new(entries: Vec<Action>) -> Self {
+        let mut state = ListState::default();
+        state.select(if entries.is_empty() { None } else { Some(0) });
+
+        Self {
+            filter: String::new(),
+            state,
+            entries,
+        }
+    }
+
+    /// Entries matching the current filter, in display order.
+    pub fn visible(&self) -> Vec<&Action> {
+        self.entries
+            .iter()
+            .filter(|action| fuzzy_match(&self.filter, &action.label()))
+            .collect()
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.reset_selection();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.filter.pop();
+        self.reset_selection();
+    }
+
+    pub fn navigate(&mut self, motion: Motion) {
+        let count = self.visible().len();
+        self.state
+            .select(motion.apply(self.state.selected(), count));
+    }
+
+    pub fn selected(&self) -> Option<Action> {
+        let idx = self.state.selected()?;
+        self.visible().get(idx).map(|action| (*action).clone())
+    }
+
+    fn reset_selection(&mut self) {
+        let has_entries = !self.visible().is_empty();
+        self.state.select(if has_entries { Some(0) } else { None });
+    }
+}
@@ -0,0 +1,92 @@
+use ratatui::widgets::ListState;
+use std::collections::BTreeMap;
+
+use crate::app::Motion;
+use crate::monitor::{parse_resolution, Rate};
+
+/// Popup listing every advertised mode of a monitor, narrowed down by a
+/// fuzzy filter typed by the user.
+pub struct ModePicker {
+    /// Name of the monitor the modes are for.
+    pub monitor: String,
+    pub filter: String,
+    pub state: ListState,
+    entries: Vec<(String, f64)>,
+}
+
+impl ModePicker {
+    pub fn new(
+        monitor: &str,
+        modes: &BTreeMap<String, Vec<Rate>>,
+        resolution: &str,
+        refresh_rate: f64,
+    ) -> Self {
+        let mut resolutions: Vec<_> = modes.keys().collect();
+        resolutions.sort_by_key(|res| {
+            std::cmp::Reverse(parse_resolution(res).map_or(0, |(w, h)| w as u64 * h as u64))
+        });
+
+        let entries: Vec<(String, f64)> = resolutions
+            .into_iter()
+            .flat_map(|res| modes[res].iter().map(move |rate| (res.clone(), rate.value)))
+            .collect();
+
+        let current = entries
+            .iter()
+            .position(|(res, rate)| res == resolution && (rate - refresh_rate).abs() < 0.01);
+
+        let mut state = ListState::default();
+        state.select(current.or(if entries.is_empty() { None } else { Some(0) }));
+
+        Self {
+            monitor: monitor.to_string(),
+            filter: String::new(),
+            state,
+            entries,
+        }
+    }
+
+    /// Entries matching the current filter, in display order.
+    pub fn visible(&self) -> Vec<&(String, f64)> {
+        self.entries
+            .iter()
+            .filter(|(res, rate)| fuzzy_match(&self.filter, &format!("{}@{:.2}", res, rate)))
+            .collect()
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.reset_selection();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.filter.pop();
+        self.reset_selection();
+    }
+
+    pub fn navigate(&mut self, motion: Motion) {
+        let count = self.visible().len();
+        self.state
+            .select(motion.apply(self.state.selected(), count));
+    }
+
+    pub fn selected(&self) -> Option<(String, f64)> {
+        let idx = self.state.selected()?;
+        self.visible().get(idx).map(|entry| (*entry).clone())
+    }
+
+    fn reset_selection(&mut self) {
+        let has_entries = !self.visible().is_empty();
+        self.state.select(if has_entries { Some(0) } else { None });
+    }
+}
+
+/// Case-insensitive subsequence match: every character of `pattern` must
+/// appear in `text` in order, not necessarily adjacent.
+pub fn fuzzy_match(pattern: &str, text: &str) -> bool {
+    let mut text_chars = text.chars().flat_map(char::to_lowercase);
+    pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|p| text_chars.any(|t| t == p))
+}
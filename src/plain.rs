@@ -0,0 +1,320 @@
+use std::io::{self, BufRead, Write};
+
+use crate::app::App;
+use crate::control::ControlServer;
+use crate::message::Severity;
+use crate::monitor::{self, Monitor, MonitorConfig, PowerState};
+
+const HELP: &str = "Type a monitor number to select it, or a command: \
+l list monitors, m change mode, a apply, s save, h help, q quit.";
+
+/// The line-based interface started with `--plain`, for screen readers and
+/// braille displays: numbered lists and one question at a time on standard
+/// output, answered with a line on standard input. Nothing is redrawn, so
+/// every line stays in the terminal's history.
+pub fn run() -> io::Result<()> {
+    ControlServer::ensure_single_instance()?;
+    let stdin = io::stdin();
+    let mut session = Session {
+        app: App::new()?,
+        selected: 0,
+        input: stdin.lock(),
+        output: io::stdout(),
+    };
+    session.run()
+}
+
+struct Session<R, W> {
+    app: App,
+    selected: usize,
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Session<R, W> {
+    fn run(&mut self) -> io::Result<()> {
+        writeln!(self.output, "hypr-tui plain mode. {}", HELP)?;
+        self.list_monitors()?;
+        self.print_messages()?;
+        loop {
+            let name = self
+                .app
+                .monitors
+                .get(self.selected)
+                .map_or("none", |m| m.name.as_str());
+            let Some(line) = self.prompt(&format!("Selected {}. Command", name))? else {
+                return Ok(());
+            };
+            match line.as_str() {
+                "" => {}
+                "h" | "help" | "?" => writeln!(self.output, "{}", HELP)?,
+                "l" | "list" => self.list_monitors()?,
+                "m" | "mode" => self.change_mode()?,
+                "a" | "apply" => {
+                    let result = self.app.apply_monitor(self.selected);
+                    self.print_result(result)?;
+                }
+                "s" | "save" => self.save()?,
+                "q" | "quit" => {
+                    if self.confirm_quit()? {
+                        return Ok(());
+                    }
+                }
+                other => match other.parse::<usize>() {
+                    Ok(n) if (1..=self.app.monitors.len()).contains(&n) => {
+                        self.selected = n - 1;
+                        writeln!(
+                            self.output,
+                            "Selected {}",
+                            describe(
+                                &self.app.display_name(n - 1),
+                                &self.app.monitors[n - 1],
+                                &self.app.configs[n - 1]
+                            )
+                        )?;
+                    }
+                    _ => writeln!(self.output, "Unknown command {:?}. {}", other, HELP)?,
+                },
+            }
+            self.print_messages()?;
+        }
+    }
+
+    /// Asks a question and returns the trimmed answer, or `None` at the end
+    /// of input.
+    fn prompt(&mut self, question: &str) -> io::Result<Option<String>> {
+        write!(self.output, "{}: ", question)?;
+        self.output.flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            writeln!(self.output)?;
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_string()))
+    }
+
+    fn confirm(&mut self, question: &str) -> io::Result<bool> {
+        let answer = self.prompt(&format!("{} (y or n)", question))?;
+        Ok(answer.is_some_and(|a| a.eq_ignore_ascii_case("y") || a.eq_ignore_ascii_case("yes")))
+    }
+
+    fn list_monitors(&mut self) -> io::Result<()> {
+        if self.app.monitors.is_empty() {
+            return writeln!(self.output, "No monitors found.");
+        }
+        writeln!(self.output, "{} monitors:", self.app.monitors.len())?;
+        for (i, (monitor, config)) in self.app.monitors.iter().zip(&self.app.configs).enumerate() {
+            let name = self.app.display_name(i);
+            writeln!(
+                self.output,
+                "{}. {}",
+                i + 1,
+                describe(&name, monitor, config)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn change_mode(&mut self) -> io::Result<()> {
+        let Some(monitor) = self.app.monitors.get(self.selected) else {
+            return writeln!(self.output, "No monitor selected.");
+        };
+        let config = &self.app.configs[self.selected];
+        let choices = mode_choices(monitor);
+        writeln!(self.output, "{} modes for {}:", choices.len(), monitor.name)?;
+        for (i, (resolution, rate)) in choices.iter().enumerate() {
+            let mut notes = String::new();
+            if let Some(ratio) = monitor::aspect_ratio(resolution) {
+                notes.push_str(&format!(", {}", ratio));
+            }
+            if monitor.native_resolution.as_ref() == Some(resolution) {
+                notes.push_str(", native");
+            }
+            if *resolution == config.resolution && *rate == config.refresh_rate {
+                notes.push_str(", current");
+            }
+            writeln!(
+                self.output,
+                "{}. {} at {:.2} Hz{}",
+                i + 1,
+                resolution,
+                rate,
+                notes
+            )?;
+        }
+
+        let Some(answer) = self.prompt("Mode number, or Enter to keep the current one")? else {
+            return Ok(());
+        };
+        if answer.is_empty() {
+            return Ok(());
+        }
+        match answer.parse::<usize>() {
+            Ok(n) if (1..=choices.len()).contains(&n) => {
+                let (resolution, rate) = choices[n - 1].clone();
+                self.app.set_mode(self.selected, resolution, rate);
+                writeln!(
+                    self.output,
+                    "Mode set to {} at {:.2} Hz. Type a to apply it.",
+                    choices[n - 1].0,
+                    rate
+                )
+            }
+            _ => writeln!(self.output, "No mode numbered {:?}.", answer),
+        }
+    }
+
+    fn save(&mut self) -> io::Result<()> {
+        let stale = self.app.stale_monitors();
+        let pruned = if stale.is_empty() {
+            Vec::new()
+        } else {
+            writeln!(
+                self.output,
+                "These saved monitors have not been connected for a long time: {}",
+                stale.join(", ")
+            )?;
+            if self.confirm("Remove their lines from the file")? {
+                stale
+            } else {
+                Vec::new()
+            }
+        };
+        self.app.write_config_file(&pruned);
+        Ok(())
+    }
+
+    fn confirm_quit(&mut self) -> io::Result<bool> {
+        let unsaved = self.app.unsaved_count();
+        if unsaved == 0 {
+            return Ok(true);
+        }
+        self.confirm(&format!(
+            "{} monitors have unsaved changes. Quit anyway",
+            unsaved
+        ))
+    }
+
+    fn print_result(&mut self, result: Result<String, String>) -> io::Result<()> {
+        match result {
+            Ok(message) => writeln!(self.output, "{}", message),
+            Err(message) => writeln!(self.output, "Error: {}", message),
+        }
+    }
+
+    /// Prints and clears what the app queued for its status bar.
+    fn print_messages(&mut self) -> io::Result<()> {
+        for message in self.app.messages.take_all() {
+            match message.severity {
+                Severity::Info => writeln!(self.output, "{}", message.text)?,
+                Severity::Warning => writeln!(self.output, "Warning: {}", message.text)?,
+                Severity::Error => writeln!(self.output, "Error: {}", message.text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One monitor as a sentence, without symbols a screen reader would spell out.
+fn describe(name: &str, monitor: &Monitor, config: &MonitorConfig) -> String {
+    let mut text = name.to_string();
+    if !monitor.description.is_empty() {
+        text.push_str(&format!(", {}", monitor.description));
+    }
+    match monitor.power_state() {
+        PowerState::Disabled => {
+            text.push_str(": disabled");
+            return text;
+        }
+        PowerState::Sleeping => text.push_str(", sleeping"),
+        PowerState::On => {}
+    }
+    text.push_str(&format!(
+        ": {} at {:.2} Hz, scale {:.2}, position {} {}",
+        config.resolution,
+        config.refresh_rate,
+        config.scale_as_float(),
+        monitor.x,
+        monitor.y
+    ));
+    if let Some(source) = &monitor.mirror_of {
+        text.push_str(&format!(", mirroring {}", source));
+    }
+    text
+}
+
+/// Every mode the monitor supports, one entry per resolution and rate.
+fn mode_choices(monitor: &Monitor) -> Vec<(String, f64)> {
+    monitor
+        .modes
+        .iter()
+        .flat_map(|(resolution, rates)| rates.iter().map(|rate| (resolution.clone(), rate.value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::ColorMode;
+
+    #[test]
+    fn describes_monitors_and_modes_in_words() {
+        let mut monitor: Monitor = serde_json::from_value(serde_json::json!({
+            "name": "DP-1",
+            "description": "Dell U2720Q",
+            "active": true,
+            "x": -2560,
+            "y": 0,
+        }))
+        .expect("monitor parses");
+        monitor.modes = [
+            (String::from("1920x1080"), vec![60.0.into()]),
+            (String::from("2560x1440"), vec![143.91.into(), 59.95.into()]),
+        ]
+        .into_iter()
+        .collect();
+        let config = MonitorConfig {
+            resolution: String::from("2560x1440"),
+            refresh_rate: 143.91,
+            scale: 125,
+            resolution_index: 1,
+            refresh_rate_index: 0,
+            vrr: false,
+            previous_refresh_rate: None,
+            previous_vrr: None,
+            previous_tearing: None,
+            reserved: [0; 4],
+            position: None,
+            save_disabled: false,
+            persist: true,
+            transform: 0,
+            bitdepth: None,
+            color_mode: ColorMode::Default,
+            modeline: None,
+        };
+
+        assert_eq!(
+            describe("DP-1", &monitor, &config),
+            "DP-1, Dell U2720Q: 2560x1440 at 143.91 Hz, scale 1.25, position -2560 0"
+        );
+        assert_eq!(
+            mode_choices(&monitor),
+            vec![
+                (String::from("1920x1080"), 60.0),
+                (String::from("2560x1440"), 143.91),
+                (String::from("2560x1440"), 59.95),
+            ]
+        );
+
+        monitor.dpms_on = false;
+        assert_eq!(
+            describe("DP-1", &monitor, &config),
+            "DP-1, Dell U2720Q, sleeping: 2560x1440 at 143.91 Hz, scale 1.25, position -2560 0"
+        );
+        monitor.active = false;
+        assert_eq!(
+            describe("DP-1", &monitor, &config),
+            "DP-1, Dell U2720Q: disabled"
+        );
+    }
+}
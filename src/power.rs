@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+/// Refresh rates for the internal panel depending on the power source, used
+/// by the daemon. Profiles may carry their own copy to override the app-wide
+/// settings while they are active.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PowerSettings {
+    pub enabled: bool,
+    /// Rate on AC power; unset means the highest rate of the current mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ac_refresh_rate: Option<f64>,
+    pub battery_refresh_rate: f64,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ac_refresh_rate: None,
+            battery_refresh_rate: 60.0,
+        }
+    }
+}
+
+impl PowerSettings {
+    /// The rate to use from `rates` (sorted highest first): the highest one
+    /// not above the target for the current power source.
+    pub fn pick_rate(&self, rates: &[f64], on_ac: bool) -> Option<f64> {
+        let target = if on_ac {
+            self.ac_refresh_rate
+        } else {
+            Some(self.battery_refresh_rate)
+        };
+
+        match target {
+            None => rates.first().copied(),
+            Some(target) => rates
+                .iter()
+                .copied()
+                .find(|&rate| rate <= target + 0.01)
+                .or_else(|| rates.last().copied()),
+        }
+    }
+}
+
+/// Whether the machine runs on mains power. `None` when there is no
+/// mains-type supply at all, e.g. on desktops.
+pub fn on_ac_power() -> Option<bool> {
+    let entries = fs::read_dir(POWER_SUPPLY_DIR).ok()?;
+    let mut found_mains = false;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if read_trimmed(&path.join("type")).as_deref() != Some("Mains") {
+            continue;
+        }
+        found_mains = true;
+        if read_trimmed(&path.join("online")).as_deref() == Some("1") {
+            return Some(true);
+        }
+    }
+
+    found_mains.then_some(false)
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
@@ -0,0 +1,155 @@
+use clap::ValueEnum;
+
+use crate::commands::HyprCommand;
+use crate::monitor::{self, Monitor, MonitorConfig};
+
+/// The classic display-switcher layouts, built from whichever monitors are
+/// currently connected.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Preset {
+    #[value(alias = "laptop-only")]
+    InternalOnly,
+    ExternalOnly,
+    #[value(alias = "docked")]
+    Extend,
+    #[value(alias = "mirror-all")]
+    Mirror,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [
+        Preset::InternalOnly,
+        Preset::ExternalOnly,
+        Preset::Extend,
+        Preset::Mirror,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Preset::InternalOnly => "Internal only",
+            Preset::ExternalOnly => "External only",
+            Preset::Extend => "Extend",
+            Preset::Mirror => "Mirror",
+        }
+    }
+
+    /// The hyprctl commands that produce this layout. Monitors are enabled
+    /// before others are disabled so there is always at least one output.
+    pub fn commands(
+        &self,
+        monitors: &[Monitor],
+        configs: &[MonitorConfig],
+    ) -> Result<Vec<HyprCommand>, String> {
+        let internal = monitors.iter().position(Monitor::is_internal);
+        let externals: Vec<usize> = (0..monitors.len())
+            .filter(|&i| Some(i) != internal)
+            .collect();
+
+        let enable = |i: usize| {
+            HyprCommand::monitor(format!(
+                "{},{},auto,{:.2}",
+                monitors[i].name,
+                mode_spec(&monitors[i], &configs[i]),
+                configs[i].scale_as_float()
+            ))
+        };
+        let disable = |i: usize| HyprCommand::monitor(format!("{},disable", monitors[i].name));
+
+        let commands = match self {
+            Preset::InternalOnly => {
+                let internal = internal.ok_or("No internal panel detected")?;
+                std::iter::once(enable(internal))
+                    .chain(externals.iter().map(|&i| disable(i)))
+                    .collect()
+            }
+            Preset::ExternalOnly => {
+                let internal = internal.ok_or("No internal panel detected")?;
+                if externals.is_empty() {
+                    return Err(String::from("No external monitor connected"));
+                }
+                externals
+                    .iter()
+                    .map(|&i| enable(i))
+                    .chain(std::iter::once(disable(internal)))
+                    .collect()
+            }
+            Preset::Extend => (0..monitors.len()).map(enable).collect(),
+            Preset::Mirror => {
+                let source = internal.unwrap_or(0);
+                let Some(source_monitor) = monitors.get(source) else {
+                    return Err(String::from("No monitors connected"));
+                };
+                std::iter::once(enable(source))
+                    .chain(externals.iter().filter(|&&i| i != source).map(|&i| {
+                        let mode = monitor::mirror_mode(
+                            &monitors[i].modes,
+                            &configs[source].resolution,
+                            configs[source].refresh_rate,
+                        )
+                        .map(|(resolution, rate, _)| format!("{}@{}", resolution, rate.spec))
+                        .unwrap_or_else(|| String::from("preferred"));
+                        HyprCommand::monitor(format!(
+                            "{},{},auto,{:.2},mirror,{}",
+                            monitors[i].name,
+                            mode,
+                            configs[i].scale_as_float(),
+                            source_monitor.name
+                        ))
+                    }))
+                    .collect()
+            }
+        };
+
+        Ok(commands)
+    }
+}
+
+/// One change to a single monitor; monitor presets are sequences of these.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PresetStep {
+    HighestRefreshRate,
+    PreviousRefreshRate,
+    Vrr(bool),
+    Tearing(bool),
+}
+
+/// Per-monitor presets that bundle several settings into one action.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MonitorPreset {
+    Gaming,
+    Desktop,
+}
+
+impl MonitorPreset {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MonitorPreset::Gaming => "Gaming mode",
+            MonitorPreset::Desktop => "Desktop mode",
+        }
+    }
+
+    pub fn steps(&self) -> &'static [PresetStep] {
+        match self {
+            MonitorPreset::Gaming => &[
+                PresetStep::HighestRefreshRate,
+                PresetStep::Vrr(true),
+                PresetStep::Tearing(true),
+            ],
+            MonitorPreset::Desktop => &[
+                PresetStep::PreviousRefreshRate,
+                PresetStep::Vrr(false),
+                PresetStep::Tearing(false),
+            ],
+        }
+    }
+}
+
+/// The configured mode for active monitors; disabled monitors have no
+/// meaningful current mode, so let Hyprland pick.
+fn mode_spec(monitor: &Monitor, config: &MonitorConfig) -> String {
+    if monitor.active && !config.resolution.is_empty() {
+        monitor.mode_spec(config)
+    } else {
+        String::from("preferred")
+    }
+}
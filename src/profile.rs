@@ -0,0 +1,372 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use crate::blanking::BlankingSettings;
+use crate::commands::HyprCommand;
+use crate::monitor::Monitor;
+use crate::power::PowerSettings;
+use crate::wallpaper::Wallpapers;
+use crate::xdg;
+
+const PROFILES_DIR: &str = "hyprmonitor/profiles";
+/// Name of the profile applied last, so cycling continues from it across
+/// the TUI and CLI.
+const LAST_PROFILE_PATH: &str = "hyprmonitor/last-profile";
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ProfileSettings {
+    /// Save layouts to `monitors-<hostname>.conf`, sourced from
+    /// `monitors.conf`, so one dotfiles repository can carry a layout per
+    /// machine.
+    pub per_host: bool,
+}
+
+/// This machine's hostname, as used in per-host file names.
+pub fn hostname() -> io::Result<String> {
+    let name = fs::read_to_string("/proc/sys/kernel/hostname")
+        .or_else(|_| fs::read_to_string("/etc/hostname"))?
+        .trim()
+        .to_string();
+    if name.is_empty() || name.contains(['/', '\0']) || name.starts_with('.') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unusable hostname: {:?}", name),
+        ));
+    }
+    Ok(name)
+}
+
+fn last_profile_path() -> io::Result<PathBuf> {
+    xdg::state_path(LAST_PROFILE_PATH)
+}
+
+/// The profile applied most recently, if any was.
+pub fn last_applied() -> Option<String> {
+    let name = fs::read_to_string(last_profile_path().ok()?).ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+pub fn remember_applied(name: &str) -> io::Result<()> {
+    let path = last_profile_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, name)
+}
+
+/// A named monitor layout stored as `<name>.toml` in the profiles directory.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Profile {
+    #[serde(skip)]
+    pub name: String,
+    /// Shell commands run after this profile applied successfully, e.g. to
+    /// move workspaces or set a wallpaper for the layout.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_apply: Vec<String>,
+    /// Image per monitor name, used instead of the configured wallpapers
+    /// while this profile is the one applied.
+    #[serde(default, skip_serializing_if = "Wallpapers::is_empty")]
+    pub wallpapers: Wallpapers,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blanking: Option<BlankingSettings>,
+    #[serde(default, rename = "monitor")]
+    pub monitors: Vec<ProfileMonitor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileMonitor {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// `WIDTHxHEIGHT@RATE`, or `preferred`.
+    #[serde(default = "default_mode")]
+    pub mode: String,
+    #[serde(default = "default_position")]
+    pub position: String,
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_mode() -> String {
+    String::from("preferred")
+}
+
+fn default_position() -> String {
+    String::from("auto")
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+impl Profile {
+    pub fn dir() -> io::Result<PathBuf> {
+        xdg::config_path(PROFILES_DIR)
+    }
+
+    fn path(name: &str) -> io::Result<PathBuf> {
+        if name.is_empty() || name.contains(['/', '\0']) || name.starts_with('.') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid profile name: {:?}", name),
+            ));
+        }
+        Ok(Self::dir()?.join(format!("{}.toml", name)))
+    }
+
+    /// Names of all saved profiles, sorted.
+    pub fn list() -> io::Result<Vec<String>> {
+        let entries = match fs::read_dir(Self::dir()?) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "toml" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn load(name: &str) -> io::Result<Self> {
+        let path = Self::path(name)?;
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("Profile {:?}: {}", name, e)))?;
+        let mut profile: Profile = toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e.message()),
+            )
+        })?;
+        profile.name = name.to_string();
+        Ok(profile)
+    }
+
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = Self::path(&self.name)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// Whether every monitor this profile turns on is connected.
+    pub fn applies_to(&self, connected: &[String]) -> bool {
+        self.monitors
+            .iter()
+            .filter(|m| m.enabled)
+            .all(|m| connected.contains(&m.name))
+    }
+
+    /// Indices of the monitors this profile turns on that aren't connected.
+    pub fn missing(&self, connected: &[String]) -> Vec<usize> {
+        (0..self.monitors.len())
+            .filter(|&i| self.monitors[i].enabled && !connected.contains(&self.monitors[i].name))
+            .collect()
+    }
+
+    /// Outputs among `monitors` that could stand in for each of `missing`,
+    /// each used at most once: one advertising the same resolution first,
+    /// then one on the same kind of connector, then any.
+    pub fn suggest(&self, missing: &[usize], monitors: &[Monitor]) -> Vec<Option<String>> {
+        let mut free: Vec<&Monitor> = monitors
+            .iter()
+            .filter(|m| !self.monitors.iter().any(|p| p.name == m.name))
+            .collect();
+        missing
+            .iter()
+            .map(|&i| {
+                let wanted = &self.monitors[i];
+                let resolution = wanted.mode.split('@').next().unwrap_or_default();
+                let kind = |name: &str| {
+                    name.rsplit_once('-')
+                        .map_or(name, |(kind, _)| kind)
+                        .to_string()
+                };
+                let pick = free
+                    .iter()
+                    .position(|m| m.modes.contains_key(resolution))
+                    .or_else(|| {
+                        free.iter()
+                            .position(|m| kind(&m.name) == kind(&wanted.name))
+                    })
+                    .or(if free.is_empty() { None } else { Some(0) })?;
+                Some(free.remove(pick).name.clone())
+            })
+            .collect()
+    }
+
+    /// This profile with entry `i` of each `(i, output)` pair moved to
+    /// `output`, or dropped where there is none.
+    pub fn remapped(&self, mapping: &[(usize, Option<String>)]) -> Profile {
+        let mut profile = self.clone();
+        for (i, output) in mapping {
+            if let Some(output) = output {
+                profile.monitors[*i].name = output.clone();
+            }
+        }
+        let dropped: Vec<usize> = mapping
+            .iter()
+            .filter(|(_, output)| output.is_none())
+            .map(|&(i, _)| i)
+            .collect();
+        profile.monitors = profile
+            .monitors
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !dropped.contains(i))
+            .map(|(_, monitor)| monitor)
+            .collect();
+        profile
+    }
+
+    /// The saved profile after (or before) the last applied one, skipping
+    /// those that need a monitor that isn't connected.
+    pub fn cycle(connected: &[String], forward: bool) -> io::Result<Option<Profile>> {
+        let applicable: Vec<Profile> = Self::list()?
+            .iter()
+            .filter_map(|name| Self::load(name).ok())
+            .filter(|profile| profile.applies_to(connected))
+            .collect();
+        if applicable.is_empty() {
+            return Ok(None);
+        }
+
+        let count = applicable.len();
+        let current = last_applied()
+            .and_then(|name| applicable.iter().position(|profile| profile.name == name));
+        let next = match current {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        Ok(applicable.into_iter().nth(next))
+    }
+
+    /// The hyprctl commands that apply this profile. Enabled monitors come
+    /// first so disabling the rest never leaves Hyprland without an output.
+    pub fn commands(&self) -> Vec<HyprCommand> {
+        let enabled = self.monitors.iter().filter(|m| m.enabled).map(|m| {
+            HyprCommand::monitor(format!(
+                "{},{},{},{:.2}",
+                m.name, m.mode, m.position, m.scale
+            ))
+        });
+        let disabled = self
+            .monitors
+            .iter()
+            .filter(|m| !m.enabled)
+            .map(|m| HyprCommand::monitor(format!("{},disable", m.name)));
+
+        enabled.chain(disabled).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_when_every_enabled_monitor_is_connected() {
+        let profile: Profile = toml::from_str(
+            r#"
+            [[monitor]]
+            name = "eDP-1"
+            enabled = false
+
+            [[monitor]]
+            name = "DP-1"
+            mode = "2560x1440@144"
+            "#,
+        )
+        .unwrap();
+
+        assert!(profile.applies_to(&[String::from("DP-1")]));
+        assert!(profile.applies_to(&[String::from("eDP-1"), String::from("DP-1")]));
+        assert!(!profile.applies_to(&[String::from("eDP-1")]));
+    }
+
+    #[test]
+    fn missing_monitors_map_to_similar_outputs() {
+        let profile: Profile = toml::from_str(
+            r#"
+            [[monitor]]
+            name = "eDP-1"
+
+            [[monitor]]
+            name = "DP-3"
+            mode = "2560x1440@144"
+            position = "1920x0"
+
+            [[monitor]]
+            name = "HDMI-A-2"
+            "#,
+        )
+        .unwrap();
+        let monitor = |name: &str, resolution: &str| {
+            let mut monitor: Monitor =
+                serde_json::from_value(serde_json::json!({ "name": name })).unwrap();
+            monitor.modes = [(resolution.to_string(), vec![60.0.into()])]
+                .into_iter()
+                .collect();
+            monitor
+        };
+        let monitors = [
+            monitor("eDP-1", "1920x1080"),
+            monitor("HDMI-A-1", "1920x1080"),
+            monitor("DP-4", "2560x1440"),
+        ];
+        let connected: Vec<String> = monitors.iter().map(|m| m.name.clone()).collect();
+
+        let missing = profile.missing(&connected);
+        assert_eq!(missing, [1, 2]);
+        let suggested = profile.suggest(&missing, &monitors);
+        assert_eq!(
+            suggested,
+            [Some(String::from("DP-4")), Some(String::from("HDMI-A-1"))]
+        );
+
+        let remapped = profile.remapped(&[(1, suggested[0].clone()), (2, None)]);
+        let names: Vec<&str> = remapped.monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["eDP-1", "DP-4"]);
+        assert_eq!(remapped.monitors[1].position, "1920x0");
+    }
+
+    #[test]
+    fn activation_hooks_round_trip() {
+        let profile: Profile = toml::from_str(
+            r#"
+            on_apply = ["hyprctl dispatch moveworkspacetomonitor 1 DP-1"]
+
+            [[monitor]]
+            name = "DP-1"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            profile.on_apply,
+            ["hyprctl dispatch moveworkspacetomonitor 1 DP-1"]
+        );
+
+        let saved: Profile = toml::from_str(&toml::to_string_pretty(&profile).unwrap()).unwrap();
+        assert_eq!(saved.on_apply, profile.on_apply);
+        assert_eq!(saved.monitors.len(), 1);
+    }
+}
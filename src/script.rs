@@ -0,0 +1,190 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Named keys, as written in scripts.
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Space", KeyCode::Char(' ')),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+];
+
+/// Modifier prefixes, as written in scripts.
+const MODIFIERS: [(&str, KeyModifiers); 3] = [
+    ("Ctrl+", KeyModifiers::CONTROL),
+    ("Alt+", KeyModifiers::ALT),
+    ("Shift+", KeyModifiers::SHIFT),
+];
+
+/// One line of a key script.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    Key(KeyEvent),
+    /// `wait MS`: lets debounced and timed actions, such as live mode,
+    /// catch up before the next key.
+    Wait(Duration),
+}
+
+/// A key as a script line: `a`, `G`, `Enter`, `Shift+Left`, `Ctrl+p`, `F2`.
+/// Uppercase letters imply Shift. `None` for keys scripts can't express.
+pub fn format_key(key: &KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(' ') => String::from("Space"),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::PageUp => String::from("PageUp"),
+        KeyCode::PageDown => String::from("PageDown"),
+        code => NAMED_KEYS
+            .iter()
+            .find(|&&(_, named)| named == code)?
+            .0
+            .to_string(),
+    };
+    let mut line = String::new();
+    for (prefix, modifier) in MODIFIERS {
+        let implied = modifier == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_));
+        if key.modifiers.contains(modifier) && !implied {
+            line.push_str(prefix);
+        }
+    }
+    line.push_str(&name);
+    Some(line)
+}
+
+/// Reads a line written by [`format_key`].
+pub fn parse_key(line: &str) -> Option<KeyEvent> {
+    let mut rest = line;
+    let mut modifiers = KeyModifiers::NONE;
+    // A lone `+` is the key itself, not a prefix.
+    while rest.len() > 1 {
+        let Some(&(prefix, modifier)) = MODIFIERS.iter().find(|(p, _)| rest.starts_with(p)) else {
+            break;
+        };
+        modifiers |= modifier;
+        rest = &rest[prefix.len()..];
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => {
+            if c.is_uppercase() {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            KeyCode::Char(c)
+        }
+        _ => match rest {
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            _ => match rest.strip_prefix('F').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => NAMED_KEYS.iter().find(|&&(name, _)| name == rest)?.1,
+            },
+        },
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// Reads a key script: one key or `wait MS` per line, with blank lines and
+/// `#` comments skipped.
+pub fn load(path: &Path) -> io::Result<Vec<Step>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+    let mut steps = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        // Keys are never padded, so a line of spaces is blank, not Space.
+        let line = line.trim();
+        if line.is_empty() || (line.starts_with('#') && line.len() > 1) {
+            continue;
+        }
+        let step = match line.strip_prefix("wait ") {
+            Some(ms) => ms
+                .trim()
+                .parse()
+                .ok()
+                .map(Duration::from_millis)
+                .map(Step::Wait),
+            None => parse_key(line).map(Step::Key),
+        };
+        steps.push(step.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: unknown key {:?}", path.display(), i + 1, line),
+            )
+        })?);
+    }
+    Ok(steps)
+}
+
+/// Appends each key pressed to a script that [`load`] can replay.
+pub struct Recorder {
+    file: File,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let mut file = File::create(path)?;
+        writeln!(file, "# Recorded by hypr-tui; replay with --script")?;
+        Ok(Self { file })
+    }
+
+    pub fn record(&mut self, key: &KeyEvent) -> io::Result<()> {
+        match format_key(key) {
+            Some(line) => writeln!(self.file, "{}", line),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_read_back_as_written() {
+        let keys = [
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+            KeyEvent::new(KeyCode::Char('+'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('#'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE),
+        ];
+        let lines: Vec<String> = keys.iter().filter_map(format_key).collect();
+        assert_eq!(
+            lines,
+            [
+                "a",
+                "G",
+                "Ctrl+p",
+                "+",
+                "#",
+                "Space",
+                "Shift+Left",
+                "Enter",
+                "F2"
+            ]
+        );
+        for (line, key) in lines.iter().zip(keys) {
+            assert_eq!(parse_key(line), Some(key), "{}", line);
+        }
+        assert_eq!(parse_key("Enterr"), None);
+        assert_eq!(parse_key("F13"), None);
+    }
+}
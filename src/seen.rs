@@ -0,0 +1,73 @@
+use chrono::{Duration, Local};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::xdg;
+
+const SEEN_PATH: &str = "hyprmonitor/seen-monitors.toml";
+/// Saved monitors that haven't been connected for this long are offered for
+/// cleanup.
+const STALE_AFTER_DAYS: i64 = 90;
+
+/// When each output was last connected, as Unix timestamps, so saved lines
+/// for monitors that are long gone can be told apart from ones that are
+/// just unplugged right now.
+#[derive(Debug, Default)]
+pub struct SeenMonitors {
+    last_seen: BTreeMap<String, i64>,
+    /// Whether the record existed before this session.
+    existed: bool,
+}
+
+impl SeenMonitors {
+    fn path() -> io::Result<PathBuf> {
+        xdg::state_path(SEEN_PATH)
+    }
+
+    pub fn load() -> Self {
+        let Some(contents) = Self::path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return Self::default();
+        };
+        Self {
+            last_seen: toml::from_str(&contents).unwrap_or_default(),
+            existed: true,
+        }
+    }
+
+    /// Records `connected` as seen now. On the first run every monitor in
+    /// the saved configuration is counted as seen too, since there is no
+    /// record yet of when it last was.
+    pub fn update<'a>(
+        &mut self,
+        connected: impl IntoIterator<Item = &'a str>,
+        saved: impl IntoIterator<Item = &'a str>,
+    ) -> io::Result<()> {
+        let now = Local::now().timestamp();
+        if !self.existed {
+            for name in saved {
+                self.last_seen.insert(name.to_string(), now);
+            }
+            self.existed = true;
+        }
+        for name in connected {
+            self.last_seen.insert(name.to_string(), now);
+        }
+
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            path,
+            toml::to_string(&self.last_seen).map_err(io::Error::other)?,
+        )
+    }
+
+    /// Whether `name` hasn't been connected for a long time, or ever.
+    pub fn is_stale(&self, name: &str) -> bool {
+        let cutoff = (Local::now() - Duration::days(STALE_AFTER_DAYS)).timestamp();
+        self.last_seen.get(name).is_none_or(|&seen| seen < cutoff)
+    }
+}
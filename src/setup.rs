@@ -0,0 +1,121 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::config::Config;
+use crate::i18n::tr;
+
+/// Where the first-run setup puts the other active monitors relative to the
+/// primary one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Arrangement {
+    PrimaryLeft,
+    PrimaryRight,
+    /// The others side by side, with the primary centered below them.
+    PrimaryBelow,
+    /// Leave the positions Hyprland picked.
+    Keep,
+}
+
+impl Arrangement {
+    pub const ALL: [Arrangement; 4] = [
+        Arrangement::PrimaryLeft,
+        Arrangement::PrimaryRight,
+        Arrangement::PrimaryBelow,
+        Arrangement::Keep,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Arrangement::PrimaryLeft => tr("setup.primary_left"),
+            Arrangement::PrimaryRight => tr("setup.primary_right"),
+            Arrangement::PrimaryBelow => tr("setup.primary_below"),
+            Arrangement::Keep => tr("setup.keep"),
+        }
+    }
+
+    /// Top-left corners, in logical pixels, for monitors of the given
+    /// logical `sizes`, the primary's first. `None` for [`Arrangement::Keep`].
+    pub fn positions(self, sizes: &[(u32, u32)]) -> Option<Vec<(i32, i32)>> {
+        let sizes: Vec<(i32, i32)> = sizes.iter().map(|&(w, h)| (w as i32, h as i32)).collect();
+        let Some((&(primary_w, _), others)) = sizes.split_first() else {
+            return Some(Vec::new());
+        };
+        let row = |start: i32, y: i32| {
+            others.iter().scan(start, move |x, &(w, _)| {
+                let position = (*x, y);
+                *x += w;
+                Some(position)
+            })
+        };
+        let others_w: i32 = others.iter().map(|&(w, _)| w).sum();
+        Some(match self {
+            Arrangement::PrimaryLeft => std::iter::once((0, 0)).chain(row(primary_w, 0)).collect(),
+            Arrangement::PrimaryRight => std::iter::once((others_w, 0)).chain(row(0, 0)).collect(),
+            Arrangement::PrimaryBelow => {
+                let others_h = others.iter().map(|&(_, h)| h).max().unwrap_or(0);
+                let x = if others.is_empty() {
+                    0
+                } else {
+                    (others_w - primary_w) / 2
+                };
+                std::iter::once((x, others_h)).chain(row(0, 0)).collect()
+            }
+            Arrangement::Keep => return None,
+        })
+    }
+}
+
+/// Whether this looks like a first run: no settings file and no saved
+/// layout yet.
+pub fn is_first_run(saved_conf: &str) -> bool {
+    saved_conf.trim().is_empty() && Config::path().is_ok_and(|path| !path.exists())
+}
+
+/// Writes the settings chosen during setup, which also keeps the setup from
+/// being offered again. An existing file is left alone.
+pub fn write_config(per_host: bool) -> io::Result<PathBuf> {
+    let path = Config::path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    write!(
+        file,
+        "# Written by the hypr-tui setup; see the README for every setting.\n\n[profiles]\nper_host = {}\n",
+        per_host
+    )?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrangements_place_the_primary_first() {
+        let sizes = [(1920, 1080), (2560, 1440), (1280, 1024)];
+        assert_eq!(
+            Arrangement::PrimaryLeft.positions(&sizes),
+            Some(vec![(0, 0), (1920, 0), (4480, 0)])
+        );
+        assert_eq!(
+            Arrangement::PrimaryRight.positions(&sizes),
+            Some(vec![(3840, 0), (0, 0), (2560, 0)])
+        );
+        assert_eq!(
+            Arrangement::PrimaryBelow.positions(&sizes),
+            Some(vec![(960, 1440), (0, 0), (2560, 0)])
+        );
+        assert_eq!(Arrangement::Keep.positions(&sizes), None);
+        assert_eq!(
+            Arrangement::PrimaryBelow.positions(&sizes[..1]),
+            Some(vec![(0, 0)])
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{io, path::Path};
+
+use crate::commands::HyprCommand;
+use crate::monitor::{ColorMode, Monitor, MonitorConfig};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum StateFormat {
+    Json,
+    Toml,
+}
+
+impl StateFormat {
+    /// TOML for `.toml` files, JSON for anything else.
+    pub fn for_path(path: &Path) -> Self {
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            StateFormat::Toml
+        } else {
+            StateFormat::Json
+        }
+    }
+}
+
+/// Every monitor's settings, complete enough to put the layout back exactly
+/// as it was. Unlike a profile it also carries transforms, reserved areas,
+/// mirroring and color settings.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct State {
+    #[serde(rename = "monitor")]
+    pub monitors: Vec<MonitorState>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MonitorState {
+    pub name: String,
+    pub enabled: bool,
+    pub resolution: String,
+    pub refresh_rate: f64,
+    pub scale: f64,
+    pub position: [i32; 2],
+    #[serde(default)]
+    pub transform: u8,
+    #[serde(default)]
+    pub vrr: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitdepth: Option<u8>,
+    /// The `cm` keyword, when one is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_mode: Option<String>,
+    /// Top, bottom, left, right.
+    #[serde(default)]
+    pub reserved: [u32; 4],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror_of: Option<String>,
+}
+
+impl State {
+    pub fn capture(monitors: &[Monitor], configs: &[MonitorConfig]) -> Self {
+        let monitors = monitors
+            .iter()
+            .zip(configs)
+            .map(|(monitor, config)| MonitorState {
+                name: monitor.name.clone(),
+                enabled: monitor.active,
+                resolution: config.resolution.clone(),
+                refresh_rate: config.refresh_rate,
+                scale: config.scale_as_float(),
+                position: [monitor.x, monitor.y],
+                transform: config.transform,
+                vrr: config.vrr,
+                bitdepth: config.bitdepth,
+                color_mode: config.color_mode.keyword().map(str::to_string),
+                reserved: config.reserved,
+                mirror_of: monitor.mirror_of.clone(),
+            })
+            .collect();
+        Self { monitors }
+    }
+
+    pub fn dump(&self, format: StateFormat) -> io::Result<String> {
+        match format {
+            StateFormat::Json => serde_json::to_string_pretty(self)
+                .map(|json| json + "\n")
+                .map_err(io::Error::other),
+            StateFormat::Toml => toml::to_string_pretty(self).map_err(io::Error::other),
+        }
+    }
+
+    pub fn parse(contents: &str, format: StateFormat) -> io::Result<Self> {
+        match format {
+            StateFormat::Json => serde_json::from_str(contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            StateFormat::Toml => toml::from_str(contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.message().to_string())),
+        }
+    }
+
+    /// The hyprctl commands that restore this state onto the connected
+    /// monitors, whose current settings fill in anything not recorded.
+    /// Monitors that aren't connected are skipped. Sources are set up before
+    /// their mirrors and nothing is disabled until the rest is on.
+    pub fn commands(&self, monitors: &[Monitor], configs: &[MonitorConfig]) -> Vec<HyprCommand> {
+        let connected = |name: &str| {
+            monitors
+                .iter()
+                .zip(configs)
+                .find(|(monitor, _)| monitor.name == name)
+        };
+
+        let mut enabled: Vec<&MonitorState> = self.monitors.iter().filter(|m| m.enabled).collect();
+        enabled.sort_by_key(|m| m.mirror_of.is_some());
+
+        let mut commands = Vec::new();
+        for saved in enabled {
+            let Some((monitor, config)) = connected(&saved.name) else {
+                continue;
+            };
+            let config = saved.apply_to(config);
+            let mut rule = monitor.rule(&config);
+            if let Some(source) = &saved.mirror_of {
+                rule.push_str(&format!(",mirror,{}", source));
+            }
+            commands.push(HyprCommand::monitor(rule));
+            commands.push(HyprCommand::monitor(config.reserved_rule(&saved.name)));
+        }
+        commands.extend(
+            self.monitors
+                .iter()
+                .filter(|m| !m.enabled && connected(&m.name).is_some())
+                .map(|m| HyprCommand::monitor(format!("{},disable", m.name))),
+        );
+        commands
+    }
+}
+
+impl MonitorState {
+    fn apply_to(&self, config: &MonitorConfig) -> MonitorConfig {
+        MonitorConfig {
+            resolution: self.resolution.clone(),
+            refresh_rate: self.refresh_rate,
+            scale: (self.scale * 100.0).round() as i32,
+            position: Some((self.position[0], self.position[1])),
+            transform: self.transform,
+            vrr: self.vrr,
+            bitdepth: self.bitdepth,
+            color_mode: self
+                .color_mode
+                .as_deref()
+                .map(ColorMode::from_keyword)
+                .unwrap_or_default(),
+            reserved: self.reserved,
+            ..config.clone()
+        }
+    }
+}
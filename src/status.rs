@@ -0,0 +1,91 @@
+use serde_json::json;
+
+use crate::monitor::{Monitor, MonitorConfig};
+
+/// What `status` reports: the profile applied last and the monitors that
+/// are on.
+pub struct Status {
+    pub profile: Option<String>,
+    /// One line per active monitor, e.g. `DP-1 2560x1440@144.00 x1.25`.
+    pub active: Vec<String>,
+    pub total: usize,
+}
+
+impl Status {
+    pub fn capture(
+        monitors: &[Monitor],
+        configs: &[MonitorConfig],
+        profile: Option<String>,
+    ) -> Self {
+        let active = monitors
+            .iter()
+            .zip(configs)
+            .filter(|(monitor, _)| monitor.active)
+            .map(|(monitor, config)| {
+                format!(
+                    "{} {} x{:.2}",
+                    monitor.name,
+                    monitor.mode_spec(config),
+                    config.scale_as_float()
+                )
+            })
+            .collect();
+        Self {
+            profile,
+            active,
+            total: monitors.len(),
+        }
+    }
+
+    fn count(&self) -> String {
+        format!("{}/{}", self.active.len(), self.total)
+    }
+
+    /// The object a waybar (or eww) custom module with
+    /// `"return-type": "json"` reads: `alt` is the profile, for
+    /// `format-icons`, and `class` is `monitors-N` for styling.
+    pub fn waybar(&self) -> serde_json::Value {
+        let text = match &self.profile {
+            Some(profile) => format!("{} {}", profile, self.count()),
+            None => self.count(),
+        };
+        json!({
+            "text": text,
+            "alt": self.profile.as_deref().unwrap_or("none"),
+            "tooltip": self.plain(),
+            "class": format!("monitors-{}", self.active.len()),
+        })
+    }
+
+    pub fn plain(&self) -> String {
+        let mut lines = vec![
+            format!("Profile: {}", self.profile.as_deref().unwrap_or("none")),
+            format!("{} monitors active", self.count()),
+        ];
+        lines.extend(self.active.iter().cloned());
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waybar_output_summarizes_the_layout() {
+        let status = Status {
+            profile: Some(String::from("desk")),
+            active: vec![String::from("DP-1 2560x1440@144.00 x1.00")],
+            total: 2,
+        };
+        assert_eq!(
+            status.waybar(),
+            json!({
+                "text": "desk 1/2",
+                "alt": "desk",
+                "tooltip": "Profile: desk\n1/2 monitors active\nDP-1 2560x1440@144.00 x1.00",
+                "class": "monitors-1",
+            })
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::{io, path::PathBuf};
+
+use crate::monitor::{Monitor, MonitorConfig};
+
+/// Where the TUI's "Export layout as SVG" action writes the diagram.
+const EXPORT_PATH: &str = "~/hyprmonitor-layout.svg";
+/// Width of the drawn layout; the height follows from the arrangement.
+const CANVAS_WIDTH: f64 = 960.0;
+const MARGIN: f64 = 20.0;
+const LINE_HEIGHT: f64 = 20.0;
+const FILLS: [&str; 4] = ["#4c7899", "#6a8d4f", "#8f5f8f", "#a0703c"];
+
+pub fn export_path() -> io::Result<PathBuf> {
+    shellexpand::full(EXPORT_PATH)
+        .map(|p| PathBuf::from(p.into_owned()))
+        .map_err(|e| io::Error::new(io::ErrorKind::NotFound, e))
+}
+
+/// Draws the active monitors to scale at their layout positions, labelled
+/// with name, mode, scale and position. Mirrors and disabled monitors take
+/// no space in the layout, so they are listed underneath instead.
+pub fn layout(monitors: &[Monitor], configs: &[MonitorConfig]) -> String {
+    let placed: Vec<(&Monitor, &MonitorConfig, (u32, u32))> = monitors
+        .iter()
+        .zip(configs)
+        .filter(|(monitor, _)| monitor.active && monitor.mirror_of.is_none())
+        .filter_map(|(monitor, config)| Some((monitor, config, config.logical_size()?)))
+        .collect();
+
+    let min_x = placed.iter().map(|(m, _, _)| m.x).min().unwrap_or(0);
+    let min_y = placed.iter().map(|(m, _, _)| m.y).min().unwrap_or(0);
+    let max_x = placed
+        .iter()
+        .map(|(m, _, (w, _))| m.x + *w as i32)
+        .max()
+        .unwrap_or(0);
+    let max_y = placed
+        .iter()
+        .map(|(m, _, (_, h))| m.y + *h as i32)
+        .max()
+        .unwrap_or(0);
+    let factor = CANVAS_WIDTH / f64::from((max_x - min_x).max(1));
+
+    let notes: Vec<String> = monitors
+        .iter()
+        .filter_map(|monitor| match (&monitor.mirror_of, monitor.active) {
+            (_, false) => Some(format!("{} is disabled", monitor.name)),
+            (Some(source), true) => Some(format!("{} mirrors {}", monitor.name, source)),
+            (None, true) => None,
+        })
+        .collect();
+
+    let layout_height = f64::from(max_y - min_y) * factor;
+    let width = CANVAS_WIDTH + 2.0 * MARGIN;
+    let height = layout_height + 2.0 * MARGIN + notes.len() as f64 * LINE_HEIGHT;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" \
+         viewBox=\"0 0 {:.0} {:.0}\" font-family=\"sans-serif\">\n",
+        width, height, width, height
+    );
+    svg.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"#1e1e2e\"/>\n");
+
+    for (i, (monitor, config, (w, h))) in placed.iter().enumerate() {
+        let x = MARGIN + f64::from(monitor.x - min_x) * factor;
+        let y = MARGIN + f64::from(monitor.y - min_y) * factor;
+        let (w, h) = (f64::from(*w) * factor, f64::from(*h) * factor);
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        svg.push_str(&format!(
+            "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"6\" \
+             fill=\"{}\" stroke=\"#cdd6f4\" stroke-width=\"2\"/>\n",
+            x,
+            y,
+            w,
+            h,
+            FILLS[i % FILLS.len()]
+        ));
+        let lines = [
+            monitor.name.clone(),
+            format!("{}@{:.2}", config.resolution, config.refresh_rate),
+            format!(
+                "scale {:.2}, at {},{}",
+                config.scale_as_float(),
+                monitor.x,
+                monitor.y
+            ),
+        ];
+        for (row, line) in lines.iter().enumerate() {
+            let dy = (row as f64 - 1.0) * LINE_HEIGHT;
+            let weight = if row == 0 {
+                " font-weight=\"bold\""
+            } else {
+                ""
+            };
+            svg.push_str(&format!(
+                "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"#ffffff\" font-size=\"14\" \
+                 text-anchor=\"middle\" dominant-baseline=\"middle\"{}>{}</text>\n",
+                cx,
+                cy + dy,
+                weight,
+                escape(line)
+            ));
+        }
+    }
+
+    for (row, note) in notes.iter().enumerate() {
+        svg.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" fill=\"#a6adc8\" font-size=\"14\">{}</text>\n",
+            MARGIN,
+            MARGIN + layout_height + (row as f64 + 1.0) * LINE_HEIGHT,
+            escape(note)
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,111 @@
+use crate::i18n::tr;
+
+/// Common desk arrangements. Each has a fixed number of slots that the user
+/// fills with monitors; the positions follow from their logical sizes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Template {
+    /// Two side by side.
+    Row2,
+    /// One centered above two side by side.
+    OneOverTwo,
+    /// Three side by side.
+    Row3,
+    /// Two side by side and one below the first.
+    LShape,
+}
+
+impl Template {
+    pub const ALL: [Template; 4] = [
+        Template::Row2,
+        Template::OneOverTwo,
+        Template::Row3,
+        Template::LShape,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Template::Row2 => tr("templates.row2"),
+            Template::OneOverTwo => tr("templates.one_over_two"),
+            Template::Row3 => tr("templates.row3"),
+            Template::LShape => tr("templates.l_shape"),
+        }
+    }
+
+    /// Locale keys naming each slot, in the order they are filled.
+    fn slot_keys(self) -> &'static [&'static str] {
+        match self {
+            Template::Row2 => &["templates.left", "templates.right"],
+            Template::OneOverTwo => &[
+                "templates.top",
+                "templates.bottom_left",
+                "templates.bottom_right",
+            ],
+            Template::Row3 => &["templates.left", "templates.middle", "templates.right"],
+            Template::LShape => &["templates.top_left", "templates.right", "templates.below"],
+        }
+    }
+
+    pub fn slots(self) -> usize {
+        self.slot_keys().len()
+    }
+
+    pub fn slot_label(self, slot: usize) -> &'static str {
+        tr(self.slot_keys()[slot])
+    }
+
+    /// Top-left corners, in logical pixels, for monitors of the given
+    /// logical `sizes`, one per slot.
+    pub fn positions(self, sizes: &[(u32, u32)]) -> Vec<(i32, i32)> {
+        let size = |slot: usize| {
+            let (w, h) = sizes[slot];
+            (w as i32, h as i32)
+        };
+        match self {
+            Template::Row2 | Template::Row3 => sizes
+                .iter()
+                .scan(0, |x, &(w, _)| {
+                    let position = (*x, 0);
+                    *x += w as i32;
+                    Some(position)
+                })
+                .collect(),
+            Template::OneOverTwo => {
+                let ((top_w, top_h), (left_w, _), (right_w, _)) = (size(0), size(1), size(2));
+                vec![
+                    ((left_w + right_w - top_w) / 2, 0),
+                    (0, top_h),
+                    (left_w, top_h),
+                ]
+            }
+            Template::LShape => {
+                let (first_w, first_h) = size(0);
+                vec![(0, 0), (first_w, 0), (0, first_h)]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_follow_logical_sizes() {
+        let sizes = [(2560, 1440), (1920, 1080), (1920, 1080)];
+        assert_eq!(
+            Template::Row3.positions(&sizes),
+            [(0, 0), (2560, 0), (4480, 0)]
+        );
+        assert_eq!(
+            Template::OneOverTwo.positions(&sizes),
+            [(640, 0), (0, 1440), (1920, 1440)]
+        );
+        assert_eq!(
+            Template::LShape.positions(&sizes),
+            [(0, 0), (2560, 0), (0, 1440)]
+        );
+        assert!(Template::ALL
+            .iter()
+            .all(|t| t.positions(&sizes[..t.slots()]).len() == t.slots()));
+    }
+}
@@ -0,0 +1,135 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+/// Built-in color themes, chosen with `[ui] theme` or cycled at runtime.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    /// Blue and orange instead of green and red.
+    Deuteranopia,
+    /// Bright colors and bold text for low vision.
+    HighContrast,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 3] = [
+        ThemeName::Default,
+        ThemeName::Deuteranopia,
+        ThemeName::HighContrast,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeName::Default => "default",
+            ThemeName::Deuteranopia => "deuteranopia",
+            ThemeName::HighContrast => "high contrast",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&name| name == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn theme(self) -> &'static Theme {
+        match self {
+            ThemeName::Default => &DEFAULT,
+            ThemeName::Deuteranopia => &DEUTERANOPIA,
+            ThemeName::HighContrast => &HIGH_CONTRAST,
+        }
+    }
+}
+
+/// Colors by meaning rather than by hue, so a theme can swap them out.
+/// Nothing relies on color alone: states are also spelled out or marked.
+pub struct Theme {
+    /// Selected tab, focused borders and the selection background.
+    pub accent: Color,
+    /// Text on the selection background.
+    pub selected_text: Color,
+    /// Apply actions and successful commands.
+    pub positive: Color,
+    /// Disable actions, failures and errors.
+    pub negative: Color,
+    /// Save actions and informational messages.
+    pub info: Color,
+    /// Warnings, unsaved changes and key hints.
+    pub warning: Color,
+    /// Secondary text such as details and section headers.
+    pub muted: Color,
+    /// The live mode keep/revert prompt.
+    pub prompt: Color,
+    /// Added to every colored style.
+    pub emphasis: Modifier,
+}
+
+const DEFAULT: Theme = Theme {
+    accent: Color::Blue,
+    selected_text: Color::Reset,
+    positive: Color::Green,
+    negative: Color::Red,
+    info: Color::Cyan,
+    warning: Color::Yellow,
+    muted: Color::DarkGray,
+    prompt: Color::Magenta,
+    emphasis: Modifier::empty(),
+};
+
+/// Based on the Okabe-Ito palette, which stays distinguishable with the
+/// common forms of color blindness.
+const DEUTERANOPIA: Theme = Theme {
+    accent: Color::Rgb(0, 114, 178),
+    selected_text: Color::White,
+    positive: Color::Rgb(86, 180, 233),
+    negative: Color::Rgb(230, 159, 0),
+    info: Color::White,
+    warning: Color::Rgb(240, 228, 66),
+    muted: Color::Gray,
+    prompt: Color::Rgb(204, 121, 167),
+    emphasis: Modifier::empty(),
+};
+
+const HIGH_CONTRAST: Theme = Theme {
+    accent: Color::Yellow,
+    selected_text: Color::Black,
+    positive: Color::LightGreen,
+    negative: Color::LightRed,
+    info: Color::LightCyan,
+    warning: Color::LightYellow,
+    muted: Color::White,
+    prompt: Color::LightMagenta,
+    emphasis: Modifier::BOLD,
+};
+
+impl Theme {
+    pub fn fg(&self, color: Color) -> Style {
+        Style::default().fg(color).add_modifier(self.emphasis)
+    }
+
+    /// The highlighted row of a list.
+    pub fn selection(&self) -> Style {
+        Style::default()
+            .fg(self.selected_text)
+            .bg(self.accent)
+            .add_modifier(Modifier::BOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycling_visits_every_theme() {
+        let mut name = ThemeName::default();
+        for expected in ThemeName::ALL.iter().skip(1) {
+            name = name.next();
+            assert_eq!(name, *expected);
+        }
+        assert_eq!(name.next(), ThemeName::Default);
+        let theme = ThemeName::Deuteranopia.theme();
+        assert_ne!(theme.positive, theme.negative);
+    }
+}
@@ -1,139 +1,1255 @@
 use ratatui::{prelude::*, widgets::*};
 
-use crate::app::{App, FocusedPane};
+use crate::app::{
+    App, Cleanup, Details, DisableConfirm, FocusedPane, Popup, Reconcile, ReloadConfirm, SetupStep,
+    SetupWizard, ShrinkConfirm, Tab, TemplateChooser, DISABLE_CHOICES, OPTION_SECTIONS,
+    RELOAD_CHOICES, SHRINK_CHOICES,
+};
+use crate::hyprconf;
+use crate::i18n::{tr, tr_args};
+use crate::input::TextInput;
+use crate::message::Severity;
+use crate::monitor::{self, Monitor, PowerState};
+use crate::palette::Palette;
+use crate::picker::ModePicker;
+use crate::presets::Preset;
+use crate::profile;
+use crate::setup::Arrangement;
+use crate::template::Template;
+use crate::theme::Theme;
 
-pub fn render(f: &mut Frame, app: &App) {
+/// Below this width the panes are stacked and labels shortened.
+const NARROW_WIDTH: u16 = 80;
+
+/// Markers that differ between the default and ASCII-only rendering.
+struct Glyphs {
+    active: &'static str,
+    inactive: &'static str,
+    sleeping: &'static str,
+    focused: &'static str,
+    main: &'static str,
+    expanded: &'static str,
+    collapsed: &'static str,
+    power_on: &'static str,
+    standby: &'static str,
+    scroll_track: &'static str,
+    scroll_thumb: &'static str,
+    divider: &'static str,
+    rule: &'static str,
+}
+
+const UNICODE_GLYPHS: Glyphs = Glyphs {
+    active: "✅",
+    inactive: "❌",
+    sleeping: "🌙",
+    focused: " ◆",
+    main: " ★",
+    expanded: "▾",
+    collapsed: "▸",
+    power_on: "●",
+    standby: "◌",
+    scroll_track: "│",
+    scroll_thumb: "█",
+    divider: "│",
+    rule: "──",
+};
+
+const ASCII_GLYPHS: Glyphs = Glyphs {
+    active: "[+]",
+    inactive: "[-]",
+    sleeping: "[z]",
+    focused: " *",
+    main: " (main)",
+    expanded: "v",
+    collapsed: ">",
+    power_on: "o",
+    standby: ".",
+    scroll_track: "|",
+    scroll_thumb: "#",
+    divider: "|",
+    rule: "--",
+};
+
+fn theme(app: &App) -> &'static Theme {
+    app.theme.theme()
+}
+
+fn glyphs(app: &App) -> &'static Glyphs {
+    if app.config.ui.ascii {
+        &ASCII_GLYPHS
+    } else {
+        &UNICODE_GLYPHS
+    }
+}
+
+/// Replaces the unicode arrows and markers used in hints and titles.
+fn ascii_text(app: &App, text: &str) -> String {
+    if !app.config.ui.ascii {
+        return text.to_string();
+    }
+    text.replace("↑/↓", "Up/Down")
+        .replace("←/→", "Left/Right")
+        .replace('◆', "*")
+        .replace('★', "(main)")
+}
+
+pub fn render(f: &mut Frame, app: &mut App) {
+    let compact = f.size().width < NARROW_WIDTH;
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Max(3)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Max(3),
+        ])
         .split(f.size());
 
-    if let [content_area, instructions_area] = main_chunks[..] {
-        let content_chunks = Layout::default()
+    if let [tabs_area, content_area, status_area, instructions_area] = main_chunks[..] {
+        render_tabs(f, app, tabs_area, compact);
+        match app.tab {
+            Tab::Monitors => render_monitors_tab(f, app, content_area, compact),
+            Tab::Workspaces => render_workspaces(f, app, content_area),
+            Tab::Profiles => render_profiles(f, app, content_area),
+            Tab::Log => render_log(f, app, content_area),
+        }
+        render_status_bar(f, app, status_area, compact);
+        render_instructions(f, app, instructions_area, compact);
+    }
+
+    // Taken out for the duration so popups can update their own list state
+    // while still reading the rest of the app.
+    let Some(mut popup) = app.popup.take() else {
+        return;
+    };
+    match &mut popup {
+        Popup::ModePicker(picker) => render_mode_picker(f, app, picker),
+        Popup::Palette(palette) => render_palette(f, app, palette),
+        Popup::TextInput(input) => render_text_input(f, app, input),
+        Popup::Presets(state) => render_presets(f, app, state),
+        Popup::DisableConfirm(confirm) => render_disable_confirm(f, app, confirm),
+        Popup::Details(details) => render_details(f, app, details),
+        Popup::Cleanup(cleanup) => render_cleanup(f, app, cleanup),
+        Popup::Template(chooser) => render_template(f, app, chooser),
+        Popup::Setup(wizard) => render_setup(f, app, wizard),
+        Popup::Reconcile(reconcile) => render_reconcile(f, app, reconcile),
+        Popup::ShrinkConfirm(confirm) => render_shrink_confirm(f, app, confirm),
+        Popup::Review(state) => render_review(f, app, state),
+        Popup::ReloadConfirm(confirm) => render_reload_confirm(f, app, confirm),
+    }
+    app.popup = Some(popup);
+}
+
+fn render_tabs(f: &mut Frame, app: &App, area: Rect, compact: bool) {
+    let titles: Vec<String> = Tab::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| {
+            if compact {
+                let short: String = tab.title().chars().take(3).collect();
+                format!("F{} {}", i + 1, short)
+            } else {
+                format!("F{} {}", i + 1, tab.title())
+            }
+        })
+        .collect();
+
+    let tabs = Tabs::new(titles)
+        .block(Block::default().borders(Borders::ALL))
+        .divider(glyphs(app).divider)
+        .select(Tab::ALL.iter().position(|&tab| tab == app.tab).unwrap_or(0))
+        .highlight_style(
+            theme(app)
+                .fg(theme(app).accent)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    f.render_widget(tabs, area);
+}
+
+fn render_monitors_tab(f: &mut Frame, app: &mut App, area: Rect, compact: bool) {
+    let content_chunks = if compact {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(area)
+    } else {
+        Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-            .split(content_area);
+            .split(area)
+    };
 
-        if let [monitors_area, options_area] = content_chunks[..] {
-            render_monitors_pane(f, app, monitors_area);
-            render_options_pane(f, app, options_area);
-            render_instructions(f, app, instructions_area);
-        }
+    if let [monitors_area, options_area] = content_chunks[..] {
+        render_monitors_pane(f, app, monitors_area);
+        render_options_pane(f, app, options_area, compact);
     }
 }
 
-fn render_monitors_pane(f: &mut Frame, app: &App, area: Rect) {
+fn render_monitors_pane(f: &mut Frame, app: &mut App, area: Rect) {
     let is_focused = app.is_focused(FocusedPane::Monitors);
+    let glyphs = glyphs(app);
 
     let items: Vec<ListItem> = app
         .monitors
         .iter()
-        .map(|m| {
-            let icon = if m.active { "✅" } else { "❌" };
-            ListItem::new(format!("{} {}", icon, m.name))
+        .zip(&app.configs)
+        .enumerate()
+        .map(|(i, (m, config))| {
+            if app.is_hidden(i) {
+                return ListItem::new(Vec::<Line>::new());
+            }
+            let header = app.group_header(i).map(|group| {
+                let collapsed = app.collapsed_groups.contains(group);
+                let text = if collapsed {
+                    format!("{} {} ({})", glyphs.collapsed, group, app.group_size(group))
+                } else {
+                    format!("{} {}", glyphs.expanded, group)
+                };
+                (
+                    collapsed,
+                    Line::from(text).style(theme(app).fg(theme(app).accent)),
+                )
+            });
+            if let Some((true, header)) = header {
+                return ListItem::new(header);
+            }
+            let icon = match m.power_state() {
+                PowerState::On => glyphs.active,
+                PowerState::Sleeping => glyphs.sleeping,
+                PowerState::Disabled => glyphs.inactive,
+            };
+            let focus = if m.focused { glyphs.focused } else { "" };
+            let main = if app.main_monitor.as_deref() == Some(m.name.as_str()) {
+                glyphs.main
+            } else {
+                ""
+            };
+            let name = format!("{}{}{}", app.display_name(i), main, focus);
+            let is_new = app
+                .new_monitor
+                .as_ref()
+                .is_some_and(|(new, _)| *new == m.name);
+            let title = match &m.mirror_of {
+                _ if is_new => {
+                    format!("{} {}", icon, tr_args("monitor.new", &[("monitor", &name)]))
+                }
+                Some(source) => format!(
+                    "{} {}",
+                    icon,
+                    tr_args(
+                        "monitor.mirroring",
+                        &[("monitor", &name), ("source", source)]
+                    )
+                ),
+                None => format!("{} {}", icon, name),
+            };
+            let details = if m.active {
+                let power = if m.dpms_on {
+                    format!("{} {}", glyphs.power_on, tr("monitor.power_on"))
+                } else {
+                    format!("{} {}", glyphs.standby, tr("monitor.sleeping"))
+                };
+                format!(
+                    "{}@{:.0}  {:.2}  {:+}{:+}  {}",
+                    config.resolution,
+                    config.refresh_rate,
+                    config.scale_as_float(),
+                    m.x,
+                    m.y,
+                    power
+                )
+            } else {
+                tr("monitor.disabled").to_string()
+            };
+            let badges = m.badges().into_iter().map(|badge| {
+                Span::styled(
+                    format!(" [{}]", badge.label()),
+                    theme(app).fg(theme(app).accent),
+                )
+            });
+            let title = Line::from(
+                std::iter::once(Span::raw(title))
+                    .chain(badges)
+                    .collect::<Vec<_>>(),
+            );
+            let title = if is_new {
+                title.style(theme(app).fg(theme(app).positive))
+            } else {
+                title
+            };
+            let lines = vec![
+                title,
+                Line::from(format!("    {}", details)).style(theme(app).fg(theme(app).muted)),
+            ];
+            ListItem::new(
+                header
+                    .map(|(_, header)| header)
+                    .into_iter()
+                    .chain(lines)
+                    .collect::<Vec<_>>(),
+            )
         })
         .collect();
 
+    let title = tr_args("panes.monitors", &[("order", &app.monitor_order.label())]);
     let list = List::new(items)
-        .block(create_block("Monitors", is_focused))
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .bg(Color::Blue),
-        )
+        .block(create_block(app, &title, is_focused))
+        .highlight_style(theme(app).selection())
         .highlight_symbol(">> ");
 
-    f.render_stateful_widget(list, area, &mut app.monitor_list_state.clone());
+    let len = list.len();
+    f.render_stateful_widget(list, area, &mut app.monitor_list_state);
+    // Entries are two lines tall; the scrollbar counts lines.
+    render_scrollbar(f, app, area, len * 2, app.monitor_list_state.offset() * 2);
 }
 
-fn render_options_pane(f: &mut Frame, app: &App, area: Rect) {
+fn render_options_pane(f: &mut Frame, app: &mut App, area: Rect, compact: bool) {
     let is_focused = app.is_focused(FocusedPane::Options);
-    let block = create_block("Options", is_focused);
+    let block = create_block(
+        app,
+        if app.live_mode {
+            tr("panes.options_live")
+        } else {
+            tr("panes.options")
+        },
+        is_focused,
+    );
 
     let Some(idx) = app.selected_monitor() else {
         f.render_widget(block, area);
         return;
     };
 
+    let monitor = &app.monitors[idx];
     let config = &app.configs[idx];
-    let dpms_status_text = if config.dpms_on { "On" } else { "Off" };
+    let dpi_text = match monitor.dpi(&config.resolution) {
+        Some(dpi) if compact => tr_args("options.dpi", &[("dpi", &format!("{:.0}", dpi))]),
+        Some(dpi) => tr_args(
+            "options.dpi_size",
+            &[
+                ("dpi", &format!("{:.0}", dpi)),
+                ("width", &monitor.physical_width),
+                ("height", &monitor.physical_height),
+            ],
+        ),
+        None if compact => String::from("?"),
+        None => tr("options.unknown_size").to_string(),
+    };
+    let on_off = |on: bool| {
+        if on {
+            tr("options.on")
+        } else {
+            tr("options.off")
+        }
+    };
+    let centered = |text: String| ListItem::new(Line::from(text).alignment(Alignment::Center));
 
-    let items = vec![
-        ListItem::new(format!("{:<13} <{}>", "Resolution:", config.resolution)),
+    // Pending values that differ from what is running get the running
+    // value next to them.
+    let applied = &monitor.applied;
+    let pending = |text: String, differs: bool, live: String| {
+        if monitor.active && differs {
+            ListItem::new(format!(
+                "{} {}",
+                text,
+                tr_args("options.live", &[("value", &live)])
+            ))
+            .style(theme(app).fg(theme(app).warning))
+        } else {
+            ListItem::new(text)
+        }
+    };
+
+    let mut items = vec![
+        pending(
+            format!(
+                "{:<13} <{}> {}",
+                tr("options.resolution"),
+                config.resolution,
+                mode_notes(monitor, &config.resolution)
+            ),
+            config.resolution != applied.resolution,
+            applied.resolution.clone(),
+        ),
+        pending(
+            format!(
+                "{:<13} <{:.1} Hz>",
+                tr("options.refresh_rate"),
+                config.refresh_rate
+            ),
+            (config.refresh_rate - applied.refresh_rate).abs() >= 0.0005,
+            format!("{:.1} Hz", applied.refresh_rate),
+        ),
+        pending(
+            format!(
+                "{:<13} <{:.2}>",
+                tr("options.scale"),
+                config.scale_as_float()
+            ),
+            config.scale != applied.scale,
+            format!("{:.2}", applied.scale as f64 / 100.0),
+        ),
         ListItem::new(format!(
-            "{:<13} <{:.1} Hz>",
-            "Refresh Rate:", config.refresh_rate
+            "{:<13} <{}>",
+            tr("options.color"),
+            config.color_mode.label()
+        )),
+        centered(tr_args("options.suggest_scale", &[("dpi", &dpi_text)])),
+        ListItem::new(format!(
+            "{:<13} <{} px>",
+            tr("options.reserved_top"),
+            config.reserved[0]
+        )),
+        ListItem::new(format!(
+            "{:<13} <{} px>",
+            tr("options.reserved_bottom"),
+            config.reserved[1]
+        )),
+        ListItem::new(format!(
+            "{:<13} <{} px>",
+            tr("options.reserved_left"),
+            config.reserved[2]
+        )),
+        ListItem::new(format!(
+            "{:<13} <{} px>",
+            tr("options.reserved_right"),
+            config.reserved[3]
         )),
-        ListItem::new(format!("{:<13} <{:.2}>", "Scale:", config.scale_as_float())),
         ListItem::new(
-            Line::from("-> Apply Changes <-")
-                .style(Style::default().fg(Color::Green))
+            Line::from(tr("options.apply"))
+                .style(theme(app).fg(theme(app).positive))
                 .alignment(Alignment::Center),
         ),
-        ListItem::new(Line::from("Set as Main Screen").alignment(Alignment::Center)),
-        ListItem::new(Line::from("Extend Left").alignment(Alignment::Center)),
-        ListItem::new(Line::from("Extend Right").alignment(Alignment::Center)),
-        ListItem::new(Line::from("Mirror Another Monitor").alignment(Alignment::Center)),
-        ListItem::new(
-            Line::from(format!(
-                "Toggle Black Screen (Currently: {})",
-                dpms_status_text
-            ))
-            .alignment(Alignment::Center),
+        ListItem::new(format!(
+            "{:<13} <{}>",
+            tr("options.position"),
+            config.position_spec()
+        )),
+        centered(
+            if app.xwayland_primary.as_deref() == Some(monitor.name.as_str()) {
+                tr("options.set_main_primary")
+            } else {
+                tr("options.set_main")
+            }
+            .to_string(),
         ),
+        centered(tr("options.extend_left").to_string()),
+        centered(tr("options.extend_right").to_string()),
+        centered(match &monitor.mirror_of {
+            Some(source) => tr_args("options.stop_mirroring", &[("source", source)]),
+            None => tr("options.mirror").to_string(),
+        }),
+        centered(tr_args(
+            if compact {
+                "options.gaming"
+            } else {
+                "options.gaming_long"
+            },
+            &[("vrr", &on_off(config.vrr))],
+        )),
+        centered(tr("options.desktop").to_string()),
+        centered(tr_args(
+            if compact {
+                "options.black_screen"
+            } else {
+                "options.black_screen_long"
+            },
+            &[("state", &on_off(monitor.dpms_on))],
+        )),
+        ListItem::new(format!(
+            "{:<13} <{}>",
+            tr("options.lid_close"),
+            app.lid_rule.label()
+        )),
         ListItem::new(
-            Line::from("-> Save to File <-")
-                .style(Style::default().fg(Color::Cyan))
+            Line::from(tr("options.disable"))
+                .style(theme(app).fg(theme(app).negative))
                 .alignment(Alignment::Center),
         ),
+        ListItem::new(format!(
+            "{:<13} [{}]",
+            tr("options.persist"),
+            if config.persist { "x" } else { " " }
+        )),
+        ListItem::new(format!(
+            "{:<13} <{}>",
+            tr("options.save_disabled"),
+            if config.save_disabled {
+                tr("options.yes")
+            } else {
+                tr("options.no")
+            }
+        )),
         ListItem::new(
-            Line::from("-> Disable Monitor <-")
-                .style(Style::default().fg(Color::Red))
+            Line::from(tr("options.save"))
+                .style(theme(app).fg(theme(app).info))
                 .alignment(Alignment::Center),
         ),
     ];
+    // Section indices are final row positions, so insert them in order.
+    for &(idx, title) in &OPTION_SECTIONS {
+        let rule = glyphs(app).rule;
+        items.insert(
+            idx,
+            ListItem::new(Line::from(format!("{} {} {}", rule, tr(title), rule)))
+                .style(theme(app).fg(theme(app).muted).add_modifier(Modifier::BOLD)),
+        );
+    }
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme(app).selection());
+
+    let len = list.len();
+    f.render_stateful_widget(list, area, &mut app.option_list_state);
+    render_scrollbar(f, app, area, len, app.option_list_state.offset());
+}
+
+fn render_mode_picker(f: &mut Frame, app: &App, picker: &mut ModePicker) {
+    let area = centered_rect(50, 70, f.size());
+    f.render_widget(Clear, area);
+
+    let block = create_block(app, tr("popups.mode_picker"), true);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let filter = Paragraph::new(tr_args("popups.filter", &[("filter", &picker.filter)]))
+        .style(theme(app).fg(theme(app).warning))
+        .block(Block::default().borders(Borders::BOTTOM));
+    f.render_widget(filter, chunks[0]);
+
+    let monitor = app.selected_monitor().map(|idx| &app.monitors[idx]);
+    let mut previous_resolution: Option<&str> = None;
+    let items: Vec<ListItem> = picker
+        .visible()
+        .into_iter()
+        .map(|(resolution, rate)| {
+            let first = previous_resolution != Some(resolution.as_str());
+            previous_resolution = Some(resolution);
+            let (label, notes) = match monitor {
+                Some(monitor) if first => (resolution.as_str(), mode_notes(monitor, resolution)),
+                _ => ("", String::new()),
+            };
+            ListItem::new(format!("{:<12} {:>8.2} Hz  {}", label, rate, notes))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut picker.state);
+}
+
+/// Aspect ratio of a resolution, and whether it is the panel's native one,
+/// so picking a scaled-down mode is a conscious choice.
+fn mode_notes(monitor: &Monitor, resolution: &str) -> String {
+    let mut notes = monitor::aspect_ratio(resolution).unwrap_or_default();
+    if monitor.native_resolution.as_deref() == Some(resolution) {
+        notes.push_str(&format!(" ({})", tr("options.native")));
+    }
+    notes
+}
+
+fn render_palette(f: &mut Frame, app: &App, palette: &mut Palette) {
+    let area = centered_rect(50, 60, f.size());
+    f.render_widget(Clear, area);
+
+    let block = create_block(app, tr("popups.palette"), true);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let filter = Paragraph::new(format!("> {}_", palette.filter))
+        .style(theme(app).fg(theme(app).warning))
+        .block(Block::default().borders(Borders::BOTTOM));
+    f.render_widget(filter, chunks[0]);
+
+    let items: Vec<ListItem> = palette
+        .visible()
+        .into_iter()
+        .map(|action| ListItem::new(action.label()))
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut palette.state);
+}
+
+fn render_text_input(f: &mut Frame, app: &App, input: &TextInput) {
+    let theme = theme(app);
+    let area = centered_rect(50, 20, f.size());
+    f.render_widget(Clear, area);
+
+    let title = ascii_text(
+        app,
+        &tr_args("popups.text_input", &[("title", &input.title)]),
+    );
+    let (before, after) = input.split_at_cursor();
+    let mut after = after.chars();
+    let at_cursor = after
+        .next()
+        .map(String::from)
+        .unwrap_or_else(|| String::from(" "));
+    let field = Line::from(vec![
+        Span::raw(format!("{}: ", input.label)),
+        Span::styled(before, theme.fg(theme.warning)),
+        Span::styled(
+            at_cursor,
+            theme.fg(theme.warning).add_modifier(Modifier::REVERSED),
+        ),
+        Span::styled(after.as_str().to_string(), theme.fg(theme.warning)),
+    ]);
+
+    let mut lines = vec![field];
+    if let Some(error) = &input.error {
+        lines.push(
+            Line::from(tr_args("status.error", &[("text", error)])).style(theme.fg(theme.negative)),
+        );
+    }
+    if !input.help.is_empty() {
+        lines.push(Line::from(input.help.as_str()).style(theme.fg(theme.muted)));
+    }
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(create_block(app, &title, true));
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_workspaces(f: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .workspaces
+        .iter()
+        .map(|workspace| {
+            ListItem::new(format!(
+                "{:<12} {:<12} {}",
+                workspace.name,
+                workspace.monitor,
+                tr_args("workspaces.windows", &[("count", &workspace.windows)])
+            ))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(create_block(app, tr("panes.workspaces"), true))
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    let len = list.len();
+    f.render_stateful_widget(list, area, &mut app.workspace_list_state);
+    render_scrollbar(f, app, area, len, app.workspace_list_state.offset());
+}
+
+fn render_profiles(f: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .profiles
+        .iter()
+        .map(|name| ListItem::new(name.clone()))
+        .collect();
+
+    let list = List::new(items)
+        .block(create_block(app, tr("panes.profiles"), true))
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    let len = list.len();
+    f.render_stateful_widget(list, area, &mut app.profile_list_state);
+    render_scrollbar(f, app, area, len, app.profile_list_state.offset());
+}
+
+fn render_log(f: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .history
+        .entries()
+        .iter()
+        .rev()
+        .map(|entry| {
+            let (status, color) = if entry.success {
+                (tr("log.ok"), theme(app).positive)
+            } else {
+                (tr("log.failed"), theme(app).negative)
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", entry.timestamp.format("%H:%M:%S"))),
+                Span::styled(format!("{:<7}", status), Style::default().fg(color)),
+                Span::raw(entry.command.to_string()),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(create_block(app, tr("panes.log"), true))
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    let len = list.len();
+    f.render_stateful_widget(list, area, &mut app.log_list_state);
+    render_scrollbar(f, app, area, len, app.log_list_state.offset());
+}
+
+fn render_presets(f: &mut Frame, app: &App, state: &mut ListState) {
+    let area = centered_rect(40, 40, f.size());
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = Preset::ALL
+        .iter()
+        .map(|preset| ListItem::new(preset.label()))
+        .collect();
+
+    let list = List::new(items)
+        .block(create_block(app, tr("popups.presets"), true))
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, state);
+}
+
+fn render_review(f: &mut Frame, app: &App, state: &mut ListState) {
+    let area = centered_rect(80, 50, f.size());
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = app
+        .queue
+        .iter()
+        .map(|change| ListItem::new(format!("{:<10} {}", change.monitor, change.command)))
+        .collect();
+
+    let title = tr_args("popups.review", &[("count", &app.queue.len())]);
+    let list = List::new(items)
+        .block(create_block(app, &title, true))
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, state);
+}
+
+fn render_template(f: &mut Frame, app: &App, chooser: &mut TemplateChooser) {
+    let area = centered_rect(50, 40, f.size());
+    f.render_widget(Clear, area);
+
+    let (title, items): (String, Vec<ListItem>) = match chooser.template {
+        None => (
+            tr("popups.templates").to_string(),
+            Template::ALL
+                .iter()
+                .map(|template| ListItem::new(template.label()))
+                .collect(),
+        ),
+        Some(template) => {
+            let slot = chooser.chosen.len();
+            let title = tr_args(
+                "popups.template_slot",
+                &[
+                    ("template", &template.label()),
+                    ("slot", &template.slot_label(slot)),
+                    ("number", &(slot + 1)),
+                    ("count", &template.slots()),
+                ],
+            );
+            let items = chooser
+                .candidates(&app.monitors)
+                .into_iter()
+                .map(|i| {
+                    let monitor = &app.monitors[i];
+                    ListItem::new(format!("{}  {}", app.display_name(i), monitor.description))
+                })
+                .collect();
+            (title, items)
+        }
+    };
+
+    let list = List::new(items)
+        .block(create_block(app, &title, true))
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, &mut chooser.state);
+}
+
+fn render_setup(f: &mut Frame, app: &App, wizard: &mut SetupWizard) {
+    let area = centered_rect(60, 40, f.size());
+    f.render_widget(Clear, area);
+
+    let path = |path: std::io::Result<std::path::PathBuf>| {
+        path.map_or_else(|e| e.to_string(), |p| p.display().to_string())
+    };
+    let (question, choices): (&str, Vec<String>) = match wizard.step {
+        SetupStep::Primary => (
+            tr("setup.primary"),
+            SetupWizard::candidates(&app.monitors)
+                .into_iter()
+                .map(|i| {
+                    let monitor = &app.monitors[i];
+                    format!("{}  {}", app.display_name(i), monitor.description)
+                })
+                .collect(),
+        ),
+        SetupStep::Arrangement => (
+            tr("setup.arrangement"),
+            Arrangement::ALL
+                .iter()
+                .map(|arrangement| arrangement.label().to_string())
+                .collect(),
+        ),
+        SetupStep::SavePath => (
+            tr("setup.save_path"),
+            vec![
+                tr_args(
+                    "setup.shared",
+                    &[("path", &path(hyprconf::monitors_conf_path()))],
+                ),
+                tr_args(
+                    "setup.per_host",
+                    &[(
+                        "path",
+                        &path(
+                            profile::hostname()
+                                .and_then(|host| hyprconf::host_monitors_conf_path(&host)),
+                        ),
+                    )],
+                ),
+            ],
+        ),
+        SetupStep::Source => (
+            tr("setup.source"),
+            vec![
+                tr("setup.add_source").to_string(),
+                tr("setup.skip_source").to_string(),
+            ],
+        ),
+    };
+    let number = wizard.step as usize + 1;
+    let title = tr_args(
+        "popups.setup",
+        &[("question", &question), ("number", &number), ("count", &4)],
+    );
+
+    let list = List::new(choices.into_iter().map(ListItem::new).collect::<Vec<_>>())
+        .block(create_block(app, &title, true))
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, &mut wizard.state);
+}
+
+fn render_disable_confirm(f: &mut Frame, app: &App, confirm: &mut DisableConfirm) {
+    let area = centered_rect(60, 50, f.size());
+    f.render_widget(Clear, area);
+
+    let title = ascii_text(app, tr("popups.disable"));
+    let block = create_block(app, &title, true);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let window_count: usize = confirm.workspaces.iter().map(|w| w.windows).sum();
+    let workspace_names: Vec<&str> = confirm.workspaces.iter().map(|w| w.name.as_str()).collect();
+    let target = confirm
+        .target
+        .map_or_else(|| tr("popups.none").to_string(), |i| app.display_name(i));
+
+    let summary = Paragraph::new(vec![
+        Line::from(tr_args(
+            "popups.disable_summary",
+            &[
+                ("monitor", &app.display_name(confirm.monitor)),
+                ("windows", &window_count),
+                ("workspaces", &workspace_names.join(", ")),
+            ],
+        )),
+        Line::from(tr_args("popups.disable_target", &[("target", &target)])),
+    ])
+    .wrap(Wrap { trim: true })
+    .block(Block::default().borders(Borders::BOTTOM));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(summary, chunks[0]);
+
+    let list = List::new(
+        DISABLE_CHOICES
+            .iter()
+            .map(|&choice| ListItem::new(tr(choice))),
+    )
+    .highlight_style(theme(app).selection())
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut confirm.state);
+}
+
+fn render_reload_confirm(f: &mut Frame, app: &App, confirm: &mut ReloadConfirm) {
+    let area = centered_rect(60, 40, f.size());
+    f.render_widget(Clear, area);
+
+    let block = create_block(app, tr("popups.reload"), true);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let summary = Paragraph::new(tr_args(
+        "popups.reload_summary",
+        &[("monitors", &confirm.monitors.join(", "))],
+    ))
+    .wrap(Wrap { trim: true })
+    .block(Block::default().borders(Borders::BOTTOM));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(summary, chunks[0]);
+
+    let list = List::new(
+        RELOAD_CHOICES
+            .iter()
+            .map(|&choice| ListItem::new(tr(choice))),
+    )
+    .highlight_style(theme(app).selection())
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut confirm.state);
+}
+
+fn render_shrink_confirm(f: &mut Frame, app: &App, confirm: &mut ShrinkConfirm) {
+    let area = centered_rect(60, 40, f.size());
+    f.render_widget(Clear, area);
 
-    let list = List::new(items).block(block).highlight_style(
-        Style::default()
-            .add_modifier(Modifier::BOLD)
-            .bg(Color::Blue),
+    let title = ascii_text(app, tr("popups.shrink"));
+    let block = create_block(app, &title, true);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let window_count: usize = confirm.workspaces.iter().map(|w| w.windows).sum();
+    let workspace_names: Vec<&str> = confirm.workspaces.iter().map(|w| w.name.as_str()).collect();
+    let config = &app.configs[confirm.monitor];
+    let summary = Paragraph::new(tr_args(
+        "popups.shrink_summary",
+        &[
+            (
+                "mode",
+                &format!("{} x{:.2}", config.resolution, config.scale_as_float()),
+            ),
+            ("monitor", &app.display_name(confirm.monitor)),
+            ("percent", &format!("{:.0}", confirm.ratio * 100.0)),
+            ("windows", &window_count),
+            ("workspaces", &workspace_names.join(", ")),
+        ],
+    ))
+    .wrap(Wrap { trim: true })
+    .block(Block::default().borders(Borders::BOTTOM));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(summary, chunks[0]);
+
+    let list = List::new(
+        SHRINK_CHOICES
+            .iter()
+            .map(|&choice| ListItem::new(tr(choice))),
+    )
+    .highlight_style(theme(app).selection())
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut confirm.state);
+}
+
+fn render_cleanup(f: &mut Frame, app: &App, cleanup: &mut Cleanup) {
+    let area = centered_rect(60, 50, f.size());
+    f.render_widget(Clear, area);
+
+    let title = ascii_text(app, tr("popups.cleanup"));
+    let block = create_block(app, &title, true);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let summary = Paragraph::new(tr("popups.cleanup_summary"))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::BOTTOM));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(summary, chunks[0]);
+
+    let items: Vec<ListItem> = cleanup
+        .names
+        .iter()
+        .zip(&cleanup.prune)
+        .map(|(name, &prune)| {
+            ListItem::new(format!(
+                "[{}] {} ({})",
+                if prune { "x" } else { " " },
+                name,
+                if prune {
+                    tr("popups.cleanup_remove")
+                } else {
+                    tr("popups.cleanup_keep")
+                }
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut cleanup.state);
+}
+
+fn render_reconcile(f: &mut Frame, app: &App, reconcile: &mut Reconcile) {
+    let area = centered_rect(60, 50, f.size());
+    f.render_widget(Clear, area);
+
+    let title = ascii_text(
+        app,
+        &tr_args("popups.reconcile", &[("profile", &reconcile.profile.name)]),
     );
+    let block = create_block(app, &title, true);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let summary = Paragraph::new(tr("popups.reconcile_summary"))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::BOTTOM));
 
-    f.render_stateful_widget(list, area, &mut app.option_list_state.clone());
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(summary, chunks[0]);
+
+    let arrow = if app.config.ui.ascii { "->" } else { "→" };
+    let items: Vec<ListItem> = reconcile
+        .entries
+        .iter()
+        .map(|(i, output)| {
+            let wanted = &reconcile.profile.monitors[*i];
+            ListItem::new(format!(
+                "{} ({}) {} {}",
+                wanted.name,
+                wanted.mode,
+                arrow,
+                output.as_deref().unwrap_or(tr("popups.reconcile_skip"))
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(theme(app).selection())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[1], &mut reconcile.state);
 }
 
-fn render_instructions(f: &mut Frame, app: &App, area: Rect) {
-    let text = if let Some(msg) = &app.info_message {
-        msg.clone()
+fn render_details(f: &mut Frame, app: &App, details: &Details) {
+    let area = centered_rect(70, 80, f.size());
+    f.render_widget(Clear, area);
+
+    let title = ascii_text(
+        app,
+        &tr_args("popups.details", &[("title", &details.title)]),
+    );
+    let lines: Vec<Line> = details
+        .lines
+        .iter()
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .block(create_block(app, &title, true))
+        .scroll((details.scroll as u16, 0));
+
+    f.render_widget(paragraph, area);
+    render_scrollbar(f, app, area, details.lines.len(), details.scroll);
+}
+
+/// Draws a scrollbar over the right border of a bordered list in `area` when
+/// its `len` items don't all fit, positioned by the list's scroll `offset`.
+fn render_scrollbar(f: &mut Frame, app: &App, area: Rect, len: usize, offset: usize) {
+    let visible = area.height.saturating_sub(2) as usize;
+    if len <= visible {
+        return;
+    }
+
+    let mut state = ScrollbarState::new(len - visible).position(offset);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_symbol(Some(glyphs(app).scroll_track))
+        .thumb_symbol(glyphs(app).scroll_thumb);
+
+    f.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut state,
+    );
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+/// One line of context: the backend, monitor counts, unsaved changes and the
+/// last result. Queued messages replace it while they are up.
+fn render_status_bar(f: &mut Frame, app: &App, area: Rect, compact: bool) {
+    if let Some(message) = app.messages.current() {
+        let (color, text) = match message.severity {
+            Severity::Info => (theme(app).info, message.text.clone()),
+            Severity::Warning => (
+                theme(app).warning,
+                tr_args("status.warning", &[("text", &message.text)]),
+            ),
+            Severity::Error => (
+                theme(app).negative,
+                tr_args("status.error", &[("text", &message.text)]),
+            ),
+        };
+        let mut text = format!(" {}", text);
+        if app.messages.pending() > 0 {
+            text.push_str(&format!(" (+{})", app.messages.pending()));
+        }
+        f.render_widget(
+            Paragraph::new(Line::from(text).style(Style::default().fg(color))),
+            area,
+        );
+        return;
+    }
+
+    let separator = Span::styled(
+        format!(" {} ", glyphs(app).divider),
+        theme(app).fg(theme(app).muted),
+    );
+    let backend = match app.hyprland_version {
+        Some(version) if !compact => format!(" Hyprland {} (hyprctl)", version),
+        Some(version) => format!(" Hyprland {}", version),
+        None => String::from(" Hyprland (hyprctl)"),
+    };
+    let active = app.monitors.iter().filter(|m| m.active).count();
+    let unsaved = app.unsaved_count();
+
+    let mut spans = vec![
+        Span::raw(backend),
+        separator.clone(),
+        Span::raw(tr_args(
+            "status.active",
+            &[("active", &active), ("total", &app.monitors.len())],
+        )),
+        separator.clone(),
+        Span::styled(
+            tr_args("status.unsaved", &[("count", &unsaved)]),
+            if unsaved > 0 {
+                theme(app).fg(theme(app).warning)
+            } else {
+                Style::default()
+            },
+        ),
+    ];
+    if let Some(result) = app.last_result.as_ref().filter(|_| !compact) {
+        spans.push(separator);
+        spans.push(Span::styled(
+            tr_args("status.last", &[("result", result)]),
+            theme(app).fg(theme(app).muted),
+        ));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn render_instructions(f: &mut Frame, app: &App, area: Rect, compact: bool) {
+    let revert_at = app.live_change.as_ref().and_then(|change| change.revert_at);
+    let text = if let Some(revert_at) = revert_at {
+        let seconds = revert_at
+            .saturating_duration_since(std::time::Instant::now())
+            .as_secs()
+            + 1;
+        tr_args("hints.live_prompt", &[("seconds", &seconds)])
+    } else if compact {
+        ascii_text(
+            app,
+            tr(match app.tab {
+                Tab::Monitors => "hints.monitors_compact",
+                Tab::Workspaces => "hints.workspaces_compact",
+                Tab::Profiles => "hints.profiles_compact",
+                Tab::Log => "hints.log_compact",
+            }),
+        )
     } else {
-        String::from("Tab: Switch Panes | ↑/↓: Navigate | ←/→: Change Value | Enter: Execute Action | q: Quit")
+        let keys = &app.config.keys;
+        ascii_text(
+            app,
+            &tr_args(
+                match app.tab {
+                    Tab::Monitors => "hints.monitors",
+                    Tab::Workspaces => "hints.workspaces",
+                    Tab::Profiles => "hints.profiles",
+                    Tab::Log => "hints.log",
+                },
+                &[
+                    ("previous", &keys.profile_previous),
+                    ("next", &keys.profile_next),
+                ],
+            ),
+        )
     };
 
-    let color = if app.info_message.is_some() {
-        Color::Cyan
+    let color = if revert_at.is_some() {
+        theme(app).prompt
     } else {
-        Color::Yellow
+        theme(app).warning
     };
 
     let instructions = Paragraph::new(text)
-        .style(Style::default().fg(color))
+        .style(theme(app).fg(color))
         .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
         .block(
             Block::default()
                 .borders(Borders::TOP)
-                .border_style(Style::default().fg(Color::Reset)),
+                .border_style(Style::default()),
         );
 
     f.render_widget(instructions, area);
 }
 
-fn create_block(title: &str, is_focused: bool) -> Block<'_> {
+fn create_block<'a>(app: &App, title: &'a str, is_focused: bool) -> Block<'a> {
     Block::default()
         .borders(Borders::ALL)
         .title(title)
-        .border_style(Style::default().fg(if is_focused {
-            Color::Blue
+        .border_style(if is_focused {
+            theme(app).fg(theme(app).accent)
         } else {
-            Color::Reset
-        }))
+            Style::default()
+        })
 }
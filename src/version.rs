@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// Hyprland syntax that only newer releases understand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Feature {
+    /// The `cm` monitor option (sRGB, wide gamut and HDR presets).
+    ColorManagement,
+    /// `monitorv2 { ... }` blocks in place of `monitor=` lines.
+    MonitorV2,
+}
+
+impl Feature {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Feature::ColorManagement => "Color management",
+            Feature::MonitorV2 => "The monitorv2 syntax",
+        }
+    }
+
+    /// The first Hyprland release that accepts this syntax.
+    pub fn min_version(&self) -> HyprVersion {
+        match self {
+            Feature::ColorManagement => HyprVersion::new(0, 47, 0),
+            Feature::MonitorV2 => HyprVersion::new(0, 50, 0),
+        }
+    }
+}
+
+/// A Hyprland release number, as reported by `hyprctl version -j`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HyprVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl HyprVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses `0.45.2`, or a git tag like `v0.45.2-12-gabcdef`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('v');
+        let release = text.split('-').next()?;
+        let mut parts = release.split('.').map(|part| part.parse::<u32>().ok());
+        Some(Self::new(
+            parts.next()??,
+            parts.next()??,
+            parts.next().flatten().unwrap_or(0),
+        ))
+    }
+
+    /// Reads the version from `hyprctl version -j` output. Older releases
+    /// only report it as part of the git tag.
+    pub fn from_json(data: &serde_json::Value) -> Option<Self> {
+        data["version"]
+            .as_str()
+            .and_then(Self::parse)
+            .or_else(|| data["tag"].as_str().and_then(Self::parse))
+    }
+
+    pub fn supports(&self, feature: Feature) -> bool {
+        *self >= feature.min_version()
+    }
+}
+
+impl fmt::Display for HyprVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
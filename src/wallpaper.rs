@@ -0,0 +1,132 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// The program that draws wallpapers. Output geometry changes usually need
+/// the wallpaper set again, so it is re-issued after every apply.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum WallpaperBackend {
+    Hyprpaper,
+    Swww,
+    Swaybg,
+    /// `command` from the settings.
+    Command,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct WallpaperSettings {
+    /// Nothing is run until a backend is chosen.
+    pub backend: Option<WallpaperBackend>,
+    /// For the `command` backend: run through the shell with `{monitor}` and
+    /// `{path}` filled in, both already quoted.
+    pub command: String,
+    /// Image for monitors without an entry of their own.
+    pub default: Option<String>,
+    /// Image per monitor name.
+    pub monitors: BTreeMap<String, String>,
+}
+
+/// Per-monitor images a profile sets on top of the configured ones.
+pub type Wallpapers = BTreeMap<String, String>;
+
+/// The shell commands that set the wallpaper on each of `monitors`, with
+/// `overrides` (from a profile) taking precedence over the settings.
+pub fn commands(
+    settings: &WallpaperSettings,
+    overrides: &Wallpapers,
+    monitors: &[&str],
+) -> Vec<String> {
+    let Some(backend) = settings.backend else {
+        return Vec::new();
+    };
+    monitors
+        .iter()
+        .filter_map(|&monitor| {
+            let path = overrides
+                .get(monitor)
+                .or_else(|| settings.monitors.get(monitor))
+                .or(settings.default.as_ref())?;
+            let path = shellexpand::full(path).map_or_else(|_| path.clone(), |p| p.into_owned());
+            // swaybg keeps running; the instance for this output is
+            // found by its command line and replaced.
+            let running_swaybg = quote(&format!("^swaybg -o {} ", regex_escape(monitor)));
+            let (monitor, path) = (quote(monitor), quote(&path));
+            Some(match backend {
+                WallpaperBackend::Hyprpaper => format!(
+                    "hyprctl hyprpaper preload {path} && hyprctl hyprpaper wallpaper {monitor},{path}"
+                ),
+                WallpaperBackend::Swww => format!("swww img -o {monitor} {path}"),
+                WallpaperBackend::Swaybg => format!(
+                    "pkill -f {running_swaybg}; exec swaybg -o {monitor} -i {path} -m fill"
+                ),
+                WallpaperBackend::Command => settings
+                    .command
+                    .replace("{monitor}", &monitor)
+                    .replace("{path}", &path),
+            })
+        })
+        .collect()
+}
+
+/// `text` matched literally in the extended regex `pkill -f` takes.
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.^$|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// `text` as a single shell word.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_images_win_over_configured_ones() {
+        let settings = WallpaperSettings {
+            backend: Some(WallpaperBackend::Swww),
+            command: String::new(),
+            default: Some(String::from("/walls/default.png")),
+            monitors: [(String::from("DP-1"), String::from("/walls/left.png"))]
+                .into_iter()
+                .collect(),
+        };
+        let overrides: Wallpapers = [(String::from("HDMI-A-1"), String::from("/walls/it's.png"))]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            commands(&settings, &overrides, &["DP-1", "HDMI-A-1", "eDP-1"]),
+            [
+                "swww img -o 'DP-1' '/walls/left.png'",
+                "swww img -o 'HDMI-A-1' '/walls/it'\\''s.png'",
+                "swww img -o 'eDP-1' '/walls/default.png'",
+            ]
+        );
+        assert!(commands(&WallpaperSettings::default(), &overrides, &["DP-1"]).is_empty());
+    }
+
+    #[test]
+    fn swaybg_replaces_the_instance_for_the_output() {
+        let settings = WallpaperSettings {
+            backend: Some(WallpaperBackend::Swaybg),
+            default: Some(String::from("/walls/default.png")),
+            ..WallpaperSettings::default()
+        };
+        assert_eq!(
+            commands(&settings, &Wallpapers::new(), &["DP-1", "HEADLESS.2"]),
+            [
+                "pkill -f '^swaybg -o DP-1 '; exec swaybg -o 'DP-1' -i '/walls/default.png' -m fill",
+                "pkill -f '^swaybg -o HEADLESS\\.2 '; exec swaybg -o 'HEADLESS.2' -i '/walls/default.png' -m fill",
+            ]
+        );
+    }
+}
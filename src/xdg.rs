@@ -0,0 +1,72 @@
+use std::{
+    env,
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// A base directory from its XDG variable, or `fallback` under the home
+/// directory when the variable is unset, empty or relative, which the
+/// specification says to ignore.
+fn base_dir(var: Option<OsString>, home: Option<OsString>, fallback: &str) -> Option<PathBuf> {
+    var.map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| {
+            home.map(PathBuf::from)
+                .filter(|home| home.is_absolute())
+                .map(|home| home.join(fallback))
+        })
+}
+
+fn resolve(var: &str, fallback: &str) -> io::Result<PathBuf> {
+    base_dir(env::var_os(var), env::var_os("HOME"), fallback).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Neither {} nor HOME is set", var),
+        )
+    })
+}
+
+/// `$XDG_CONFIG_HOME`, `~/.config` by default: hypr-tui's settings and
+/// profiles, and Hyprland's own config.
+pub fn config_home() -> io::Result<PathBuf> {
+    resolve("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_STATE_HOME`, `~/.local/state` by default: logs and what hypr-tui
+/// remembers between runs.
+pub fn state_home() -> io::Result<PathBuf> {
+    resolve("XDG_STATE_HOME", ".local/state")
+}
+
+/// `relative` under [`config_home`].
+pub fn config_path(relative: impl AsRef<Path>) -> io::Result<PathBuf> {
+    config_home().map(|dir| dir.join(relative))
+}
+
+/// `relative` under [`state_home`].
+pub fn state_path(relative: impl AsRef<Path>) -> io::Result<PathBuf> {
+    state_home().map(|dir| dir.join(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_or_missing_variables_fall_back_to_home() {
+        let os = |s: &str| Some(OsString::from(s));
+        assert_eq!(
+            base_dir(os("/xdg/config"), os("/home/alex"), ".config"),
+            Some(PathBuf::from("/xdg/config"))
+        );
+        for var in [None, os(""), os("relative/config")] {
+            assert_eq!(
+                base_dir(var, os("/home/alex"), ".config"),
+                Some(PathBuf::from("/home/alex/.config"))
+            );
+        }
+        assert_eq!(base_dir(None, None, ".local/state"), None);
+        assert_eq!(base_dir(None, os("home"), ".local/state"), None);
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    env,
+    process::{Command, Stdio},
+};
+
+/// Whether X clients are running through XWayland and `xrandr` is there to
+/// talk to it.
+pub fn available() -> bool {
+    env::var_os("DISPLAY").is_some()
+        && Command::new("xrandr")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+}
+
+/// Makes `output` the X primary monitor, which many games and older X
+/// applications use instead of Hyprland's layout. XWayland names its outputs
+/// after the Wayland connectors.
+pub fn set_primary(output: &str) -> bool {
+    Command::new("xrandr")
+        .args(["--output", output, "--primary"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// The line saved to the monitor configuration so the primary output is set
+/// again at login. Hyprland runs it through a shell, so only plain connector
+/// names are written.
+pub fn config_line(output: &str) -> Option<String> {
+    let plain = !output.is_empty()
+        && output
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_".contains(c));
+    plain.then(|| format!("exec-once = xrandr --output {} --primary\n", output))
+}
+
+/// The primary output recorded by `config_line`, if any.
+pub fn find_primary(contents: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "exec-once" {
+            return None;
+        }
+        let args: Vec<&str> = value.split_whitespace().collect();
+        match args[..] {
+            ["xrandr", "--output", output, "--primary"] => Some(output.to_string()),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_primary_reads_back() {
+        let line = config_line("DP-1").unwrap();
+        assert_eq!(
+            find_primary(&format!("monitor=DP-1,disable\n{}", line)).as_deref(),
+            Some("DP-1")
+        );
+        assert_eq!(find_primary("exec-once = waybar"), None);
+    }
+
+    #[test]
+    fn refuses_names_a_shell_would_interpret() {
+        assert_eq!(config_line("DP-1; rm -rf ~"), None);
+        assert_eq!(config_line("$(id)"), None);
+        assert_eq!(config_line(""), None);
+    }
+}